@@ -0,0 +1,210 @@
+use crate::{Diagnostic, Severity};
+use std::fmt;
+
+/// Builds a [`Ctx`] from a format string, recording where it was
+/// written. Contexts are errors unless a `severity` is given first.
+///
+/// ```
+/// use erks::{ctx, ResultExt, Severity};
+///
+/// let path = "missing.toml";
+/// let err = std::fs::read_to_string(path)
+///     .ctx(ctx!("reading {path}"))
+///     .unwrap_err();
+/// assert!(err.to_string().starts_with("reading missing.toml (at "));
+///
+/// let err = std::fs::read_to_string(path)
+///     .ctx(ctx!(severity = Severity::Warning, "reading {path}"))
+///     .unwrap_err();
+/// assert!(err.to_string().starts_with("warning: reading missing.toml"));
+/// assert_eq!(erks::severity(&err), Severity::Warning);
+/// ```
+#[macro_export]
+macro_rules! ctx {
+	(severity = $severity:expr, $($arg:tt)+) => {
+		$crate::ctx!($($arg)+).with_severity($severity)
+	};
+	($($arg:tt)+) => {
+		$crate::Ctx::new(
+			format!($($arg)+),
+			$crate::Location {
+				file: file!(),
+				line: line!(),
+				function: $crate::function_name!(),
+			},
+		)
+	};
+}
+
+/// The path of the enclosing function, without closure suffixes.
+#[doc(hidden)]
+#[macro_export]
+macro_rules! function_name {
+	() => {{
+		fn f() {}
+		fn type_name_of<T>(_: T) -> &'static str {
+			std::any::type_name::<T>()
+		}
+		let mut name = type_name_of(f).trim_end_matches("::f");
+		while let Some(outer) = name.strip_suffix("::{{closure}}") {
+			name = outer;
+		}
+		name
+	}};
+}
+
+/// Where a context message was attached.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Location {
+	pub file: &'static str,
+	pub line: u32,
+	pub function: &'static str,
+}
+
+impl fmt::Display for Location {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		write!(f, "{}:{} in {}", self.file, self.line, self.function)
+	}
+}
+
+/// A context message tagged with its call site and severity.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Ctx {
+	pub message: String,
+	pub location: Location,
+	pub severity: Severity,
+}
+
+impl Ctx {
+	pub fn new(message: String, location: Location) -> Self {
+		Self {
+			message,
+			location,
+			severity: Severity::Error,
+		}
+	}
+
+	pub fn with_severity(mut self, severity: Severity) -> Self {
+		self.severity = severity;
+		self
+	}
+}
+
+/// Errors read as the bare message; anything milder is labelled.
+impl fmt::Display for Ctx {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		if self.severity != Severity::Error {
+			write!(f, "{}: ", self.severity)?;
+		}
+		write!(f, "{} (at {})", self.message, self.location)
+	}
+}
+
+impl From<Ctx> for Diagnostic {
+	fn from(ctx: Ctx) -> Self {
+		Diagnostic::new(ctx.severity, ctx.message).at(ctx.location)
+	}
+}
+
+/// The severity of the outermost [`Ctx`] on `error`, or
+/// [`Severity::Error`] when none was attached.
+pub fn severity(error: &anyhow::Error) -> Severity {
+	error
+		.downcast_ref::<Ctx>()
+		.map_or(Severity::Error, |ctx| ctx.severity)
+}
+
+/// Attaches a [`Ctx`] to anything `anyhow::Context` accepts, so
+/// every layer of an error chain says where it came from.
+pub trait ResultExt<T, E> {
+	fn ctx(self, ctx: Ctx) -> anyhow::Result<T>;
+
+	/// Like `ctx`, but only builds the message on failure.
+	fn with_ctx<F>(self, ctx: F) -> anyhow::Result<T>
+	where
+		F: FnOnce() -> Ctx;
+}
+
+impl<T, E, R> ResultExt<T, E> for R
+where
+	R: anyhow::Context<T, E>,
+{
+	fn ctx(self, ctx: Ctx) -> anyhow::Result<T> {
+		self.context(ctx)
+	}
+
+	fn with_ctx<F>(self, ctx: F) -> anyhow::Result<T>
+	where
+		F: FnOnce() -> Ctx,
+	{
+		self.with_context(ctx)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::ctx;
+
+	fn missing() -> anyhow::Result<String> {
+		std::fs::read_to_string("erks-missing.toml")
+			.ctx(ctx!("reading {}", "erks-missing.toml"))
+	}
+
+	#[test]
+	fn test_ctx_records_call_site() {
+		let ctx = ctx!("loading {}", 1);
+		assert_eq!(ctx.message, "loading 1");
+		assert_eq!(ctx.location.file, file!());
+		assert_eq!(
+			ctx.location.function,
+			"erks::context::tests::test_ctx_records_call_site"
+		);
+		assert_eq!(ctx.severity, Severity::Error);
+		assert_eq!(
+			ctx.to_string(),
+			format!("loading 1 (at {})", ctx.location)
+		);
+	}
+
+	#[test]
+	fn test_ctx_severity_formats_and_converts() {
+		let ctx = ctx!(severity = Severity::Note, "cache cold");
+		assert!(ctx.to_string().starts_with("note: cache cold (at "));
+
+		let diagnostic = Diagnostic::from(ctx.clone());
+		assert_eq!(diagnostic.severity, Severity::Note);
+		assert_eq!(diagnostic.location, Some(ctx.location));
+		assert_eq!(diagnostic.to_string(), ctx.to_string());
+	}
+
+	#[test]
+	fn test_ctx_chain_keeps_every_layer() {
+		let err = missing()
+			.with_ctx(|| {
+				ctx!(severity = Severity::Warning, "loading settings")
+			})
+			.unwrap_err();
+		let chain: Vec<String> =
+			err.chain().map(ToString::to_string).collect();
+		assert_eq!(chain.len(), 3);
+		assert!(
+			chain[0].starts_with("warning: loading settings (at ")
+		);
+		assert!(
+			chain[1].starts_with("reading erks-missing.toml (at ")
+		);
+		assert!(
+			chain[1].ends_with("in erks::context::tests::missing)")
+		);
+		assert_eq!(severity(&err), Severity::Warning);
+		assert_eq!(
+			severity(&missing().unwrap_err()),
+			Severity::Error
+		);
+		assert_eq!(
+			severity(&anyhow::anyhow!("plain")),
+			Severity::Error
+		);
+	}
+}
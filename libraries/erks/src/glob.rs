@@ -0,0 +1,107 @@
+use std::path::{Path, PathBuf};
+
+/// A glob that could not be expanded, or an entry it could not read,
+/// always naming the pattern involved.
+#[derive(Debug, thiserror::Error)]
+pub enum GlobError {
+	#[error("Invalid glob pattern '{pattern}': {source}")]
+	Pattern {
+		pattern: String,
+		#[source]
+		source: glob::PatternError,
+	},
+	#[error("Failed to read {} while expanding '{pattern}'", path.display())]
+	Entry {
+		pattern: String,
+		/// The path reached when the walk failed.
+		path: PathBuf,
+		#[source]
+		source: std::io::Error,
+	},
+}
+
+impl GlobError {
+	pub fn pattern(&self) -> &str {
+		match self {
+			Self::Pattern { pattern, .. }
+			| Self::Entry { pattern, .. } => pattern,
+		}
+	}
+
+	/// The path that failed, if the pattern itself was valid.
+	pub fn path(&self) -> Option<&Path> {
+		match self {
+			Self::Pattern { .. } => None,
+			Self::Entry { path, .. } => Some(path),
+		}
+	}
+
+	fn entry(pattern: &str, error: glob::GlobError) -> Self {
+		Self::Entry {
+			pattern: pattern.to_string(),
+			path: error.path().to_path_buf(),
+			source: error.into_error(),
+		}
+	}
+}
+
+/// Expands `pattern`, stopping at the first entry that cannot be read.
+///
+/// ```
+/// use erks::glob::{expand, GlobError};
+///
+/// let err = expand("src/[").unwrap_err();
+/// assert!(matches!(err, GlobError::Pattern { .. }));
+/// assert_eq!(err.pattern(), "src/[");
+/// ```
+pub fn expand(pattern: &str) -> Result<Vec<PathBuf>, GlobError> {
+	paths(pattern)?
+		.map(|entry| entry.map_err(|e| GlobError::entry(pattern, e)))
+		.collect()
+}
+
+/// Expands every pattern, collecting what matched and what failed instead
+/// of aborting on the first bad pattern or entry.
+///
+/// ```
+/// use erks::glob::partition;
+///
+/// let (matches, errors) = partition(["Cargo.toml", "src/[", "*.nothing"]);
+/// assert_eq!(matches, [std::path::PathBuf::from("Cargo.toml")]);
+/// assert_eq!(errors.len(), 1);
+/// assert_eq!(errors[0].pattern(), "src/[");
+/// ```
+pub fn partition<I, S>(patterns: I) -> (Vec<PathBuf>, Vec<GlobError>)
+where
+	I: IntoIterator<Item = S>,
+	S: AsRef<str>,
+{
+	let mut matches = Vec::new();
+	let mut errors = Vec::new();
+
+	for pattern in patterns {
+		let pattern = pattern.as_ref();
+		match paths(pattern) {
+			Ok(entries) => {
+				for entry in entries {
+					match entry {
+						Ok(path) => matches.push(path),
+						Err(e) => {
+							errors.push(GlobError::entry(pattern, e))
+						}
+					}
+				}
+			}
+			Err(e) => errors.push(e),
+		}
+	}
+
+	(matches, errors)
+}
+
+fn paths(pattern: &str) -> Result<glob::Paths, GlobError> {
+	glob::glob(pattern).map_err(|source| GlobError::Pattern {
+		pattern: pattern.to_string(),
+		source,
+	})
+}
@@ -0,0 +1,194 @@
+use reqwest::{header::RETRY_AFTER, Response};
+use std::{error::Error, fmt, time::Duration};
+
+pub use reqwest::{Method, StatusCode};
+
+/// Longest response body kept on an [`HttpError`], in bytes.
+pub const BODY_LIMIT: usize = 512;
+
+/// A failed HTTP request with whatever is known about it: the request,
+/// the response status and headers that matter for retrying, and the start
+/// of the body the server sent back.
+///
+/// ```
+/// use erks::http::{HttpError, Method, StatusCode};
+/// use std::time::Duration;
+///
+/// let err = HttpError::new(Method::GET, "https://api.example.com/items")
+///     .with_status(StatusCode::TOO_MANY_REQUESTS)
+///     .with_retry_after(Duration::from_secs(30))
+///     .with_body("slow down");
+///
+/// assert!(err.is_client_error() && err.is_rate_limited());
+/// assert!(err.is_retryable());
+/// assert_eq!(
+///     err.to_string(),
+///     "GET https://api.example.com/items returned 429 Too Many Requests \
+///      (retry after 30s): slow down"
+/// );
+/// ```
+#[derive(Debug, Default)]
+pub struct HttpError {
+	pub method: Option<Method>,
+	pub url: Option<String>,
+	pub status: Option<StatusCode>,
+	pub retry_after: Option<Duration>,
+	/// At most [`BODY_LIMIT`] bytes of the response body.
+	pub body: Option<String>,
+	pub source: Option<reqwest::Error>,
+}
+
+impl HttpError {
+	pub fn new<U: AsRef<str>>(method: Method, url: U) -> Self {
+		Self {
+			method: Some(method),
+			url: Some(url.as_ref().to_string()),
+			..Self::default()
+		}
+	}
+
+	/// Captures the status, `Retry-After` and body of a response.
+	pub async fn from_response(response: Response) -> Self {
+		let status = response.status();
+		let url = response.url().to_string();
+		let retry_after = response
+			.headers()
+			.get(RETRY_AFTER)
+			.and_then(|value| value.to_str().ok())
+			.and_then(|value| value.trim().parse().ok())
+			.map(Duration::from_secs);
+		let body = response.text().await.ok();
+
+		Self {
+			url: Some(url),
+			status: Some(status),
+			retry_after,
+			..Self::default()
+		}
+		.with_body(body.unwrap_or_default())
+	}
+
+	/// Passes successful responses through and turns 4xx and 5xx ones
+	/// into errors; the replacement for `Response::error_for_status`.
+	pub async fn check(response: Response) -> Result<Response, Self> {
+		let status = response.status();
+		if status.is_client_error() || status.is_server_error() {
+			return Err(Self::from_response(response).await);
+		}
+		Ok(response)
+	}
+
+	pub fn with_method(mut self, method: Method) -> Self {
+		self.method = Some(method);
+		self
+	}
+
+	pub fn with_status(mut self, status: StatusCode) -> Self {
+		self.status = Some(status);
+		self
+	}
+
+	pub fn with_retry_after(mut self, delay: Duration) -> Self {
+		self.retry_after = Some(delay);
+		self
+	}
+
+	/// Keeps the body, trimmed and cut to [`BODY_LIMIT`] bytes. Empty
+	/// bodies are dropped.
+	pub fn with_body<S: AsRef<str>>(mut self, body: S) -> Self {
+		let body = body.as_ref().trim();
+		self.body = match body.len() {
+			0 => None,
+			len if len <= BODY_LIMIT => Some(body.to_string()),
+			_ => {
+				let mut end = BODY_LIMIT;
+				while !body.is_char_boundary(end) {
+					end -= 1;
+				}
+				Some(format!("{}…", &body[..end]))
+			}
+		};
+		self
+	}
+
+	/// A 4xx status.
+	pub fn is_client_error(&self) -> bool {
+		self.status.is_some_and(|status| status.is_client_error())
+	}
+
+	/// A 5xx status.
+	pub fn is_server_error(&self) -> bool {
+		self.status.is_some_and(|status| status.is_server_error())
+	}
+
+	/// 429 Too Many Requests.
+	pub fn is_rate_limited(&self) -> bool {
+		self.status == Some(StatusCode::TOO_MANY_REQUESTS)
+	}
+
+	/// The request timed out, or the server said it did (408, 504).
+	pub fn is_timeout(&self) -> bool {
+		self.source.as_ref().is_some_and(reqwest::Error::is_timeout)
+			|| matches!(
+				self.status,
+				Some(
+					StatusCode::REQUEST_TIMEOUT
+						| StatusCode::GATEWAY_TIMEOUT
+				)
+			)
+	}
+
+	/// Whether sending the same request again later may succeed.
+	pub fn is_retryable(&self) -> bool {
+		self.is_rate_limited()
+			|| self.is_server_error()
+			|| self.is_timeout()
+			|| self
+				.source
+				.as_ref()
+				.is_some_and(reqwest::Error::is_connect)
+	}
+}
+
+/// Keeps the URL and status `reqwest` knows about.
+impl From<reqwest::Error> for HttpError {
+	fn from(source: reqwest::Error) -> Self {
+		Self {
+			url: source.url().map(ToString::to_string),
+			status: source.status(),
+			source: Some(source),
+			..Self::default()
+		}
+	}
+}
+
+impl fmt::Display for HttpError {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		match (&self.method, &self.url) {
+			(Some(method), Some(url)) => {
+				write!(f, "{} {}", method, url)?
+			}
+			(Some(method), None) => write!(f, "{}", method)?,
+			(None, Some(url)) => write!(f, "{}", url)?,
+			(None, None) => write!(f, "HTTP request")?,
+		}
+
+		match self.status {
+			Some(status) => write!(f, " returned {}", status)?,
+			None => write!(f, " failed")?,
+		}
+		if let Some(delay) = self.retry_after {
+			write!(f, " (retry after {}s)", delay.as_secs())?;
+		}
+		if let Some(body) = &self.body {
+			write!(f, ": {}", body)?;
+		}
+		Ok(())
+	}
+}
+
+impl Error for HttpError {
+	fn source(&self) -> Option<&(dyn Error + 'static)> {
+		self.source.as_ref().map(|source| source as _)
+	}
+}
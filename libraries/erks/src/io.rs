@@ -0,0 +1,154 @@
+use std::{
+	fmt,
+	path::{Path, PathBuf},
+};
+
+/// What was being done to a path when an IO error occurred.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Operation {
+	Open,
+	Read,
+	Write,
+	ReadDir,
+	CreateDir,
+	Remove,
+	Rename,
+	Copy,
+	Metadata,
+}
+
+impl fmt::Display for Operation {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		let verb = match self {
+			Self::Open => "open",
+			Self::Read => "read",
+			Self::Write => "write",
+			Self::ReadDir => "list directory",
+			Self::CreateDir => "create directory",
+			Self::Remove => "remove",
+			Self::Rename => "rename",
+			Self::Copy => "copy",
+			Self::Metadata => "read metadata of",
+		};
+		write!(f, "{}", verb)
+	}
+}
+
+/// An IO error that always names the path and operation involved.
+#[derive(Debug, thiserror::Error)]
+#[error("Failed to {operation} {}", path.display())]
+pub struct IoError {
+	pub operation: Operation,
+	pub path: PathBuf,
+	#[source]
+	pub source: std::io::Error,
+}
+
+impl IoError {
+	pub fn new<P: AsRef<Path>>(
+		operation: Operation,
+		path: P,
+		source: std::io::Error,
+	) -> Self {
+		Self {
+			operation,
+			path: path.as_ref().to_path_buf(),
+			source,
+		}
+	}
+
+	pub fn open<P: AsRef<Path>>(
+		path: P,
+		source: std::io::Error,
+	) -> Self {
+		Self::new(Operation::Open, path, source)
+	}
+
+	pub fn read<P: AsRef<Path>>(
+		path: P,
+		source: std::io::Error,
+	) -> Self {
+		Self::new(Operation::Read, path, source)
+	}
+
+	pub fn write<P: AsRef<Path>>(
+		path: P,
+		source: std::io::Error,
+	) -> Self {
+		Self::new(Operation::Write, path, source)
+	}
+
+	pub fn read_dir<P: AsRef<Path>>(
+		path: P,
+		source: std::io::Error,
+	) -> Self {
+		Self::new(Operation::ReadDir, path, source)
+	}
+
+	pub fn create_dir<P: AsRef<Path>>(
+		path: P,
+		source: std::io::Error,
+	) -> Self {
+		Self::new(Operation::CreateDir, path, source)
+	}
+
+	pub fn remove<P: AsRef<Path>>(
+		path: P,
+		source: std::io::Error,
+	) -> Self {
+		Self::new(Operation::Remove, path, source)
+	}
+
+	pub fn rename<P: AsRef<Path>>(
+		path: P,
+		source: std::io::Error,
+	) -> Self {
+		Self::new(Operation::Rename, path, source)
+	}
+
+	pub fn copy<P: AsRef<Path>>(
+		path: P,
+		source: std::io::Error,
+	) -> Self {
+		Self::new(Operation::Copy, path, source)
+	}
+
+	pub fn metadata<P: AsRef<Path>>(
+		path: P,
+		source: std::io::Error,
+	) -> Self {
+		Self::new(Operation::Metadata, path, source)
+	}
+
+	pub fn kind(&self) -> std::io::ErrorKind {
+		self.source.kind()
+	}
+}
+
+/// Attaches the path and operation to a `std::io::Result`.
+///
+/// ```
+/// use erks::io::{IoResultExt, Operation};
+///
+/// let err = std::fs::read("missing.toml")
+///     .at(Operation::Read, "missing.toml")
+///     .unwrap_err();
+/// assert_eq!(err.to_string(), "Failed to read missing.toml");
+/// ```
+pub trait IoResultExt<T> {
+	fn at<P: AsRef<Path>>(
+		self,
+		operation: Operation,
+		path: P,
+	) -> Result<T, IoError>;
+}
+
+impl<T> IoResultExt<T> for std::io::Result<T> {
+	fn at<P: AsRef<Path>>(
+		self,
+		operation: Operation,
+		path: P,
+	) -> Result<T, IoError> {
+		self.map_err(|source| IoError::new(operation, path, source))
+	}
+}
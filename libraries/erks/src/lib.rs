@@ -1,3 +1,16 @@
+mod context;
+mod panic;
+
+#[cfg(feature = "glob")]
+pub mod glob;
+#[cfg(feature = "http")]
+pub mod http;
+pub mod io;
+pub mod report;
+
 pub use anyhow::{self, Context, Result as AnyhowResult};
+pub use context::{severity, Ctx, Location, ResultExt};
+pub use panic::{install_panic_hook, PanicHook};
+pub use report::{Diagnostic, Reporter, Severity, Soft};
 pub use std::io::Error as IOError;
 pub use thiserror::{self, Error as ThisError};
@@ -0,0 +1,230 @@
+use crate::Severity;
+use std::{
+	backtrace::{Backtrace, BacktraceStatus},
+	env, fs,
+	io::{self, stderr, IsTerminal, Write},
+	panic::{self, PanicHookInfo},
+	path::{Path, PathBuf},
+	thread,
+	time::{SystemTime, UNIX_EPOCH},
+};
+
+/// Replaces the default panic output with a formatted report.
+///
+/// The report shows the message, location and thread, plus the
+/// backtrace when `RUST_BACKTRACE` is set. Use [`PanicHook`] to also
+/// write crash report files.
+pub fn install_panic_hook() {
+	PanicHook::default().install();
+}
+
+/// Configures how panics are reported.
+#[derive(Debug, Clone, Default)]
+pub struct PanicHook {
+	/// Directory for crash report files, if any are written.
+	pub report_dir: Option<PathBuf>,
+}
+
+impl PanicHook {
+	pub fn new() -> Self {
+		Self::default()
+	}
+
+	/// Writes a crash report with a full backtrace into `dir`.
+	pub fn with_report_dir<P: Into<PathBuf>>(
+		mut self,
+		dir: P,
+	) -> Self {
+		self.report_dir = Some(dir.into());
+		self
+	}
+
+	/// Writes crash reports into the system temporary directory.
+	pub fn with_reports(self) -> Self {
+		self.with_report_dir(env::temp_dir())
+	}
+
+	pub fn install(self) {
+		panic::set_hook(Box::new(move |info| self.report(info)));
+	}
+
+	fn report(&self, info: &PanicHookInfo) {
+		let crash = Crash::new(info);
+		let mut output = crash.summary(use_color());
+
+		let backtrace = Backtrace::capture();
+		if backtrace.status() == BacktraceStatus::Captured {
+			output.push_str(&format!("\nbacktrace:\n{}", backtrace));
+		} else {
+			output.push_str(
+				"\nnote: run with `RUST_BACKTRACE=1` to see a backtrace",
+			);
+		}
+
+		if let Some(dir) = &self.report_dir {
+			match crash.write(dir) {
+				Ok(path) => output.push_str(&format!(
+					"\nnote: crash report written to {}",
+					path.display()
+				)),
+				Err(e) => output.push_str(&format!(
+					"\nnote: failed to write crash report: {}",
+					e
+				)),
+			}
+		}
+
+		eprintln!("{}", output);
+	}
+}
+
+struct Crash {
+	program: String,
+	message: String,
+	location: String,
+	thread: String,
+}
+
+impl Crash {
+	fn new(info: &PanicHookInfo) -> Self {
+		let payload = info.payload();
+		let message = payload
+			.downcast_ref::<&str>()
+			.map(|s| s.to_string())
+			.or_else(|| payload.downcast_ref::<String>().cloned())
+			.unwrap_or_else(|| String::from("Box<dyn Any>"));
+		let location = info
+			.location()
+			.map(|l| {
+				format!("{}:{}:{}", l.file(), l.line(), l.column())
+			})
+			.unwrap_or_else(|| String::from("<unknown>"));
+
+		Self {
+			program: program(),
+			message,
+			location,
+			thread: thread::current()
+				.name()
+				.unwrap_or("<unnamed>")
+				.to_string(),
+		}
+	}
+
+	/// Styled like any other [`Severity::Error`] diagnostic.
+	fn summary(&self, color: bool) -> String {
+		format!(
+			"{}: {} panicked: {}\n  --> {}\n  thread: {}",
+			Severity::Error.styled(color),
+			self.program,
+			self.message,
+			self.location,
+			self.thread
+		)
+	}
+
+	/// Writes the crash report, returning its path.
+	fn write(&self, dir: &Path) -> io::Result<PathBuf> {
+		let timestamp = SystemTime::now()
+			.duration_since(UNIX_EPOCH)
+			.map(|d| d.as_secs())
+			.unwrap_or_default();
+		let path = dir.join(format!(
+			"{}-crash-{}.log",
+			self.program, timestamp
+		));
+
+		fs::create_dir_all(dir)?;
+		let mut file = fs::File::create(&path)?;
+		self.render(
+			&mut file,
+			timestamp,
+			&Backtrace::force_capture(),
+		)?;
+
+		Ok(path)
+	}
+
+	/// The crash report as written to file, without color.
+	fn render(
+		&self,
+		out: &mut impl Write,
+		timestamp: u64,
+		backtrace: &Backtrace,
+	) -> io::Result<()> {
+		writeln!(
+			out,
+			"{}\n  os: {} {}\n  time: {}\n\nbacktrace:\n{}",
+			self.summary(false),
+			env::consts::OS,
+			env::consts::ARCH,
+			timestamp,
+			backtrace
+		)
+	}
+}
+
+fn program() -> String {
+	env::current_exe()
+		.ok()
+		.and_then(|exe| {
+			exe.file_stem().map(|s| s.to_string_lossy().into())
+		})
+		.unwrap_or_else(|| String::from("program"))
+}
+
+fn use_color() -> bool {
+	env::var_os("NO_COLOR").is_none() && stderr().is_terminal()
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	fn crash() -> Crash {
+		Crash {
+			program: "demo".to_string(),
+			message: "index out of bounds".to_string(),
+			location: "src/main.rs:3:5".to_string(),
+			thread: "main".to_string(),
+		}
+	}
+
+	#[test]
+	fn test_summary_is_styled_by_severity() {
+		let crash = crash();
+		assert_eq!(
+			crash.summary(false),
+			"error: demo panicked: index out of bounds\n  \
+			 --> src/main.rs:3:5\n  thread: main"
+		);
+		assert!(crash
+			.summary(true)
+			.starts_with("\x1b[1;31merror\x1b[0m: "));
+	}
+
+	#[test]
+	fn test_crash_report_renders_and_writes() {
+		let crash = crash();
+		let mut buffer = Vec::new();
+		crash
+			.render(&mut buffer, 42, &Backtrace::disabled())
+			.unwrap();
+		let report = String::from_utf8(buffer).unwrap();
+		assert!(report.starts_with(&crash.summary(false)));
+		assert!(report.contains(&format!("os: {}", env::consts::OS)));
+		assert!(report.contains("time: 42\n\nbacktrace:\n"));
+
+		let dir = env::temp_dir()
+			.join(format!("erks-{}", std::process::id()));
+		let path = crash.write(&dir).unwrap();
+		let written = fs::read_to_string(&path).unwrap();
+		fs::remove_dir_all(&dir).unwrap();
+		assert!(path
+			.file_name()
+			.unwrap()
+			.to_string_lossy()
+			.starts_with("demo-crash-"));
+		assert!(written.starts_with(&crash.summary(false)));
+	}
+}
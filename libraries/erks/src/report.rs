@@ -0,0 +1,287 @@
+use crate::Location;
+use std::{cell::RefCell, fmt, mem};
+
+/// How serious a reported problem is, from least to most.
+#[derive(
+	Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash,
+)]
+pub enum Severity {
+	Note,
+	Warning,
+	Error,
+}
+
+impl fmt::Display for Severity {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		let label = match self {
+			Self::Note => "note",
+			Self::Warning => "warning",
+			Self::Error => "error",
+		};
+		write!(f, "{}", label)
+	}
+}
+
+impl Severity {
+	/// The label in bold, colored by severity when `color` is set.
+	pub fn styled(self, color: bool) -> String {
+		if !color {
+			return self.to_string();
+		}
+		let code = match self {
+			Self::Note => "1;36",
+			Self::Warning => "1;33",
+			Self::Error => "1;31",
+		};
+		format!("\x1b[{}m{}\x1b[0m", code, self)
+	}
+}
+
+/// A non-fatal problem recorded while work carried on.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Diagnostic {
+	pub severity: Severity,
+	pub message: String,
+	pub location: Option<Location>,
+}
+
+impl Diagnostic {
+	pub fn new<M: fmt::Display>(
+		severity: Severity,
+		message: M,
+	) -> Self {
+		Self {
+			severity,
+			message: message.to_string(),
+			location: None,
+		}
+	}
+
+	pub fn at(mut self, location: Location) -> Self {
+		self.location = Some(location);
+		self
+	}
+}
+
+impl fmt::Display for Diagnostic {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		write!(f, "{}: {}", self.severity, self.message)?;
+		if let Some(location) = &self.location {
+			write!(f, " (at {})", location)?;
+		}
+		Ok(())
+	}
+}
+
+/// Collects diagnostics so a batch can finish before they are shown.
+///
+/// Diagnostics below the threshold are dropped as they arrive.
+#[derive(Debug, Clone)]
+pub struct Reporter {
+	pub threshold: Severity,
+	diagnostics: Vec<Diagnostic>,
+}
+
+impl Default for Reporter {
+	fn default() -> Self {
+		Self {
+			threshold: Severity::Warning,
+			diagnostics: Vec::new(),
+		}
+	}
+}
+
+impl Reporter {
+	pub fn new() -> Self {
+		Self::default()
+	}
+
+	pub fn with_threshold(mut self, threshold: Severity) -> Self {
+		self.threshold = threshold;
+		self
+	}
+
+	pub fn push(&mut self, diagnostic: Diagnostic) {
+		if diagnostic.severity >= self.threshold {
+			self.diagnostics.push(diagnostic);
+		}
+	}
+
+	pub fn report<M: fmt::Display>(
+		&mut self,
+		severity: Severity,
+		message: M,
+	) {
+		self.push(Diagnostic::new(severity, message));
+	}
+
+	pub fn note<M: fmt::Display>(&mut self, message: M) {
+		self.report(Severity::Note, message);
+	}
+
+	pub fn warn<M: fmt::Display>(&mut self, message: M) {
+		self.report(Severity::Warning, message);
+	}
+
+	pub fn error<M: fmt::Display>(&mut self, message: M) {
+		self.report(Severity::Error, message);
+	}
+
+	pub fn diagnostics(&self) -> &[Diagnostic] {
+		&self.diagnostics
+	}
+
+	pub fn is_empty(&self) -> bool {
+		self.diagnostics.is_empty()
+	}
+
+	/// The most serious severity recorded so far.
+	pub fn worst(&self) -> Option<Severity> {
+		self.diagnostics.iter().map(|d| d.severity).max()
+	}
+
+	/// Whether anything at or above `severity` was recorded.
+	pub fn has(&self, severity: Severity) -> bool {
+		self.worst().is_some_and(|worst| worst >= severity)
+	}
+
+	/// Takes every recorded diagnostic, leaving the reporter empty.
+	pub fn drain(&mut self) -> Vec<Diagnostic> {
+		mem::take(&mut self.diagnostics)
+	}
+
+	/// Renders one diagnostic per line.
+	pub fn render(&self) -> String {
+		self.diagnostics
+			.iter()
+			.map(ToString::to_string)
+			.collect::<Vec<_>>()
+			.join("\n")
+	}
+
+	/// Fails if anything at or above `fail_at` was recorded, otherwise
+	/// hands back the diagnostics for the caller to show.
+	pub fn finish(
+		mut self,
+		fail_at: Severity,
+	) -> anyhow::Result<Vec<Diagnostic>> {
+		if self.has(fail_at) {
+			anyhow::bail!("{}", self.render());
+		}
+		Ok(self.drain())
+	}
+}
+
+/// Turns failures into diagnostics instead of stopping the caller.
+pub trait Soft<T> {
+	/// Records an error as a diagnostic of `severity`, returning
+	/// `None` so the caller can skip the item and carry on.
+	fn soft(
+		self,
+		reporter: &mut Reporter,
+		severity: Severity,
+	) -> Option<T>;
+}
+
+impl<T, E: fmt::Display> Soft<T> for Result<T, E> {
+	fn soft(
+		self,
+		reporter: &mut Reporter,
+		severity: Severity,
+	) -> Option<T> {
+		self.map_err(|e| reporter.report(severity, e)).ok()
+	}
+}
+
+thread_local! {
+	static REPORTER: RefCell<Reporter> = RefCell::new(Reporter::new());
+}
+
+/// Records a diagnostic on this thread's reporter.
+pub fn emit(diagnostic: Diagnostic) {
+	REPORTER.with(|reporter| reporter.borrow_mut().push(diagnostic));
+}
+
+/// Records a warning on this thread's reporter.
+pub fn warn<M: fmt::Display>(message: M) {
+	emit(Diagnostic::new(Severity::Warning, message));
+}
+
+/// Sets which severities this thread's reporter keeps.
+pub fn set_threshold(threshold: Severity) {
+	REPORTER
+		.with(|reporter| reporter.borrow_mut().threshold = threshold);
+}
+
+/// Takes everything recorded on this thread's reporter.
+pub fn drain() -> Vec<Diagnostic> {
+	REPORTER.with(|reporter| reporter.borrow_mut().drain())
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn test_threshold_drops_lesser_diagnostics() {
+		let mut reporter = Reporter::new();
+		reporter.note("skipped");
+		reporter.warn("kept");
+		assert_eq!(reporter.diagnostics().len(), 1);
+		assert_eq!(reporter.worst(), Some(Severity::Warning));
+
+		let mut reporter =
+			Reporter::new().with_threshold(Severity::Note);
+		reporter.note("kept");
+		assert!(!reporter.has(Severity::Warning));
+		assert!(reporter.has(Severity::Note));
+	}
+
+	#[test]
+	fn test_finish_fails_at_severity() {
+		let mut reporter = Reporter::new();
+		reporter.warn("slow disk");
+		reporter.error("missing config");
+		let err =
+			reporter.clone().finish(Severity::Error).unwrap_err();
+		assert_eq!(
+			err.to_string(),
+			"warning: slow disk\nerror: missing config"
+		);
+
+		let mut reporter = Reporter::new();
+		reporter.warn("slow disk");
+		let diagnostics = reporter.finish(Severity::Error).unwrap();
+		assert_eq!(
+			diagnostics,
+			[Diagnostic::new(Severity::Warning, "slow disk")]
+		);
+	}
+
+	#[test]
+	fn test_soft_records_errors() {
+		let mut reporter = Reporter::new();
+		let ok: Result<u8, &str> = Ok(1);
+		let err: Result<u8, &str> = Err("bad entry");
+		assert_eq!(ok.soft(&mut reporter, Severity::Error), Some(1));
+		assert_eq!(err.soft(&mut reporter, Severity::Warning), None);
+		assert_eq!(reporter.render(), "warning: bad entry");
+	}
+
+	#[test]
+	fn test_thread_reporter_drains() {
+		set_threshold(Severity::Warning);
+		emit(Diagnostic::new(Severity::Note, "dropped"));
+		warn("first");
+		std::thread::spawn(|| warn("other thread")).join().unwrap();
+		assert_eq!(
+			drain(),
+			[Diagnostic::new(Severity::Warning, "first")]
+		);
+		assert!(drain().is_empty());
+
+		set_threshold(Severity::Note);
+		emit(Diagnostic::new(Severity::Note, "kept"));
+		assert_eq!(drain().len(), 1);
+	}
+}
@@ -0,0 +1,70 @@
+use super::zones::print;
+use chrono::NaiveDate;
+use intime::zones;
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+pub enum Error {
+    #[error(transparent)]
+    Zone(#[from] intime::Error),
+
+    #[error("Invalid date '{0}', expected YYYY-MM-DD")]
+    InvalidDate(String),
+
+    #[error(transparent)]
+    Print(#[from] super::zones::Error),
+}
+
+pub type Result<T> = std::result::Result<T, Error>;
+
+#[derive(clap::Parser)]
+#[command(about = "Convert a time of day between time zones")]
+pub struct Command {
+    /// Time of day, e.g. 14:00 or 2:30pm
+    pub time: String,
+
+    /// Zone the time is given in (defaults to the local zone)
+    #[arg(long)]
+    pub from: Option<String>,
+
+    /// Zones to convert to (defaults to the local zone)
+    #[arg(long, num_args = 1..)]
+    pub to: Vec<String>,
+
+    /// Date to use instead of today, as YYYY-MM-DD
+    #[arg(long)]
+    pub date: Option<String>,
+
+    /// Print JSON instead of a table
+    #[arg(long)]
+    pub json: bool,
+}
+
+impl Command {
+    pub fn execute(&self) -> Result<()> {
+        let from = match &self.from {
+            Some(zone) => zones::find(zone)?,
+            None => zones::local(),
+        };
+        let date = self
+            .date
+            .as_deref()
+            .map(|date| {
+                NaiveDate::parse_from_str(date, "%Y-%m-%d")
+                    .map_err(|_| Error::InvalidDate(date.to_string()))
+            })
+            .transpose()?;
+
+        let source = zones::at(zones::parse_time(&self.time)?, date, from)?;
+
+        let mut times = vec![source];
+        if self.to.is_empty() {
+            times.push(source.with_timezone(&zones::local()));
+        }
+        for zone in &self.to {
+            times.push(source.with_timezone(&zones::find(zone)?));
+        }
+
+        Ok(print(&times, self.json)?)
+    }
+}
@@ -1,4 +1,4 @@
-use super::{fetch, greet};
+use super::{at, fetch, greet, stopwatch, timer, zones};
 use clap::{CommandFactory, Parser, Subcommand};
 use std::process;
 use thiserror::Error;
@@ -17,6 +17,18 @@ pub enum Error {
     #[error("Fetch error: {0}")]
     Fetch(#[from] fetch::Error),
 
+    #[error(transparent)]
+    At(#[from] at::Error),
+
+    #[error(transparent)]
+    Zones(#[from] zones::Error),
+
+    #[error("Timer error: {0}")]
+    Timer(#[from] timer::Error),
+
+    #[error("Stopwatch error: {0}")]
+    Stopwatch(#[from] stopwatch::Error),
+
     #[error("Unknown error occurred")]
     Unknown,
 }
@@ -26,7 +38,7 @@ type Result<T> = std::result::Result<T, Error>;
 #[derive(Parser)]
 #[command(author, version, about, long_about = None)]
 pub struct Cli {
-    /// Duration in seconds (optionally followed by 's', 'm', 'h', or 'd')
+    /// Duration such as 90, 45m or 1h30m
     #[arg(required = false)]
     pub duration: Option<String>,
 
@@ -38,6 +50,10 @@ pub struct Cli {
 pub enum Commands {
     Greet(greet::Command),
     Fetch(fetch::Command),
+    At(at::Command),
+    Zones(zones::Command),
+    Timer(timer::Command),
+    Stopwatch(stopwatch::Command),
 }
 
 impl Cli {
@@ -52,6 +68,18 @@ impl Cli {
             (_, Some(Commands::Fetch(cmd))) => {
                 cmd.execute()?;
             }
+            (_, Some(Commands::At(cmd))) => {
+                cmd.execute()?;
+            }
+            (_, Some(Commands::Zones(cmd))) => {
+                cmd.execute()?;
+            }
+            (_, Some(Commands::Timer(cmd))) => {
+                cmd.execute()?;
+            }
+            (_, Some(Commands::Stopwatch(cmd))) => {
+                cmd.execute()?;
+            }
             (None, None) => {
                 Cli::command().print_help()?;
                 process::exit(1);
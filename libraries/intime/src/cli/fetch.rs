@@ -19,7 +19,7 @@ pub type Result<T> = std::result::Result<T, Error>;
 
 #[derive(clap::Parser)]
 pub struct Command {
-    /// Duration in seconds (optionally followed by 's', 'm', 'h', or 'd')
+    /// Duration such as 90, 45m or 1h30m
     pub duration: String,
 }
 
@@ -31,19 +31,9 @@ impl Command {
         Ok(())
     }
 
-    fn parse_duration(&self, duration: &str) -> Result<u32> {
-        let num_str = duration.trim_end_matches(|c: char| !c.is_ascii_digit());
-        let unit_str = duration.trim_start_matches(num_str);
-        let num: u32 = num_str
-            .parse()
-            .map_err(|_| Error::InvalidDuration("Invalid number".to_string()))?;
-
-        match unit_str {
-            "s" | "" => Ok(num),
-            "m" => Ok(num * 60),
-            "h" => Ok(num * 3600),
-            "d" => Ok(num * 86400),
-            _ => Err(Error::InvalidDuration("Invalid unit".to_string())),
-        }
+    fn parse_duration(&self, duration: &str) -> Result<u64> {
+        intime::parse(duration)
+            .map(|duration| duration.as_secs())
+            .map_err(|e| Error::InvalidDuration(e.to_string()))
     }
 }
@@ -0,0 +1,81 @@
+use crossterm::{
+    cursor,
+    event::{self, Event, KeyCode, KeyEvent, KeyEventKind, KeyModifiers},
+    execute,
+    terminal::{self, ClearType},
+};
+use intime::Stopwatch;
+use std::{
+    io::{self, Write},
+    time::Duration,
+};
+
+/// How a live display ended.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Outcome {
+    Finished,
+    Quit,
+}
+
+/// Restores the terminal when the display ends, even on error.
+struct RawMode;
+
+impl RawMode {
+    fn enable() -> io::Result<Self> {
+        terminal::enable_raw_mode()?;
+        execute!(io::stdout(), cursor::Hide)?;
+        Ok(Self)
+    }
+}
+
+impl Drop for RawMode {
+    fn drop(&mut self) {
+        let _ = execute!(io::stdout(), cursor::Show);
+        let _ = terminal::disable_raw_mode();
+    }
+}
+
+/// Redraws a single status line until `render` reports completion or the user
+/// quits. Space or `p` pauses, `r` resets, `q`, Esc or Ctrl-C quits.
+pub fn run(
+    watch: &mut Stopwatch,
+    mut render: impl FnMut(&Stopwatch) -> (String, bool),
+) -> io::Result<Outcome> {
+    let _raw = RawMode::enable()?;
+    let mut stdout = io::stdout();
+
+    loop {
+        let (line, finished) = render(watch);
+        let status = if watch.is_paused() { "  [paused]" } else { "" };
+        execute!(stdout, terminal::Clear(ClearType::CurrentLine))?;
+        write!(stdout, "\r{}{}", line, status)?;
+        stdout.flush()?;
+
+        if finished {
+            write!(stdout, "\r\n")?;
+            return Ok(Outcome::Finished);
+        }
+
+        if !event::poll(Duration::from_millis(100))? {
+            continue;
+        }
+        if let Event::Key(KeyEvent {
+            code,
+            modifiers,
+            kind: KeyEventKind::Press,
+            ..
+        }) = event::read()?
+        {
+            match code {
+                KeyCode::Char(' ') | KeyCode::Char('p') => watch.toggle(),
+                KeyCode::Char('r') => watch.reset(),
+                KeyCode::Char('q') | KeyCode::Esc => break,
+                KeyCode::Char('c') if modifiers.contains(KeyModifiers::CONTROL) => break,
+                _ => {}
+            }
+        }
+    }
+
+    write!(stdout, "\r\n")?;
+    Ok(Outcome::Quit)
+}
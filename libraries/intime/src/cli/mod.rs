@@ -1,5 +1,10 @@
+mod at;
 mod commands;
 mod fetch;
 mod greet;
+mod live;
+mod stopwatch;
+mod timer;
+mod zones;
 
 pub use commands::run;
@@ -0,0 +1,27 @@
+use super::live;
+use intime::{clock, Stopwatch};
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+pub enum Error {
+    #[error("I/O error: {0}")]
+    IO(#[from] std::io::Error),
+}
+
+pub type Result<T> = std::result::Result<T, Error>;
+
+#[derive(clap::Parser)]
+#[command(about = "Count up from zero until stopped")]
+pub struct Command {}
+
+impl Command {
+    pub fn execute(&self) -> Result<()> {
+        let mut watch = Stopwatch::new();
+        live::run(&mut watch, |watch| {
+            (format!("Stopwatch  {}", clock(watch.elapsed())), false)
+        })?;
+
+        println!("Elapsed: {}", clock(watch.elapsed()));
+        Ok(())
+    }
+}
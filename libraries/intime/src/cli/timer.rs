@@ -0,0 +1,76 @@
+use super::live::{self, Outcome};
+use intime::{clock, Stopwatch};
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+pub enum Error {
+    #[error("Invalid duration: {0}")]
+    InvalidDuration(#[from] intime::Error),
+
+    #[error("I/O error: {0}")]
+    IO(#[from] std::io::Error),
+
+    #[error("Failed to send notification: {0}")]
+    Notify(String),
+}
+
+pub type Result<T> = std::result::Result<T, Error>;
+
+#[derive(clap::Parser)]
+#[command(about = "Count down a duration, e.g. 25m for a pomodoro")]
+pub struct Command {
+    /// Duration such as 90, 25m or 1h30m
+    pub duration: String,
+
+    /// Label shown beside the countdown and in the notification
+    #[arg(short, long)]
+    pub label: Option<String>,
+
+    /// Skip the desktop notification when the timer ends
+    #[arg(long)]
+    pub quiet: bool,
+}
+
+impl Command {
+    pub fn execute(&self) -> Result<()> {
+        let total = intime::parse(&self.duration)?;
+        let label = self.label.as_deref().unwrap_or("Timer");
+
+        let mut watch = Stopwatch::new();
+        let outcome = live::run(&mut watch, |watch| {
+            let remaining = total.saturating_sub(watch.elapsed());
+            (
+                format!("{}  {}", label, clock(remaining)),
+                remaining.is_zero(),
+            )
+        })?;
+
+        if outcome == Outcome::Finished {
+            print!("\x07");
+            // The countdown itself finished, so a missing notification
+            // daemon is not worth failing over
+            if !self.quiet {
+                if let Err(e) = notify(label, &format!("{} is up", clock(total))) {
+                    eprintln!("Warning: {}", e);
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(feature = "notify")]
+fn notify(summary: &str, body: &str) -> Result<()> {
+    notify_rust::Notification::new()
+        .summary(summary)
+        .body(body)
+        .appname("intime")
+        .show()
+        .map(|_| ())
+        .map_err(|e| Error::Notify(e.to_string()))
+}
+
+#[cfg(not(feature = "notify"))]
+fn notify(_summary: &str, _body: &str) -> Result<()> {
+    Ok(())
+}
@@ -0,0 +1,80 @@
+use chrono::{DateTime, Utc};
+use chrono_tz::Tz;
+use intime::zones;
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+pub enum Error {
+    #[error(transparent)]
+    Zone(#[from] intime::Error),
+
+    #[error("JSON error: {0}")]
+    Json(#[from] serde_json::Error),
+}
+
+pub type Result<T> = std::result::Result<T, Error>;
+
+#[derive(clap::Parser)]
+#[command(about = "Show the current time in other cities or time zones")]
+pub struct Command {
+    /// Cities or IANA zone names (defaults to the local zone)
+    pub cities: Vec<String>,
+
+    /// Print JSON instead of a table
+    #[arg(long)]
+    pub json: bool,
+}
+
+impl Command {
+    pub fn execute(&self) -> Result<()> {
+        let now = Utc::now();
+        let times = if self.cities.is_empty() {
+            vec![now.with_timezone(&zones::local())]
+        } else {
+            self.cities
+                .iter()
+                .map(|city| Ok(now.with_timezone(&zones::find(city)?)))
+                .collect::<Result<Vec<_>>>()?
+        };
+
+        print(&times, self.json)
+    }
+}
+
+/// Prints one row per time, as a table or a JSON array.
+pub fn print(times: &[DateTime<Tz>], json: bool) -> Result<()> {
+    if json {
+        let rows: Vec<_> = times
+            .iter()
+            .map(|time| {
+                serde_json::json!({
+                    "zone": time.timezone().name(),
+                    "time": time.to_rfc3339(),
+                    "offset": zones::offset(time),
+                    "abbreviation": time.format("%Z").to_string(),
+                })
+            })
+            .collect();
+        println!("{}", serde_json::to_string_pretty(&rows)?);
+        return Ok(());
+    }
+
+    let width = times
+        .iter()
+        .map(|time| time.timezone().name().len())
+        .max()
+        .unwrap_or_default()
+        .max("ZONE".len());
+
+    println!("{:<width$}  {:<16}  {:<6}  ABBR", "ZONE", "TIME", "OFFSET");
+    for time in times {
+        println!(
+            "{:<width$}  {}  {}  {}",
+            time.timezone().name(),
+            time.format("%Y-%m-%d %H:%M"),
+            zones::offset(time),
+            time.format("%Z")
+        );
+    }
+    Ok(())
+}
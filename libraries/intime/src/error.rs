@@ -0,0 +1,27 @@
+use thiserror::Error;
+
+#[derive(Error, Debug, Clone, PartialEq)]
+pub enum Error {
+    #[error("No duration provided")]
+    Empty,
+
+    #[error("Invalid number in duration: {0}")]
+    InvalidNumber(String),
+
+    #[error("Unknown duration unit: {0}")]
+    UnknownUnit(String),
+
+    #[error("Duration out of range: {0}")]
+    OutOfRange(String),
+
+    #[error("Unknown time zone: {0}")]
+    UnknownZone(String),
+
+    #[error("Ambiguous time zone '{0}', could be: {1}")]
+    AmbiguousZone(String, String),
+
+    #[error("Invalid time: {0}")]
+    InvalidTime(String),
+}
+
+pub type Result<T> = std::result::Result<T, Error>;
@@ -0,0 +1,192 @@
+use chrono::TimeDelta;
+
+/// Units used when describing a duration, smallest first. Months and years
+/// are 30 and 365 days, matching [`Info`](crate::Info).
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Unit {
+    #[default]
+    Second,
+    Minute,
+    Hour,
+    Day,
+    Week,
+    Month,
+    Year,
+}
+
+impl Unit {
+    const ALL: [Unit; 7] = [
+        Unit::Second,
+        Unit::Minute,
+        Unit::Hour,
+        Unit::Day,
+        Unit::Week,
+        Unit::Month,
+        Unit::Year,
+    ];
+
+    pub fn seconds(self) -> i64 {
+        match self {
+            Unit::Second => 1,
+            Unit::Minute => 60,
+            Unit::Hour => 3_600,
+            Unit::Day => 86_400,
+            Unit::Week => 604_800,
+            Unit::Month => 2_592_000,
+            Unit::Year => 31_536_000,
+        }
+    }
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Unit::Second => "second",
+            Unit::Minute => "minute",
+            Unit::Hour => "hour",
+            Unit::Day => "day",
+            Unit::Week => "week",
+            Unit::Month => "month",
+            Unit::Year => "year",
+        }
+    }
+}
+
+/// How a duration is reduced to a whole number of its largest unit.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum Rounding {
+    /// 90 minutes is "1 hour".
+    #[default]
+    Floor,
+    /// 90 minutes is "2 hours".
+    Nearest,
+    /// 61 minutes is "2 hours".
+    Ceil,
+}
+
+/// Describes durations in words, e.g. "2 hours" or "in 3 days".
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct Humanize {
+    /// Smallest unit reported; anything shorter is "just now".
+    pub granularity: Unit,
+    pub rounding: Rounding,
+}
+
+impl Humanize {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_granularity(mut self, granularity: Unit) -> Self {
+        self.granularity = granularity;
+        self
+    }
+
+    pub fn with_rounding(mut self, rounding: Rounding) -> Self {
+        self.rounding = rounding;
+        self
+    }
+
+    fn largest(&self, delta: TimeDelta) -> Option<(i64, Unit)> {
+        let seconds = delta.num_seconds().unsigned_abs() as i64;
+
+        let mut units = Unit::ALL
+            .into_iter()
+            .filter(|unit| *unit >= self.granularity)
+            .rev()
+            .peekable();
+
+        while let Some(unit) = units.next() {
+            let is_smallest = units.peek().is_none();
+            if seconds < unit.seconds() && !is_smallest {
+                continue;
+            }
+
+            let value = self.round(seconds, unit.seconds());
+            if value == 0 {
+                return None;
+            }
+            return Some((value, unit));
+        }
+        None
+    }
+
+    /// The size of `delta` in its largest whole unit, ignoring direction.
+    /// Rounding up carries into the next unit, so 59.6 minutes is 1 hour.
+    pub fn duration(&self, delta: TimeDelta) -> Option<(i64, Unit)> {
+        let (value, unit) = self.largest(delta)?;
+        let next = Unit::ALL.into_iter().find(|next| *next > unit);
+        match next {
+            Some(next) if value * unit.seconds() >= next.seconds() => {
+                Some((value * unit.seconds() / next.seconds(), next))
+            }
+            _ => Some((value, unit)),
+        }
+    }
+
+    /// "2 hours", or "no time" when shorter than the granularity.
+    pub fn format(&self, delta: TimeDelta) -> String {
+        match self.duration(delta) {
+            Some((value, unit)) => plural(value, unit),
+            None => "no time".to_string(),
+        }
+    }
+
+    /// "in 2 hours" for future deltas, "2 hours ago" for past ones.
+    pub fn relative(&self, delta: TimeDelta) -> String {
+        match self.duration(delta) {
+            None => "just now".to_string(),
+            Some((value, unit)) if delta > TimeDelta::zero() => {
+                format!("in {}", plural(value, unit))
+            }
+            Some((value, unit)) => format!("{} ago", plural(value, unit)),
+        }
+    }
+
+    fn round(&self, seconds: i64, unit: i64) -> i64 {
+        match self.rounding {
+            Rounding::Floor => seconds / unit,
+            Rounding::Nearest => (seconds + unit / 2) / unit,
+            Rounding::Ceil => (seconds + unit - 1) / unit,
+        }
+    }
+}
+
+/// Describes `delta` relative to now with the default options, e.g.
+/// "in 2 hours" or "3 days ago".
+pub fn humanize(delta: TimeDelta) -> String {
+    Humanize::default().relative(delta)
+}
+
+fn plural(value: i64, unit: Unit) -> String {
+    let suffix = if value == 1 { "" } else { "s" };
+    format!("{} {}{}", value, unit.name(), suffix)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parse;
+
+    #[test]
+    fn test_parse_and_humanize() {
+        assert_eq!(parse("1h30m").unwrap().as_secs(), 5_400);
+        assert_eq!(parse("2d 4h").unwrap().as_secs(), 187_200);
+        assert_eq!(parse("90").unwrap().as_secs(), 90);
+        assert_eq!(parse("250ms").unwrap().as_millis(), 250);
+        assert!(parse("3 fortnights").is_err());
+        assert!(parse("").is_err());
+
+        assert_eq!(humanize(TimeDelta::hours(2)), "in 2 hours");
+        assert_eq!(humanize(-TimeDelta::days(3)), "3 days ago");
+        assert_eq!(humanize(TimeDelta::zero()), "just now");
+
+        let ninety = TimeDelta::minutes(90);
+        assert_eq!(Humanize::new().format(ninety), "1 hour");
+        let nearest = Humanize::new().with_rounding(Rounding::Nearest);
+        assert_eq!(nearest.format(ninety), "2 hours");
+        assert_eq!(nearest.format(TimeDelta::seconds(3_590)), "1 hour");
+
+        let daily = Humanize::new().with_granularity(Unit::Day);
+        assert_eq!(daily.relative(-TimeDelta::hours(5)), "just now");
+        assert_eq!(daily.format(TimeDelta::hours(50)), "2 days");
+    }
+}
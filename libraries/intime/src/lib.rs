@@ -1,8 +1,17 @@
 // mod commands;
 mod display;
+mod error;
+mod humanize;
 mod info;
+mod parse;
+mod stopwatch;
 pub mod utils;
+pub mod zones;
 
 // pub use cli::Commands;
 // pub use commands::greet;
+pub use error::{Error, Result};
+pub use humanize::{humanize, Humanize, Rounding, Unit};
 pub use info::{Info, Options};
+pub use parse::parse;
+pub use stopwatch::{clock, Stopwatch};
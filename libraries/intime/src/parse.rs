@@ -0,0 +1,58 @@
+use crate::{Error, Result};
+use std::time::Duration;
+
+/// Parses a duration such as `"90"`, `"1h30m"`, `"2d 4h"` or `"1.5h"`.
+///
+/// Components are a number followed by a unit; a bare number is seconds.
+/// Accepted units are `ns`, `us` (or `µs`), `ms`, `s`, `m`, `h`, `d`, `w`
+/// and their long forms. Numbers may use `_` as a separator.
+///
+/// ```
+/// use std::time::Duration;
+///
+/// assert_eq!(intime::parse("1h30m").unwrap(), Duration::from_secs(5_400));
+/// assert!(intime::parse("99999999999999999999h").is_err());
+/// ```
+pub fn parse(input: &str) -> Result<Duration> {
+    let mut rest = input.trim();
+    if rest.is_empty() {
+        return Err(Error::Empty);
+    }
+
+    let mut total = 0.0;
+    while !rest.is_empty() {
+        let number_end = rest
+            .find(|c: char| !(c.is_ascii_digit() || c == '.' || c == '_'))
+            .unwrap_or(rest.len());
+        let (number, tail) = rest.split_at(number_end);
+        let tail = tail.trim_start();
+        let unit_end = tail
+            .find(|c: char| !c.is_alphabetic())
+            .unwrap_or(tail.len());
+        let (unit, tail) = tail.split_at(unit_end);
+
+        let value: f64 = number
+            .replace('_', "")
+            .parse()
+            .map_err(|_| Error::InvalidNumber(input.to_string()))?;
+        total += value * unit_seconds(unit).ok_or_else(|| Error::UnknownUnit(unit.to_string()))?;
+
+        rest = tail.trim_start_matches(|c: char| c.is_whitespace() || c == ',');
+    }
+
+    Duration::try_from_secs_f64(total).map_err(|_| Error::OutOfRange(input.to_string()))
+}
+
+fn unit_seconds(unit: &str) -> Option<f64> {
+    Some(match unit.to_ascii_lowercase().as_str() {
+        "ns" | "nanos" | "nanosecond" | "nanoseconds" => 1e-9,
+        "us" | "µs" | "micros" | "microsecond" | "microseconds" => 1e-6,
+        "ms" | "msec" | "millis" | "millisecond" | "milliseconds" => 0.001,
+        "" | "s" | "sec" | "secs" | "second" | "seconds" => 1.0,
+        "m" | "min" | "mins" | "minute" | "minutes" => 60.0,
+        "h" | "hr" | "hrs" | "hour" | "hours" => 3_600.0,
+        "d" | "day" | "days" => 86_400.0,
+        "w" | "wk" | "wks" | "week" | "weeks" => 604_800.0,
+        _ => return None,
+    })
+}
@@ -0,0 +1,96 @@
+use std::time::{Duration, Instant};
+
+/// A pausable stopwatch measuring wall-clock time while running.
+#[derive(Debug, Clone, Copy)]
+pub struct Stopwatch {
+    started: Option<Instant>,
+    elapsed: Duration,
+}
+
+impl Default for Stopwatch {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Stopwatch {
+    /// Creates a running stopwatch.
+    pub fn new() -> Self {
+        Self {
+            started: Some(Instant::now()),
+            elapsed: Duration::ZERO,
+        }
+    }
+
+    pub fn elapsed(&self) -> Duration {
+        self.elapsed
+            + self
+                .started
+                .map_or(Duration::ZERO, |started| started.elapsed())
+    }
+
+    pub fn is_paused(&self) -> bool {
+        self.started.is_none()
+    }
+
+    pub fn pause(&mut self) {
+        if let Some(started) = self.started.take() {
+            self.elapsed += started.elapsed();
+        }
+    }
+
+    pub fn resume(&mut self) {
+        if self.started.is_none() {
+            self.started = Some(Instant::now());
+        }
+    }
+
+    pub fn toggle(&mut self) {
+        if self.is_paused() {
+            self.resume();
+        } else {
+            self.pause();
+        }
+    }
+
+    /// Zeroes the elapsed time, keeping the running state.
+    pub fn reset(&mut self) {
+        self.elapsed = Duration::ZERO;
+        if self.started.is_some() {
+            self.started = Some(Instant::now());
+        }
+    }
+}
+
+/// Formats a duration as `MM:SS`, or `H:MM:SS` from one hour up.
+pub fn clock(duration: Duration) -> String {
+    let seconds = duration.as_secs();
+    let (hours, minutes, seconds) = (seconds / 3600, seconds / 60 % 60, seconds % 60);
+
+    if hours > 0 {
+        format!("{}:{:02}:{:02}", hours, minutes, seconds)
+    } else {
+        format!("{:02}:{:02}", minutes, seconds)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_pause_and_clock() {
+        let mut watch = Stopwatch::new();
+        watch.pause();
+        let paused = watch.elapsed();
+        std::thread::sleep(Duration::from_millis(5));
+        assert!(watch.is_paused());
+        assert_eq!(watch.elapsed(), paused);
+
+        watch.reset();
+        assert_eq!(watch.elapsed(), Duration::ZERO);
+
+        assert_eq!(clock(Duration::from_secs(25 * 60)), "25:00");
+        assert_eq!(clock(Duration::from_secs(3_725)), "1:02:05");
+    }
+}
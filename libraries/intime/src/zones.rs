@@ -0,0 +1,113 @@
+use crate::{Error, Result};
+use chrono::{DateTime, Local, NaiveDate, NaiveTime, Offset, TimeZone};
+use chrono_tz::{Tz, TZ_VARIANTS};
+
+/// Finds a zone by IANA name (`Asia/Tokyo`) or city (`tokyo`, `new york`),
+/// ignoring case. A partial city name is accepted if it matches one zone.
+pub fn find(query: &str) -> Result<Tz> {
+    if let Ok(tz) = query.parse::<Tz>() {
+        return Ok(tz);
+    }
+
+    let wanted = query.trim().to_lowercase().replace(' ', "_");
+    let city = |tz: &Tz| {
+        tz.name()
+            .rsplit('/')
+            .next()
+            .unwrap_or_default()
+            .to_lowercase()
+    };
+
+    if let Some(tz) = TZ_VARIANTS
+        .iter()
+        .find(|tz| tz.name().eq_ignore_ascii_case(&wanted) || city(tz) == wanted)
+    {
+        return Ok(*tz);
+    }
+
+    let partial: Vec<&Tz> = TZ_VARIANTS
+        .iter()
+        .filter(|tz| city(tz).contains(&wanted))
+        .collect();
+    match partial.as_slice() {
+        [] => Err(Error::UnknownZone(query.to_string())),
+        [tz] => Ok(**tz),
+        many => Err(Error::AmbiguousZone(
+            query.to_string(),
+            many.iter()
+                .map(|tz| tz.name())
+                .collect::<Vec<_>>()
+                .join(", "),
+        )),
+    }
+}
+
+/// The system time zone, or UTC if it can't be determined.
+pub fn local() -> Tz {
+    iana_time_zone::get_timezone()
+        .ok()
+        .and_then(|name| name.parse().ok())
+        .unwrap_or(Tz::UTC)
+}
+
+/// Parses a time of day such as `14:00`, `14:00:30`, `2pm` or `2:30 PM`.
+pub fn parse_time(input: &str) -> Result<NaiveTime> {
+    let mut normalized = input.trim().to_uppercase().replace(' ', "");
+    if !normalized.is_ascii() {
+        return Err(Error::InvalidTime(input.to_string()));
+    }
+
+    // chrono needs minutes, so "2PM" becomes "2:00PM"
+    if !normalized.contains(':') && normalized.len() > 2 {
+        normalized.insert_str(normalized.len() - 2, ":00");
+    }
+
+    ["%H:%M", "%H:%M:%S", "%I:%M%p", "%I:%M:%S%p"]
+        .iter()
+        .find_map(|format| NaiveTime::parse_from_str(&normalized, format).ok())
+        .ok_or_else(|| Error::InvalidTime(input.to_string()))
+}
+
+/// The moment `time` occurs on `date` (today if `None`) in zone `tz`.
+///
+/// During a DST fold the earlier instant is used; times skipped by a DST
+/// gap are an error.
+pub fn at(time: NaiveTime, date: Option<NaiveDate>, tz: Tz) -> Result<DateTime<Tz>> {
+    let date = date.unwrap_or_else(|| Local::now().with_timezone(&tz).date_naive());
+    tz.from_local_datetime(&date.and_time(time))
+        .earliest()
+        .ok_or_else(|| Error::InvalidTime(format!("{} {} does not exist in {}", date, time, tz)))
+}
+
+/// UTC offset of `time`, formatted as `+09:00`.
+pub fn offset(time: &DateTime<Tz>) -> String {
+    let seconds = time.offset().fix().local_minus_utc();
+    let sign = if seconds < 0 { '-' } else { '+' };
+    let minutes = seconds.abs() / 60;
+    format!("{}{:02}:{:02}", sign, minutes / 60, minutes % 60)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_find_and_convert() {
+        assert_eq!(find("Asia/Tokyo").unwrap(), Tz::Asia__Tokyo);
+        assert_eq!(find("new york").unwrap(), Tz::America__New_York);
+        assert!(find("Atlantis").is_err());
+
+        let date = NaiveDate::from_ymd_opt(2024, 7, 1);
+        let time = parse_time("2pm").unwrap();
+        assert!(parse_time("1€").is_err());
+        let new_york = at(time, date, Tz::America__New_York).unwrap();
+        let tokyo = new_york.with_timezone(&Tz::Asia__Tokyo);
+
+        assert_eq!(
+            tokyo.format("%Y-%m-%d %H:%M").to_string(),
+            "2024-07-02 03:00"
+        );
+        assert_eq!(offset(&new_york), "-04:00");
+        assert_eq!(offset(&tokyo), "+09:00");
+    }
+}
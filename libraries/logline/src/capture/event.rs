@@ -0,0 +1,94 @@
+use crate::Level;
+use std::{collections::BTreeMap, fmt};
+use tracing::{field::Field, field::Visit, Metadata};
+
+/// An event recorded by [`TestTracer`](super::TestTracer).
+#[derive(Debug, Clone, PartialEq)]
+pub struct Captured {
+	pub level: tracing::Level,
+	pub target: String,
+	pub message: String,
+	/// Every field except `message`, formatted with `Debug`, or as-is for
+	/// strings.
+	pub fields: BTreeMap<String, String>,
+}
+
+impl Captured {
+	/// `ENV` matches any level and `OFF` matches none.
+	pub(super) fn matches(
+		&self,
+		level: &Level,
+		pattern: &str,
+	) -> bool {
+		let level = match level {
+			Level::TRACE => tracing::Level::TRACE,
+			Level::DEBUG => tracing::Level::DEBUG,
+			Level::INFO => tracing::Level::INFO,
+			Level::WARN => tracing::Level::WARN,
+			Level::ERROR => tracing::Level::ERROR,
+			Level::OFF => return false,
+			Level::ENV => self.level,
+		};
+
+		self.level == level
+			&& self
+				.message
+				.to_lowercase()
+				.contains(&pattern.to_lowercase())
+	}
+}
+
+impl fmt::Display for Captured {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		write!(
+			f,
+			"{} {}: {}",
+			self.level, self.target, self.message
+		)?;
+		for (name, value) in &self.fields {
+			write!(f, " {}={}", name, value)?;
+		}
+		Ok(())
+	}
+}
+
+#[derive(Default)]
+pub(crate) struct Visitor {
+	message: String,
+	fields: BTreeMap<String, String>,
+}
+
+impl Visitor {
+	pub(crate) fn finish(self, metadata: &Metadata) -> Captured {
+		Captured {
+			level: *metadata.level(),
+			target: metadata.target().to_string(),
+			message: self.message,
+			fields: self.fields,
+		}
+	}
+}
+
+impl Visit for Visitor {
+	fn record_str(&mut self, field: &Field, value: &str) {
+		if field.name() == "message" {
+			self.message = value.to_string();
+		} else {
+			self.fields
+				.insert(field.name().to_string(), value.to_string());
+		}
+	}
+
+	fn record_debug(
+		&mut self,
+		field: &Field,
+		value: &dyn fmt::Debug,
+	) {
+		let value = format!("{:?}", value);
+		if field.name() == "message" {
+			self.message = value;
+		} else {
+			self.fields.insert(field.name().to_string(), value);
+		}
+	}
+}
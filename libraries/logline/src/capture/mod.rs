@@ -0,0 +1,171 @@
+mod event;
+
+pub use event::Captured;
+
+use crate::Level;
+pub(crate) use event::Visitor;
+use std::sync::{Arc, Mutex, MutexGuard};
+use tracing::{subscriber::DefaultGuard, Event, Subscriber};
+use tracing_subscriber::{
+	layer::{Context, SubscriberExt},
+	Layer,
+};
+
+/// Captures events into memory so tests can assert on what was logged.
+///
+/// The capture is installed per thread, so parallel tests don't see each
+/// other's events.
+///
+/// ```
+/// use logline::{TestTracer, INFO, WARN};
+///
+/// let tracer = TestTracer::new();
+/// tracer.scoped(|| logline::warn!(path = "a.toml", "Config missing"));
+///
+/// tracer.assert_logged(WARN, "config missing");
+/// tracer.assert_not_logged(INFO, "config");
+/// assert_eq!(tracer.events()[0].fields["path"], "a.toml");
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct TestTracer {
+	events: Arc<Mutex<Vec<Captured>>>,
+}
+
+impl TestTracer {
+	pub fn new() -> Self {
+		Self::default()
+	}
+
+	/// Captures events until the guard is dropped.
+	pub fn install(&self) -> DefaultGuard {
+		let subscriber =
+			tracing_subscriber::registry().with(self.clone());
+		tracing::subscriber::set_default(subscriber)
+	}
+
+	/// Captures events emitted while `f` runs.
+	pub fn scoped<T>(&self, f: impl FnOnce() -> T) -> T {
+		let _guard = self.install();
+		f()
+	}
+
+	pub fn events(&self) -> Vec<Captured> {
+		self.lock().clone()
+	}
+
+	pub fn clear(&self) {
+		self.lock().clear();
+	}
+
+	/// Events at `level` whose message contains `pattern`, ignoring case.
+	pub fn find(&self, level: Level, pattern: &str) -> Vec<Captured> {
+		self.lock()
+			.iter()
+			.filter(|event| event.matches(&level, pattern))
+			.cloned()
+			.collect()
+	}
+
+	pub fn logged(&self, level: Level, pattern: &str) -> bool {
+		!self.find(level, pattern).is_empty()
+	}
+
+	/// Panics, listing everything captured, unless a matching event was
+	/// logged.
+	#[track_caller]
+	pub fn assert_logged(&self, level: Level, pattern: &str) {
+		if !self.logged(level.clone(), pattern) {
+			panic!(
+				"expected a {} event matching {:?}, captured:\n{}",
+				level,
+				pattern,
+				self.dump()
+			);
+		}
+	}
+
+	#[track_caller]
+	pub fn assert_not_logged(&self, level: Level, pattern: &str) {
+		let found = self.find(level.clone(), pattern);
+		if !found.is_empty() {
+			panic!(
+				"expected no {} event matching {:?}, found:\n{}",
+				level,
+				pattern,
+				dump(&found)
+			);
+		}
+	}
+
+	fn dump(&self) -> String {
+		dump(&self.lock())
+	}
+
+	fn lock(&self) -> MutexGuard<'_, Vec<Captured>> {
+		self.events.lock().unwrap_or_else(|e| e.into_inner())
+	}
+}
+
+impl<S: Subscriber> Layer<S> for TestTracer {
+	fn on_event(&self, event: &Event<'_>, _ctx: Context<'_, S>) {
+		let mut visitor = Visitor::default();
+		event.record(&mut visitor);
+		self.lock().push(visitor.finish(event.metadata()));
+	}
+}
+
+fn dump(events: &[Captured]) -> String {
+	if events.is_empty() {
+		return "  (nothing)".to_string();
+	}
+	events
+		.iter()
+		.map(|event| format!("  {}", event))
+		.collect::<Vec<_>>()
+		.join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::{DEBUG, ERROR, INFO, WARN};
+
+	#[test]
+	fn test_matches_level_and_pattern_ignoring_case() {
+		let tracer = TestTracer::new();
+		tracer.scoped(|| {
+			tracing::info!("Loaded Config");
+			tracing::error!("Disk full");
+		});
+
+		assert!(tracer.logged(INFO, "loaded config"));
+		assert!(tracer.logged(ERROR, "DISK"));
+		assert!(!tracer.logged(WARN, "disk"));
+		assert!(!tracer.logged(DEBUG, "config"));
+		assert_eq!(tracer.find(Level::ENV, "").len(), 2);
+		assert!(tracer.find(Level::OFF, "").is_empty());
+	}
+
+	#[test]
+	fn test_capture_is_per_thread_and_clearable() {
+		let tracer = TestTracer::new();
+		let _guard = tracer.install();
+		std::thread::spawn(|| tracing::info!("elsewhere"))
+			.join()
+			.unwrap();
+		tracing::info!("here");
+
+		assert_eq!(tracer.events().len(), 1);
+		tracer.assert_not_logged(INFO, "elsewhere");
+		tracer.clear();
+		assert!(tracer.events().is_empty());
+	}
+
+	#[test]
+	#[should_panic(expected = "expected a WARN event matching")]
+	fn test_assert_logged_panics_when_missing() {
+		let tracer = TestTracer::new();
+		tracer.scoped(|| tracing::info!("fine"));
+		tracer.assert_logged(WARN, "fine");
+	}
+}
@@ -1,10 +1,36 @@
-use super::{Level, Options, Time};
+use super::{Format, Level, Options, Sink, Target, Time};
+use crate::{RateLimit, RingBuffer};
+use std::{fs, io, sync::Mutex};
+use tracing::Subscriber;
+use tracing_subscriber::{
+	fmt::{self, time::uptime, writer::BoxMakeWriter},
+	layer::SubscriberExt,
+	registry::LookupSpan,
+	util::SubscriberInitExt,
+	Layer,
+};
+
+/// A layer that can be added to any registry-based subscriber.
+pub type BoxedLayer<S> = Box<dyn Layer<S> + Send + Sync + 'static>;
 
 #[derive(Debug, Clone)]
 pub struct Logline {
 	pub level: Level,
 	pub time: Time,
 	pub display: Options,
+	/// Outputs composed into a layered subscriber. When empty, a single
+	/// stdout subscriber is built from `level` and `display`.
+	pub sinks: Vec<Sink>,
+	/// OTLP/HTTP collector that spans are exported to.
+	#[cfg(feature = "otel")]
+	pub otlp: Option<String>,
+	/// Routes console output around [`crate::progress`] bars.
+	#[cfg(feature = "progress")]
+	pub progress: bool,
+	/// Keeps recent events at every level for post-mortem dumps.
+	pub ring: Option<RingBuffer>,
+	/// Drops events from noisy targets before any sink sees them.
+	pub rate_limit: Option<RateLimit>,
 }
 
 impl Default for Logline {
@@ -23,6 +49,13 @@ impl Default for Logline {
 			level,
 			time,
 			display,
+			sinks: Vec::new(),
+			#[cfg(feature = "otel")]
+			otlp: None,
+			#[cfg(feature = "progress")]
+			progress: false,
+			ring: None,
+			rate_limit: None,
 		}
 	}
 }
@@ -32,7 +65,37 @@ impl Logline {
 		Self::default()
 	}
 
+	/// Installs the global subscriber.
+	///
+	/// # Panics
+	///
+	/// If a global subscriber is already set; see [`Self::try_init`].
 	pub fn init(&mut self) {
+		if self.try_init() {
+			panic!("logline: a global subscriber is already set");
+		}
+	}
+
+	/// Installs the global subscriber unless one is already set, in
+	/// which case nothing changes. Returns whether one was already set,
+	/// so libraries can call it without fighting the host application.
+	///
+	/// ```
+	/// use logline::Logline;
+	///
+	/// assert!(!Logline::new().try_init());
+	/// assert!(logline::try_init());
+	/// ```
+	pub fn try_init(&self) -> bool {
+		if !self.sinks.is_empty()
+			|| self.exports_spans()
+			|| self.draws_progress()
+			|| self.ring.is_some()
+			|| self.rate_limit.is_some()
+		{
+			return self.try_init_layered();
+		}
+
 		let timer = tracing_subscriber::fmt::time::uptime();
 		let filter = self.level.filter();
 		let mut subscriber =
@@ -46,23 +109,232 @@ impl Logline {
 			.with_thread_ids(self.display.thread_id)
 			.with_line_number(self.display.line);
 
-		match self.display.pretty {
+		let result = match self.display.pretty {
 			true => match self.time {
 				Time::None => {
-					subscriber.without_time().pretty().init()
+					subscriber.without_time().pretty().try_init()
 				}
-				Time::Datetime => subscriber.pretty().init(),
+				Time::Datetime => subscriber.pretty().try_init(),
 				Time::Uptime => {
-					subscriber.with_timer(timer).pretty().init()
+					subscriber.with_timer(timer).pretty().try_init()
 				}
 			},
 			false => match self.time {
-				Time::None => subscriber.without_time().init(),
-				Time::Datetime => subscriber.init(),
-				Time::Uptime => subscriber.with_timer(timer).init(),
+				Time::None => subscriber.without_time().try_init(),
+				Time::Datetime => subscriber.try_init(),
+				Time::Uptime => {
+					subscriber.with_timer(timer).try_init()
+				}
 			},
+		};
+		result.is_err()
+	}
+
+	/// Everything [`Self::init`] would install, as one layer for a
+	/// subscriber the host application owns.
+	///
+	/// ```
+	/// use logline::{tracing_subscriber::prelude::*, Logline, Sink};
+	///
+	/// let layer = Logline::new().with_sink(Sink::stderr()).as_layer();
+	/// let subscriber = tracing_subscriber::registry().with(layer);
+	/// tracing::subscriber::with_default(subscriber, || {
+	///     logline::info!("Attached to the host's subscriber");
+	/// });
+	/// ```
+	pub fn as_layer<S>(&self) -> BoxedLayer<S>
+	where
+		S: Subscriber + Send + Sync + for<'a> LookupSpan<'a>,
+	{
+		if let Some(ring) = &self.ring {
+			ring.install();
+		}
+		self.layers().boxed()
+	}
+
+	/// Sends every sink's events through one registry, each sink filtered
+	/// by its own level.
+	fn try_init_layered(&self) -> bool {
+		let already_set = tracing_subscriber::registry()
+			.with(self.layers())
+			.try_init()
+			.is_err();
+		if let (false, Some(ring)) = (already_set, &self.ring) {
+			ring.install();
+		}
+		already_set
+	}
+
+	/// One layer per sink, plus OTLP export and the ring buffer when
+	/// configured. Sinks that can't be opened are reported and skipped.
+	fn layers<S>(&self) -> Vec<BoxedLayer<S>>
+	where
+		S: Subscriber + Send + Sync + for<'a> LookupSpan<'a>,
+	{
+		let default_sink = [Sink::stdout()
+			.with_level(self.level.clone())
+			.with_format(match self.display.pretty {
+				true => Format::Pretty,
+				false => Format::Full,
+			})];
+		let sinks = match self.sinks.is_empty() {
+			true => &default_sink[..],
+			false => &self.sinks[..],
+		};
+
+		let mut layers: Vec<_> = sinks
+			.iter()
+			.filter_map(|sink| match self.layer(sink) {
+				Ok(layer) => Some(
+					layer.with_filter(sink.level.filter()).boxed(),
+				),
+				Err(e) => {
+					eprintln!(
+						"logline: skipping {:?} sink: {}",
+						sink.target, e
+					);
+					None
+				}
+			})
+			.collect();
+
+		#[cfg(feature = "otel")]
+		if let Some(endpoint) = &self.otlp {
+			match super::otel::layer(endpoint) {
+				Ok(layer) => layers.push(
+					layer.with_filter(self.level.filter()).boxed(),
+				),
+				Err(e) => {
+					eprintln!("logline: skipping OTLP export: {}", e)
+				}
+			}
+		}
+
+		if let Some(ring) = &self.ring {
+			layers.push(ring.clone().boxed());
+		}
+
+		if let Some(limit) = &self.rate_limit {
+			layers.push(limit.clone().boxed());
+		}
+
+		layers
+	}
+
+	fn layer<S>(&self, sink: &Sink) -> io::Result<BoxedLayer<S>>
+	where
+		S: Subscriber + for<'a> LookupSpan<'a>,
+	{
+		let (writer, ansi) = match &sink.target {
+			#[cfg(feature = "progress")]
+			Target::Stderr if self.progress => (
+				BoxMakeWriter::new(
+					crate::progress::ProgressWriter::stderr(),
+				),
+				true,
+			),
+			#[cfg(feature = "progress")]
+			Target::Stdout if self.progress => (
+				BoxMakeWriter::new(
+					crate::progress::ProgressWriter::stdout(),
+				),
+				true,
+			),
+			Target::Stderr => (BoxMakeWriter::new(io::stderr), true),
+			Target::Stdout => (BoxMakeWriter::new(io::stdout), true),
+			Target::File(path) => {
+				if let Some(parent) = path.parent() {
+					fs::create_dir_all(parent)?;
+				}
+				let file = fs::OpenOptions::new()
+					.create(true)
+					.append(true)
+					.open(path)?;
+				(BoxMakeWriter::new(Mutex::new(file)), false)
+			}
+			Target::Journald => return journald(),
+		};
+
+		let layer = fmt::layer()
+			.with_writer(writer)
+			.with_ansi(ansi)
+			.with_level(self.display.level)
+			.with_file(self.display.file)
+			.with_target(self.display.target)
+			.with_thread_names(self.display.thread)
+			.with_thread_ids(self.display.thread_id)
+			.with_line_number(self.display.line);
+
+		macro_rules! timed {
+			($layer:expr) => {
+				match self.time {
+					Time::None => $layer.without_time().boxed(),
+					Time::Datetime => $layer.boxed(),
+					Time::Uptime => {
+						$layer.with_timer(uptime()).boxed()
+					}
+				}
+			};
 		}
-		// subscriber.init();
+
+		Ok(match sink.format {
+			Format::Pretty => timed!(layer.pretty()),
+			Format::Full => timed!(layer),
+			Format::Compact => timed!(layer.compact()),
+			Format::Json => timed!(layer.json()),
+		})
+	}
+
+	pub fn with_sink(mut self, sink: Sink) -> Self {
+		self.sinks.push(sink);
+		self
+	}
+
+	/// Exports spans to an OTLP/HTTP collector such as Jaeger or Tempo,
+	/// e.g. `http://localhost:4318`. Call [`shutdown_otlp`] before exit.
+	///
+	/// [`shutdown_otlp`]: crate::shutdown_otlp
+	#[cfg(feature = "otel")]
+	pub fn with_otlp(mut self, endpoint: impl Into<String>) -> Self {
+		self.otlp = Some(endpoint.into());
+		self
+	}
+
+	/// Prints console sinks above [`crate::progress`] bars so log
+	/// lines never interleave with a bar being redrawn.
+	#[cfg(feature = "progress")]
+	pub fn with_progress(mut self) -> Self {
+		self.progress = true;
+		self
+	}
+
+	/// Captures every event into `ring`, regardless of `level`, and
+	/// dumps it on panic or when an error is logged with `fatal = true`.
+	pub fn with_ring_buffer(mut self, ring: RingBuffer) -> Self {
+		self.ring = Some(ring);
+		self
+	}
+
+	/// Caps how often targets in `limit` may log, for diagnostics
+	/// inside hot loops. See [`crate::throttle!`] to sample a single
+	/// call site instead.
+	pub fn with_rate_limit(mut self, limit: RateLimit) -> Self {
+		self.rate_limit = Some(limit);
+		self
+	}
+
+	fn draws_progress(&self) -> bool {
+		#[cfg(feature = "progress")]
+		return self.progress;
+		#[cfg(not(feature = "progress"))]
+		return false;
+	}
+
+	fn exports_spans(&self) -> bool {
+		#[cfg(feature = "otel")]
+		return self.otlp.is_some();
+		#[cfg(not(feature = "otel"))]
+		return false;
 	}
 
 	pub fn with_level(mut self, level: Level) -> Self {
@@ -100,3 +372,63 @@ impl Logline {
 		self
 	}
 }
+
+#[cfg(feature = "journald")]
+fn journald<S>() -> io::Result<BoxedLayer<S>>
+where
+	S: Subscriber + for<'a> LookupSpan<'a>,
+{
+	Ok(tracing_journald::layer()?.boxed())
+}
+
+#[cfg(not(feature = "journald"))]
+fn journald<S>() -> io::Result<BoxedLayer<S>>
+where
+	S: Subscriber + for<'a> LookupSpan<'a>,
+{
+	Err(io::Error::new(
+		io::ErrorKind::Unsupported,
+		"logline was built without the journald feature",
+	))
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use tracing_subscriber::prelude::*;
+
+	#[test]
+	fn test_sinks_filter_by_their_own_level() {
+		let dir = tempfile::tempdir().unwrap();
+		let (all, warnings) =
+			(dir.path().join("all.log"), dir.path().join("warn.log"));
+		let layer = Logline::new()
+			.with_sink(Sink::file(&all).with_level(Level::TRACE))
+			.with_sink(Sink::file(&warnings).with_level(Level::WARN))
+			.as_layer();
+
+		let subscriber = tracing_subscriber::registry().with(layer);
+		tracing::subscriber::with_default(subscriber, || {
+			tracing::debug!("quiet detail");
+			tracing::warn!("loud problem");
+		});
+
+		let all = fs::read_to_string(all).unwrap();
+		let warnings = fs::read_to_string(warnings).unwrap();
+		assert!(all.contains("quiet detail"));
+		assert!(all.contains("loud problem"));
+		assert!(!warnings.contains("quiet detail"));
+		assert!(warnings.contains("loud problem"));
+	}
+
+	#[test]
+	fn test_try_init_is_idempotent_and_init_refuses_twice() {
+		let quiet = || Logline::new().with_level(Level::OFF);
+		quiet().try_init();
+
+		assert!(quiet().try_init());
+		assert!(quiet().try_init());
+		let second = std::panic::catch_unwind(|| quiet().init());
+		assert!(second.is_err());
+	}
+}
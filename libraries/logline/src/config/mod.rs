@@ -1,9 +1,15 @@
 mod core;
 mod display;
 mod level;
+#[cfg(feature = "otel")]
+mod otel;
+mod sink;
 mod time;
 
-pub use core::Logline;
+pub use core::{BoxedLayer, Logline};
 pub use display::Options;
 pub use level::Level;
+#[cfg(feature = "otel")]
+pub use otel::shutdown_otlp;
+pub use sink::{Format, Sink, Target};
 pub use time::Time;
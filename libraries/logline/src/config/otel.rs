@@ -0,0 +1,65 @@
+use opentelemetry::trace::TracerProvider as _;
+use opentelemetry_otlp::{SpanExporter, WithExportConfig};
+use opentelemetry_sdk::{
+	trace::{SdkTracer, SdkTracerProvider},
+	Resource,
+};
+use std::{env, sync::OnceLock};
+use tracing::Subscriber;
+use tracing_opentelemetry::OpenTelemetryLayer;
+use tracing_subscriber::registry::LookupSpan;
+
+pub use opentelemetry_otlp::ExporterBuildError;
+
+static PROVIDER: OnceLock<SdkTracerProvider> = OnceLock::new();
+
+/// Builds a layer exporting spans over OTLP/HTTP to `endpoint`, such as
+/// `http://localhost:4318`. The service name comes from
+/// `OTEL_SERVICE_NAME`, falling back to the executable name.
+pub(super) fn layer<S>(
+	endpoint: &str,
+) -> Result<OpenTelemetryLayer<S, SdkTracer>, ExporterBuildError>
+where
+	S: Subscriber + for<'a> LookupSpan<'a>,
+{
+	let endpoint = if endpoint.ends_with("/v1/traces") {
+		endpoint.to_string()
+	} else {
+		format!("{}/v1/traces", endpoint.trim_end_matches('/'))
+	};
+	let exporter = SpanExporter::builder()
+		.with_http()
+		.with_endpoint(endpoint)
+		.build()?;
+
+	let mut resource = Resource::builder();
+	if env::var_os("OTEL_SERVICE_NAME").is_none() {
+		if let Some(name) = service_name() {
+			resource = resource.with_service_name(name);
+		}
+	}
+
+	let provider = SdkTracerProvider::builder()
+		.with_batch_exporter(exporter)
+		.with_resource(resource.build())
+		.build();
+	let tracer = provider.tracer("logline");
+	let _ = PROVIDER.set(provider);
+
+	Ok(tracing_opentelemetry::layer().with_tracer(tracer))
+}
+
+/// Flushes pending spans and stops the OTLP exporter. Call before exiting,
+/// otherwise spans still in the batch queue are lost.
+pub fn shutdown_otlp() {
+	if let Some(provider) = PROVIDER.get() {
+		if let Err(e) = provider.shutdown() {
+			eprintln!("logline: failed to flush OTLP spans: {}", e);
+		}
+	}
+}
+
+fn service_name() -> Option<String> {
+	let exe = env::current_exe().ok()?;
+	Some(exe.file_stem()?.to_string_lossy().into_owned())
+}
@@ -0,0 +1,77 @@
+use super::Level;
+use std::path::PathBuf;
+
+/// Where a sink writes its events.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Target {
+	Stderr,
+	Stdout,
+	/// Appends to the file, creating it and its parent directories.
+	File(PathBuf),
+	/// The systemd journal; requires the `journald` feature.
+	Journald,
+}
+
+/// How a sink renders events. Ignored by [`Target::Journald`].
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub enum Format {
+	#[default]
+	Pretty,
+	/// tracing's default single-line format.
+	Full,
+	Compact,
+	Json,
+}
+
+/// One output of a layered subscriber, with its own level filter.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Sink {
+	pub target: Target,
+	pub format: Format,
+	pub level: Level,
+}
+
+impl Sink {
+	pub fn new(target: Target) -> Self {
+		Self {
+			target,
+			format: Format::default(),
+			level: Level::default(),
+		}
+	}
+
+	pub fn stderr() -> Self {
+		Self::new(Target::Stderr)
+	}
+
+	pub fn stdout() -> Self {
+		Self::new(Target::Stdout)
+	}
+
+	/// A JSON file sink, the usual choice for machine-readable logs.
+	pub fn file(path: impl Into<PathBuf>) -> Self {
+		Self::new(Target::File(path.into())).json()
+	}
+
+	pub fn journald() -> Self {
+		Self::new(Target::Journald)
+	}
+
+	pub fn with_level(mut self, level: Level) -> Self {
+		self.level = level;
+		self
+	}
+
+	pub fn with_format(mut self, format: Format) -> Self {
+		self.format = format;
+		self
+	}
+
+	pub fn compact(self) -> Self {
+		self.with_format(Format::Compact)
+	}
+
+	pub fn json(self) -> Self {
+		self.with_format(Format::Json)
+	}
+}
@@ -1,11 +1,21 @@
+mod capture;
 mod config;
+#[cfg(feature = "progress")]
+pub mod progress;
+mod ring;
+mod throttle;
+mod timing;
 pub mod utils;
 
+pub use capture::{Captured, TestTracer};
 pub use config::{
 	Level::{DEBUG, ERROR, INFO, TRACE, WARN},
 	Time::{Datetime, Uptime},
 	*,
 };
+pub use ring::{dump, RingBuffer};
+pub use throttle::RateLimit;
+pub use timing::{summary, Summary, Timer};
 pub use tracing::{self, debug, error, info, trace, warn};
 pub use tracing_subscriber;
 pub use utils::*;
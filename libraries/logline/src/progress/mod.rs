@@ -0,0 +1,95 @@
+use indicatif::{MultiProgress, ProgressBar, ProgressStyle};
+use std::{
+	io::{self, Write},
+	sync::OnceLock,
+	time::Duration,
+};
+use tracing_subscriber::fmt::MakeWriter;
+
+pub use indicatif;
+
+static MULTI: OnceLock<MultiProgress> = OnceLock::new();
+
+/// The process-wide set of progress bars that log output is drawn
+/// around.
+pub fn multi() -> &'static MultiProgress {
+	MULTI.get_or_init(MultiProgress::new)
+}
+
+/// A bar of `len` steps, added to [`multi`].
+pub fn bar(len: u64) -> ProgressBar {
+	let style = ProgressStyle::with_template(
+		"{msg} [{bar:30}] {pos}/{len} ({eta})",
+	)
+	.expect("static template is valid")
+	.progress_chars("=> ");
+
+	multi().add(ProgressBar::new(len).with_style(style))
+}
+
+/// A spinner for work of unknown length, added to [`multi`].
+pub fn spinner(message: impl Into<String>) -> ProgressBar {
+	let spinner = multi().add(ProgressBar::new_spinner());
+	spinner.set_message(message.into());
+	spinner.enable_steady_tick(Duration::from_millis(100));
+	spinner
+}
+
+/// Writes log lines above active progress bars instead of through
+/// them, by hiding the bars while each line is printed.
+#[derive(Debug, Clone, Copy)]
+pub struct ProgressWriter {
+	stdout: bool,
+}
+
+impl ProgressWriter {
+	pub fn stderr() -> Self {
+		Self { stdout: false }
+	}
+
+	pub fn stdout() -> Self {
+		Self { stdout: true }
+	}
+}
+
+impl<'a> MakeWriter<'a> for ProgressWriter {
+	type Writer = Line;
+
+	fn make_writer(&'a self) -> Self::Writer {
+		Line {
+			stdout: self.stdout,
+			buffer: Vec::new(),
+		}
+	}
+}
+
+/// One buffered event, printed in a single suspended write on drop.
+pub struct Line {
+	stdout: bool,
+	buffer: Vec<u8>,
+}
+
+impl Write for Line {
+	fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+		self.buffer.extend_from_slice(buf);
+		Ok(buf.len())
+	}
+
+	fn flush(&mut self) -> io::Result<()> {
+		if self.buffer.is_empty() {
+			return Ok(());
+		}
+
+		let buffer = std::mem::take(&mut self.buffer);
+		multi().suspend(|| match self.stdout {
+			true => io::stdout().lock().write_all(&buffer),
+			false => io::stderr().lock().write_all(&buffer),
+		})
+	}
+}
+
+impl Drop for Line {
+	fn drop(&mut self) {
+		let _ = self.flush();
+	}
+}
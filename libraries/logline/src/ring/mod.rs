@@ -0,0 +1,234 @@
+use crate::capture::Visitor;
+use crate::Captured;
+use std::{
+	collections::VecDeque,
+	env, fs, io, panic,
+	path::{Path, PathBuf},
+	sync::{Arc, Mutex, MutexGuard, OnceLock},
+	time::{Duration, Instant},
+};
+use tracing::{Event, Subscriber};
+use tracing_subscriber::{layer::Context, Layer};
+
+static INSTALLED: OnceLock<RingBuffer> = OnceLock::new();
+
+/// Keeps the last `capacity` events at every level, whatever the
+/// console shows, and writes them out when things go wrong.
+///
+/// The buffer is dumped when an error is logged with `fatal = true`,
+/// or on panic once installed through [`Logline::with_ring_buffer`].
+///
+/// ```
+/// use logline::{tracing_subscriber::prelude::*, RingBuffer};
+///
+/// let ring = RingBuffer::new(2);
+/// let subscriber = tracing_subscriber::registry().with(ring.clone());
+/// tracing::subscriber::with_default(subscriber, || {
+///     logline::trace!("one");
+///     logline::debug!("two");
+///     logline::info!("three");
+/// });
+///
+/// let kept: Vec<_> = ring.events().into_iter().map(|e| e.message).collect();
+/// assert_eq!(kept, ["two", "three"]);
+/// ```
+///
+/// [`Logline::with_ring_buffer`]: crate::Logline::with_ring_buffer
+#[derive(Debug, Clone)]
+pub struct RingBuffer {
+	pub capacity: usize,
+	/// Where dumps are written, `<program>-trace.log` in the temp
+	/// directory by default.
+	pub path: PathBuf,
+	started: Instant,
+	events: Arc<Mutex<VecDeque<(Duration, Captured)>>>,
+}
+
+impl Default for RingBuffer {
+	fn default() -> Self {
+		Self::new(1000)
+	}
+}
+
+impl RingBuffer {
+	pub fn new(capacity: usize) -> Self {
+		Self {
+			capacity,
+			path: env::temp_dir()
+				.join(format!("{}-trace.log", program())),
+			started: Instant::now(),
+			events: Arc::new(Mutex::new(VecDeque::with_capacity(
+				capacity,
+			))),
+		}
+	}
+
+	pub fn with_dump_path<P: Into<PathBuf>>(
+		mut self,
+		path: P,
+	) -> Self {
+		self.path = path.into();
+		self
+	}
+
+	/// The buffered events, oldest first.
+	pub fn events(&self) -> Vec<Captured> {
+		self.lock().iter().map(|(_, event)| event.clone()).collect()
+	}
+
+	pub fn clear(&self) {
+		self.lock().clear();
+	}
+
+	/// Writes the buffered events to [`path`](Self::path), returning it.
+	pub fn dump(&self) -> io::Result<PathBuf> {
+		self.dump_to(&self.path)?;
+		Ok(self.path.clone())
+	}
+
+	pub fn dump_to(&self, path: &Path) -> io::Result<()> {
+		let mut lines = self
+			.lock()
+			.iter()
+			.map(|(at, event)| {
+				format!("[{:>10.3}s] {}", at.as_secs_f64(), event)
+			})
+			.collect::<Vec<_>>()
+			.join("\n");
+		lines.push('\n');
+
+		if let Some(parent) = path.parent() {
+			fs::create_dir_all(parent)?;
+		}
+		fs::write(path, lines)
+	}
+
+	/// Makes this buffer the one [`dump`] writes, and dumps it on panic
+	/// before running the previous hook.
+	pub(crate) fn install(&self) {
+		if INSTALLED.set(self.clone()).is_err() {
+			return;
+		}
+
+		let previous = panic::take_hook();
+		panic::set_hook(Box::new(move |info| {
+			report(dump());
+			previous(info);
+		}));
+	}
+
+	fn record(&self, event: Captured) {
+		let mut events = self.lock();
+		if self.capacity == 0 {
+			return;
+		}
+		while events.len() >= self.capacity {
+			events.pop_front();
+		}
+		events.push_back((self.started.elapsed(), event));
+	}
+
+	fn lock(&self) -> MutexGuard<'_, VecDeque<(Duration, Captured)>> {
+		self.events.lock().unwrap_or_else(|e| e.into_inner())
+	}
+}
+
+impl<S: Subscriber> Layer<S> for RingBuffer {
+	fn on_event(&self, event: &Event<'_>, _ctx: Context<'_, S>) {
+		let mut visitor = Visitor::default();
+		event.record(&mut visitor);
+		let event = visitor.finish(event.metadata());
+		let fatal = event.level == tracing::Level::ERROR
+			&& event.fields.get("fatal").is_some_and(|v| v == "true");
+
+		self.record(event);
+		if fatal {
+			report(Some(self.dump()));
+		}
+	}
+}
+
+/// Dumps the buffer installed by [`Logline::init`], if any.
+///
+/// [`Logline::init`]: crate::Logline::init
+pub fn dump() -> Option<io::Result<PathBuf>> {
+	INSTALLED.get().map(RingBuffer::dump)
+}
+
+fn report(result: Option<io::Result<PathBuf>>) {
+	match result {
+		Some(Ok(path)) => {
+			eprintln!(
+				"logline: recent events written to {}",
+				path.display()
+			)
+		}
+		Some(Err(e)) => {
+			eprintln!("logline: failed to write recent events: {}", e)
+		}
+		None => {}
+	}
+}
+
+fn program() -> String {
+	env::current_exe()
+		.ok()
+		.and_then(|exe| {
+			exe.file_stem().map(|s| s.to_string_lossy().into())
+		})
+		.unwrap_or_else(|| String::from("program"))
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use tracing_subscriber::prelude::*;
+
+	fn logged(ring: &RingBuffer, log: impl FnOnce()) {
+		let subscriber =
+			tracing_subscriber::registry().with(ring.clone());
+		tracing::subscriber::with_default(subscriber, log);
+	}
+
+	#[test]
+	fn test_fatal_error_dumps_buffer() {
+		let dir = tempfile::tempdir().unwrap();
+		let path = dir.path().join("nested").join("trace.log");
+		let ring = RingBuffer::new(10).with_dump_path(&path);
+
+		logged(&ring, || {
+			tracing::trace!("setting up");
+			tracing::error!(fatal = true, "giving up");
+		});
+
+		let dump = fs::read_to_string(&path).unwrap();
+		let lines: Vec<_> = dump.lines().collect();
+		assert_eq!(lines.len(), 2);
+		assert!(lines[0]
+			.ends_with("TRACE logline::ring::tests: setting up"));
+		assert!(lines[1].contains("giving up fatal=true"));
+	}
+
+	#[test]
+	fn test_dump_needs_fatal_error() {
+		let dir = tempfile::tempdir().unwrap();
+		let path = dir.path().join("trace.log");
+		let ring = RingBuffer::new(10).with_dump_path(&path);
+
+		logged(&ring, || {
+			tracing::error!("recoverable");
+			tracing::warn!(fatal = true, "only a warning");
+			tracing::error!(fatal = false, "not fatal");
+		});
+
+		assert!(!path.exists());
+		assert_eq!(ring.events().len(), 3);
+	}
+
+	#[test]
+	fn test_zero_capacity_keeps_nothing() {
+		let ring = RingBuffer::new(0);
+		logged(&ring, || tracing::info!("dropped"));
+		assert!(ring.events().is_empty());
+	}
+}
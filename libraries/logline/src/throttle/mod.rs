@@ -0,0 +1,224 @@
+use std::{
+	sync::{Arc, Mutex},
+	time::{Duration, Instant},
+};
+use tracing::{Event, Subscriber};
+use tracing_subscriber::{layer::Context, Layer};
+
+/// Runs a logging statement on the first call and every `every`th
+/// call after it, counted per call site. Arguments are only evaluated
+/// when the statement runs.
+///
+/// ```
+/// for path in ["a", "b", "c", "d", "e"] {
+///     logline::throttle!(every = 2, logline::trace!("Visiting {}", path));
+/// }
+/// ```
+#[macro_export]
+macro_rules! throttle {
+	(every = $every:expr, $log:expr $(,)?) => {{
+		static CALLS: ::std::sync::atomic::AtomicU64 =
+			::std::sync::atomic::AtomicU64::new(0);
+		let every: u64 = $every;
+		let call = CALLS
+			.fetch_add(1, ::std::sync::atomic::Ordering::Relaxed);
+		if call.is_multiple_of(every.max(1)) {
+			$log;
+		}
+	}};
+}
+
+/// Caps how many events each target may log per second; the rest are
+/// dropped before any sink sees them.
+///
+/// A limit on `whers` also covers `whers::walk`, and the longest
+/// matching target wins.
+///
+/// ```
+/// use logline::{tracing_subscriber::prelude::*, RateLimit, TestTracer};
+///
+/// let limit = RateLimit::new().with_target("walk", 2);
+/// let tracer = TestTracer::new();
+/// let subscriber = tracing_subscriber::registry()
+///     .with(limit.clone())
+///     .with(tracer.clone());
+/// tracing::subscriber::with_default(subscriber, || {
+///     for i in 0..5 {
+///         logline::info!(target: "walk::dir", "Visited {}", i);
+///     }
+///     logline::info!(target: "parse", "Not limited");
+/// });
+///
+/// assert_eq!(tracer.events().len(), 3);
+/// assert_eq!(limit.dropped(), 3);
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct RateLimit {
+	limits: Vec<Arc<Limit>>,
+}
+
+#[derive(Debug)]
+struct Limit {
+	target: String,
+	per_second: u32,
+	window: Mutex<Window>,
+}
+
+#[derive(Debug)]
+struct Window {
+	started: Instant,
+	count: u32,
+	dropped: u64,
+}
+
+impl RateLimit {
+	pub fn new() -> Self {
+		Self::default()
+	}
+
+	pub fn with_target(
+		mut self,
+		target: impl Into<String>,
+		per_second: u32,
+	) -> Self {
+		self.limits.push(Arc::new(Limit {
+			target: target.into(),
+			per_second,
+			window: Mutex::new(Window {
+				started: Instant::now(),
+				count: 0,
+				dropped: 0,
+			}),
+		}));
+		self
+	}
+
+	/// Events dropped so far, across every target.
+	pub fn dropped(&self) -> u64 {
+		self.limits.iter().map(|limit| limit.lock().dropped).sum()
+	}
+
+	fn limit(&self, target: &str) -> Option<&Limit> {
+		self.limits
+			.iter()
+			.filter(|limit| covers(&limit.target, target))
+			.max_by_key(|limit| limit.target.len())
+			.map(Arc::as_ref)
+	}
+}
+
+impl Limit {
+	/// Counts the event against the current one-second window,
+	/// returning whether it fits.
+	fn admit(&self) -> bool {
+		let mut window = self.lock();
+		if window.started.elapsed() >= Duration::from_secs(1) {
+			window.started = Instant::now();
+			window.count = 0;
+		}
+		if window.count < self.per_second {
+			window.count += 1;
+			return true;
+		}
+		window.dropped += 1;
+		false
+	}
+
+	fn lock(&self) -> std::sync::MutexGuard<'_, Window> {
+		self.window.lock().unwrap_or_else(|e| e.into_inner())
+	}
+}
+
+impl<S: Subscriber> Layer<S> for RateLimit {
+	fn event_enabled(
+		&self,
+		event: &Event<'_>,
+		_ctx: Context<'_, S>,
+	) -> bool {
+		self.limit(event.metadata().target())
+			.is_none_or(Limit::admit)
+	}
+}
+
+/// Whether a limit on `prefix` applies to `target`, matching whole
+/// module path segments.
+fn covers(prefix: &str, target: &str) -> bool {
+	target
+		.strip_prefix(prefix)
+		.is_some_and(|rest| rest.is_empty() || rest.starts_with("::"))
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::TestTracer;
+	use tracing_subscriber::prelude::*;
+
+	#[test]
+	fn test_rate_limit_counts_dropped_per_target() {
+		let limit = RateLimit::new()
+			.with_target("walk", 5)
+			.with_target("walk::dir", 1);
+		let tracer = TestTracer::new();
+		let subscriber = tracing_subscriber::registry()
+			.with(limit.clone())
+			.with(tracer.clone());
+
+		tracing::subscriber::with_default(subscriber, || {
+			for _ in 0..3 {
+				tracing::info!(target: "walk::dir", "dir");
+				tracing::info!(target: "walk::file", "file");
+				tracing::info!(target: "walker", "unrelated");
+			}
+		});
+
+		let count = |message: &str| {
+			tracer
+				.events()
+				.iter()
+				.filter(|event| event.message == message)
+				.count()
+		};
+		assert_eq!(count("dir"), 1);
+		assert_eq!(count("file"), 3);
+		assert_eq!(count("unrelated"), 3);
+		assert_eq!(limit.dropped(), 2);
+	}
+
+	#[test]
+	fn test_covers_whole_segments() {
+		assert!(covers("whers", "whers"));
+		assert!(covers("whers", "whers::walk"));
+		assert!(!covers("whers", "wherever"));
+		assert!(!covers("whers::walk", "whers"));
+	}
+
+	#[test]
+	fn test_throttle_runs_every_nth_call() {
+		let tracer = TestTracer::new();
+		tracer.scoped(|| {
+			for i in 0..5 {
+				crate::throttle!(
+					every = 2,
+					tracing::info!("pair {}", i)
+				);
+			}
+			for i in 0..3 {
+				crate::throttle!(
+					every = 0,
+					tracing::info!("each {}", i)
+				);
+			}
+		});
+
+		let messages: Vec<_> =
+			tracer.events().into_iter().map(|e| e.message).collect();
+		assert_eq!(
+			messages,
+			[
+				"pair 0", "pair 2", "pair 4", "each 0", "each 1",
+				"each 2"
+			]
+		);
+	}
+}
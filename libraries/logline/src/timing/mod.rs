@@ -0,0 +1,21 @@
+mod summary;
+mod timer;
+
+pub use summary::{summary, Summary};
+pub use timer::Timer;
+
+/// Times a block inside a span named after `label`, returning the block's
+/// value.
+///
+/// ```
+/// let total = logline::timed!("sum", { (1..=10).sum::<u32>() });
+/// assert_eq!(total, 55);
+/// ```
+#[macro_export]
+macro_rules! timed {
+	($label:expr, $body:block) => {{
+		let _timer = $crate::Timer::new($label);
+		let _entered = _timer.span().enter();
+		$body
+	}};
+}
@@ -0,0 +1,139 @@
+use std::{
+	cmp::Reverse,
+	sync::{
+		atomic::{AtomicBool, Ordering},
+		Mutex,
+	},
+	time::Duration,
+};
+
+static ENABLED: AtomicBool = AtomicBool::new(false);
+static ENTRIES: Mutex<Vec<Entry>> = Mutex::new(Vec::new());
+
+#[derive(Debug, Clone)]
+struct Entry {
+	label: String,
+	calls: u32,
+	total: Duration,
+	max: Duration,
+}
+
+/// Starts collecting timings and prints a table of them to stderr when the
+/// returned guard is dropped. Hold it for the life of `main`.
+pub fn summary() -> Summary {
+	ENABLED.store(true, Ordering::Relaxed);
+	Summary { _private: () }
+}
+
+pub(super) fn record(label: &str, elapsed: Duration) {
+	if !ENABLED.load(Ordering::Relaxed) {
+		return;
+	}
+
+	let mut entries =
+		ENTRIES.lock().unwrap_or_else(|e| e.into_inner());
+	match entries.iter_mut().find(|entry| entry.label == label) {
+		Some(entry) => {
+			entry.calls += 1;
+			entry.total += elapsed;
+			entry.max = entry.max.max(elapsed);
+		}
+		None => entries.push(Entry {
+			label: label.to_string(),
+			calls: 1,
+			total: elapsed,
+			max: elapsed,
+		}),
+	}
+}
+
+#[derive(Debug)]
+pub struct Summary {
+	_private: (),
+}
+
+impl Summary {
+	/// Renders the timings collected so far, slowest total first.
+	pub fn table(&self) -> String {
+		let mut entries =
+			ENTRIES.lock().unwrap_or_else(|e| e.into_inner()).clone();
+		entries.sort_by_key(|entry| Reverse(entry.total));
+
+		let width = entries
+			.iter()
+			.map(|entry| entry.label.len())
+			.max()
+			.unwrap_or_default()
+			.max("LABEL".len());
+
+		let mut table = format!(
+			"{:<width$}  {:>6}  {:>10}  {:>10}  {:>10}\n",
+			"LABEL", "CALLS", "TOTAL", "MEAN", "MAX"
+		);
+		for entry in &entries {
+			table.push_str(&format!(
+				"{:<width$}  {:>6}  {:>10}  {:>10}  {:>10}\n",
+				entry.label,
+				entry.calls,
+				format!("{:.2?}", entry.total),
+				format!("{:.2?}", entry.total / entry.calls),
+				format!("{:.2?}", entry.max),
+			));
+		}
+		table
+	}
+}
+
+impl Drop for Summary {
+	fn drop(&mut self) {
+		ENABLED.store(false, Ordering::Relaxed);
+		let has_entries = !ENTRIES
+			.lock()
+			.unwrap_or_else(|e| e.into_inner())
+			.is_empty();
+		if has_entries {
+			eprint!("{}", self.table());
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn test_table_sorts_by_total_and_averages() {
+		let summary = summary();
+		record("summary_test::parse", Duration::from_millis(3));
+		record("summary_test::parse", Duration::from_millis(1));
+		record("summary_test::walk", Duration::from_millis(10));
+
+		let table = summary.table();
+		let mut lines = table.lines();
+		assert!(lines.next().unwrap().starts_with("LABEL"));
+
+		let rows: Vec<Vec<&str>> = lines
+			.filter(|line| line.starts_with("summary_test::"))
+			.map(|line| line.split_whitespace().collect())
+			.collect();
+		assert_eq!(
+			rows,
+			[
+				[
+					"summary_test::walk",
+					"1",
+					"10.00ms",
+					"10.00ms",
+					"10.00ms"
+				],
+				[
+					"summary_test::parse",
+					"2",
+					"4.00ms",
+					"2.00ms",
+					"3.00ms"
+				],
+			]
+		);
+	}
+}
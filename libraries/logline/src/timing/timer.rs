@@ -0,0 +1,51 @@
+use super::summary;
+use std::time::{Duration, Instant};
+use tracing::{debug, field, info_span, Span};
+
+/// Measures the time until it is dropped, then records it in the `elapsed_ms`
+/// field of its span, logs it at debug level, and adds it to the summary.
+#[derive(Debug)]
+pub struct Timer {
+	label: String,
+	span: Span,
+	start: Instant,
+}
+
+impl Timer {
+	pub fn new(label: impl Into<String>) -> Self {
+		let label = label.into();
+		let span = info_span!(
+			"timed",
+			label = %label,
+			elapsed_ms = field::Empty
+		);
+
+		Self {
+			label,
+			span,
+			start: Instant::now(),
+		}
+	}
+
+	pub fn label(&self) -> &str {
+		&self.label
+	}
+
+	pub fn span(&self) -> &Span {
+		&self.span
+	}
+
+	pub fn elapsed(&self) -> Duration {
+		self.start.elapsed()
+	}
+}
+
+impl Drop for Timer {
+	fn drop(&mut self) {
+		let elapsed = self.elapsed();
+		self.span
+			.record("elapsed_ms", elapsed.as_secs_f64() * 1000.0);
+		debug!(parent: &self.span, "{} took {:.2?}", self.label, elapsed);
+		summary::record(&self.label, elapsed);
+	}
+}
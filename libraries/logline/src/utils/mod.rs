@@ -1,12 +1,18 @@
 use crate::{
-	debug, error, info, trace, warn, Logline, DEBUG, ERROR, INFO,
-	TRACE, WARN, Level,
+	debug, error, info, trace, warn, Level, Logline, DEBUG, ERROR,
+	INFO, TRACE, WARN,
 };
 
 pub fn init() {
 	Logline::default().init();
 }
 
+/// Like [`init`], but leaves an existing global subscriber in place and
+/// returns whether there was one.
+pub fn try_init() -> bool {
+	Logline::default().try_init()
+}
+
 pub fn init_with_level(level: Level) {
 	Logline::default().with_level(level).init();
 }
@@ -0,0 +1,66 @@
+use criterion::{
+	black_box, criterion_group, criterion_main, BenchmarkId, Criterion,
+	Throughput,
+};
+use numba::{decimal::Numeral, parse_many, parse_many_scalar};
+
+/// Mixed integers and decimals of up to 8 digits.
+fn short() -> Vec<String> {
+	(0..1_000u64)
+		.map(|i| match i % 4 {
+			0 => (i * 7_919).to_string(),
+			1 => format!("{}.{:02}", i * 31, i % 100),
+			2 => format!("-{}.{:04}", i, i * 13 % 10_000),
+			_ => format!("{}{}", i, "0".repeat((i % 5) as usize)),
+		})
+		.collect()
+}
+
+/// Integers and decimals filling most of the 16-digit fast path.
+fn long() -> Vec<String> {
+	(0..1_000u64)
+		.map(|i| match i % 4 {
+			0 => (1_000_000_000_000_000 + i * 7_919_123).to_string(),
+			1 => format!("{}.{:06}", 100_000_000 + i * 31, i),
+			2 => format!("-{}.{:08}", 1_000_000 + i, i * 13),
+			_ => format!("{}{}", 1_000 + i, "0".repeat(11)),
+		})
+		.collect()
+}
+
+fn criterion_benchmark(c: &mut Criterion) {
+	let mut group = c.benchmark_group("parse_many");
+
+	for (name, owned) in [("short", short()), ("long", long())] {
+		let inputs: Vec<&str> = owned.iter().map(String::as_str).collect();
+		group.throughput(Throughput::Elements(inputs.len() as u64));
+
+		group.bench_with_input(
+			BenchmarkId::new("simd", name),
+			&inputs,
+			|b, inputs| b.iter(|| parse_many(black_box(inputs))),
+		);
+		group.bench_with_input(
+			BenchmarkId::new("scalar", name),
+			&inputs,
+			|b, inputs| b.iter(|| parse_many_scalar(black_box(inputs))),
+		);
+		group.bench_with_input(
+			BenchmarkId::new("numeral", name),
+			&inputs,
+			|b, inputs| {
+				b.iter(|| {
+					black_box(inputs)
+						.iter()
+						.map(Numeral::parse)
+						.collect::<Vec<_>>()
+				})
+			},
+		);
+	}
+
+	group.finish();
+}
+
+criterion_group!(benches, criterion_benchmark);
+criterion_main!(benches);
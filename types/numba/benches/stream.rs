@@ -0,0 +1,65 @@
+use criterion::{
+	black_box, criterion_group, criterion_main, Criterion, Throughput,
+};
+use numba::{decimal::Numeral, stream::Stream};
+use std::io::BufRead;
+
+/// A CSV of 10,000 rows with four mixed-width columns.
+fn csv() -> String {
+	(0..10_000u64)
+		.map(|i| {
+			format!(
+				"{},{}.{:02},-{}.{:06},{}\n",
+				i,
+				i * 31,
+				i % 100,
+				1_000 + i,
+				i * 7,
+				1_000_000_000_000 + i * 7_919_123
+			)
+		})
+		.collect()
+}
+
+fn criterion_benchmark(c: &mut Criterion) {
+	let data = csv();
+	let mut group = c.benchmark_group("stream");
+	group.throughput(Throughput::Bytes(data.len() as u64));
+
+	group.bench_function("values", |b| {
+		b.iter(|| {
+			Stream::new(black_box(data.as_bytes()))
+				.filter_map(Result::ok)
+				.count()
+		})
+	});
+	group.bench_function("rows", |b| {
+		b.iter(|| {
+			let mut stream = Stream::new(black_box(data.as_bytes()));
+			let mut row = Vec::new();
+			let mut count = 0;
+			while stream.read_row(&mut row).unwrap() {
+				count += row.len();
+			}
+			count
+		})
+	});
+	group.bench_function("lines_split", |b| {
+		b.iter(|| {
+			black_box(data.as_bytes())
+				.lines()
+				.map(Result::unwrap)
+				.map(|line| {
+					line.split(',')
+						.filter_map(|v| Numeral::parse(v).ok())
+						.count()
+				})
+				.sum::<usize>()
+		})
+	});
+
+	group.finish();
+}
+
+criterion_group!(benches, criterion_benchmark);
+criterion_main!(benches);
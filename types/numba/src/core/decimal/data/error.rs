@@ -79,6 +79,8 @@ pub enum Error<'a> {
     InvalidFormat(&'a str),
     #[error("Number too large: {0}")]
     Overflow(&'a str),
+    #[error("Division by zero")]
+    DivisionByZero,
     #[error("Invalid scientific notation: {0}")]
     InvalidScientific(&'a str),
     #[cfg(feature = "big-decimal")]
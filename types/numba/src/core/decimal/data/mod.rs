@@ -12,3 +12,6 @@ pub use error::Error;
 
 mod cache;
 pub use cache::{Cache, CACHE, CACHE_SIZE};
+
+mod rounding;
+pub use rounding::{Precision, Rounding};
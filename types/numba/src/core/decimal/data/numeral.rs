@@ -1,4 +1,4 @@
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub enum Numeral {
 	Small(rust_decimal::Decimal),
 	#[cfg(feature = "big-decimal")]
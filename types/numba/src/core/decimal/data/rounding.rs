@@ -0,0 +1,50 @@
+/// How a result is rounded when it has more decimal places than requested.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum Rounding {
+	/// Round half to even ("banker's rounding").
+	#[default]
+	HalfEven,
+	/// Round half away from zero.
+	HalfUp,
+	/// Round half towards zero.
+	HalfDown,
+	/// Always round away from zero.
+	Up,
+	/// Always round towards zero (truncate).
+	Down,
+	/// Always round towards positive infinity.
+	Ceiling,
+	/// Always round towards negative infinity.
+	Floor,
+}
+
+/// The number of decimal places to keep and how to round the rest.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Precision {
+	pub scale: u32,
+	pub rounding: Rounding,
+}
+
+impl Default for Precision {
+	/// Matches the 28 decimal places `rust_decimal` can represent.
+	fn default() -> Self {
+		Self {
+			scale: 28,
+			rounding: Rounding::default(),
+		}
+	}
+}
+
+impl Precision {
+	pub fn new(scale: u32) -> Self {
+		Self {
+			scale,
+			..Self::default()
+		}
+	}
+
+	pub fn with_rounding(mut self, rounding: Rounding) -> Self {
+		self.rounding = rounding;
+		self
+	}
+}
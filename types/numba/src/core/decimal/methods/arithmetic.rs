@@ -0,0 +1,200 @@
+use crate::decimal::{Error, Numeral, Precision, Rounding};
+use rust_decimal::RoundingStrategy;
+
+impl Numeral {
+	/// Adds exactly, promoting to `BigDecimal` if `rust_decimal` overflows.
+	pub fn checked_add(&self, rhs: &Self) -> Result<Self, Error<'static>> {
+		match (self, rhs) {
+			(Self::Small(a), Self::Small(b)) => match a.checked_add(*b) {
+				Some(sum) => Ok(Self::Small(sum)),
+				None => self.promoted(rhs, |a, b| a + b),
+			},
+			#[cfg(feature = "big-decimal")]
+			_ => self.promoted(rhs, |a, b| a + b),
+		}
+	}
+
+	/// Subtracts exactly, promoting to `BigDecimal` if `rust_decimal` overflows.
+	pub fn checked_sub(&self, rhs: &Self) -> Result<Self, Error<'static>> {
+		match (self, rhs) {
+			(Self::Small(a), Self::Small(b)) => match a.checked_sub(*b) {
+				Some(difference) => Ok(Self::Small(difference)),
+				None => self.promoted(rhs, |a, b| a - b),
+			},
+			#[cfg(feature = "big-decimal")]
+			_ => self.promoted(rhs, |a, b| a - b),
+		}
+	}
+
+	/// Multiplies exactly, promoting to `BigDecimal` if `rust_decimal` overflows.
+	pub fn checked_mul(&self, rhs: &Self) -> Result<Self, Error<'static>> {
+		match (self, rhs) {
+			(Self::Small(a), Self::Small(b)) => match a.checked_mul(*b) {
+				Some(product) => Ok(Self::Small(product)),
+				None => self.promoted(rhs, |a, b| a * b),
+			},
+			#[cfg(feature = "big-decimal")]
+			_ => self.promoted(rhs, |a, b| a * b),
+		}
+	}
+
+	/// Divides, rounding the quotient to `precision`.
+	pub fn checked_div(
+		&self,
+		rhs: &Self,
+		precision: Precision,
+	) -> Result<Self, Error<'static>> {
+		if rhs.is_zero() {
+			return Err(Error::DivisionByZero);
+		}
+
+		let quotient = match (self, rhs) {
+			(Self::Small(a), Self::Small(b)) => match a.checked_div(*b) {
+				Some(quotient) => Self::Small(quotient),
+				None => self.promoted(rhs, |a, b| a / b)?,
+			},
+			#[cfg(feature = "big-decimal")]
+			_ => self.promoted(rhs, |a, b| a / b)?,
+		};
+
+		Ok(quotient.round(precision))
+	}
+
+	/// Rounds to `precision.scale` decimal places.
+	pub fn round(&self, precision: Precision) -> Self {
+		match self {
+			Self::Small(value) => Self::Small(value.round_dp_with_strategy(
+				precision.scale,
+				precision.rounding.into(),
+			)),
+			#[cfg(feature = "big-decimal")]
+			Self::Large(value) => {
+				// Only round down in scale; never pad with trailing zeros
+				if value.fractional_digit_count() <= precision.scale as i64 {
+					Self::Large(value.clone())
+				} else {
+					Self::Large(value.with_scale_round(
+						precision.scale as i64,
+						precision.rounding.into(),
+					))
+				}
+			}
+		}
+	}
+
+	pub fn is_zero(&self) -> bool {
+		match self {
+			Self::Small(value) => value.is_zero(),
+			#[cfg(feature = "big-decimal")]
+			Self::Large(value) => num::Zero::is_zero(value),
+		}
+	}
+
+	/// Applies `op` to both operands as `BigDecimal`s.
+	#[cfg(feature = "big-decimal")]
+	fn promoted(
+		&self,
+		rhs: &Self,
+		op: impl Fn(
+			&bigdecimal::BigDecimal,
+			&bigdecimal::BigDecimal,
+		) -> bigdecimal::BigDecimal,
+	) -> Result<Self, Error<'static>> {
+		Ok(Self::Large(op(&self.to_big(), &rhs.to_big())))
+	}
+
+	#[cfg(not(feature = "big-decimal"))]
+	fn promoted<F>(&self, _rhs: &Self, _op: F) -> Result<Self, Error<'static>> {
+		Err(Error::Overflow("BigDecimal feature not enabled"))
+	}
+
+	#[cfg(feature = "big-decimal")]
+	fn to_big(&self) -> bigdecimal::BigDecimal {
+		match self {
+			Self::Small(value) => bigdecimal::BigDecimal::new(
+				value.mantissa().into(),
+				value.scale() as i64,
+			),
+			Self::Large(value) => value.clone(),
+		}
+	}
+}
+
+impl From<Rounding> for RoundingStrategy {
+	fn from(rounding: Rounding) -> Self {
+		match rounding {
+			Rounding::HalfEven => Self::MidpointNearestEven,
+			Rounding::HalfUp => Self::MidpointAwayFromZero,
+			Rounding::HalfDown => Self::MidpointTowardZero,
+			Rounding::Up => Self::AwayFromZero,
+			Rounding::Down => Self::ToZero,
+			Rounding::Ceiling => Self::ToPositiveInfinity,
+			Rounding::Floor => Self::ToNegativeInfinity,
+		}
+	}
+}
+
+#[cfg(feature = "big-decimal")]
+impl From<Rounding> for bigdecimal::RoundingMode {
+	fn from(rounding: Rounding) -> Self {
+		match rounding {
+			Rounding::HalfEven => Self::HalfEven,
+			Rounding::HalfUp => Self::HalfUp,
+			Rounding::HalfDown => Self::HalfDown,
+			Rounding::Up => Self::Up,
+			Rounding::Down => Self::Down,
+			Rounding::Ceiling => Self::Ceiling,
+			Rounding::Floor => Self::Floor,
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use crate::decimal::{Numeral, Precision, Rounding, ToNumeral};
+
+	fn numeral(input: &str) -> Numeral {
+		input.to_numeral().unwrap()
+	}
+
+	#[test]
+	fn test_arithmetic_and_rounding() {
+		let a = numeral("10.25");
+		let b = numeral("4");
+
+		assert_eq!(a.checked_add(&b).unwrap().to_string(), "14.25");
+		assert_eq!(a.checked_sub(&b).unwrap().to_string(), "6.25");
+		assert_eq!(a.checked_mul(&b).unwrap().to_string(), "41.00");
+
+		let third = numeral("1").checked_div(&numeral("3"), Precision::new(4));
+		assert_eq!(third.unwrap().to_string(), "0.3333");
+
+		let half = numeral("2.5");
+		let even = Precision::new(0);
+		let up = even.with_rounding(Rounding::HalfUp);
+		assert_eq!(half.round(even).to_string(), "2");
+		assert_eq!(half.round(up).to_string(), "3");
+		assert_eq!(
+			numeral("-2.1")
+				.round(even.with_rounding(Rounding::Floor))
+				.to_string(),
+			"-3"
+		);
+
+		assert!(a.checked_div(&numeral("0"), even).is_err());
+	}
+
+	#[cfg(feature = "big-decimal")]
+	#[test]
+	fn test_overflow_promotes_to_big_decimal() {
+		let max = Numeral::Small(rust_decimal::Decimal::MAX);
+		let sum = max.checked_add(&max).unwrap();
+
+		assert!(matches!(sum, Numeral::Large(_)));
+		assert_eq!(sum.to_string(), "158456325028528675187087900670");
+
+		let quotient =
+			sum.checked_div(&numeral("11"), Precision::new(2)).unwrap();
+		assert_eq!(quotient.to_string(), "14405120457138970471553445515.45");
+	}
+}
@@ -1,3 +1,6 @@
+mod arithmetic;
 mod numeral;
 mod errors;
 mod cache;
+#[cfg(feature = "serde")]
+mod serde;
@@ -0,0 +1,102 @@
+use crate::decimal::Numeral;
+use serde::{
+	de::{self, Visitor},
+	Deserialize, Deserializer, Serialize, Serializer,
+};
+use std::fmt;
+
+/// Written as a string so no digits are lost to `f64` on the way out.
+impl Serialize for Numeral {
+	fn serialize<S: Serializer>(
+		&self,
+		serializer: S,
+	) -> Result<S::Ok, S::Error> {
+		serializer.collect_str(self)
+	}
+}
+
+/// Accepts strings (`"1,234.50"`, `"1e-3"`) as well as plain numbers.
+/// Prefer strings for values that do not fit in an `f64`.
+impl<'de> Deserialize<'de> for Numeral {
+	fn deserialize<D: Deserializer<'de>>(
+		deserializer: D,
+	) -> Result<Self, D::Error> {
+		deserializer.deserialize_any(NumeralVisitor)
+	}
+}
+
+struct NumeralVisitor;
+
+impl NumeralVisitor {
+	fn parse<E: de::Error>(input: impl ToString) -> Result<Numeral, E> {
+		Numeral::parse(input).map_err(E::custom)
+	}
+}
+
+impl Visitor<'_> for NumeralVisitor {
+	type Value = Numeral;
+
+	fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		f.write_str("a decimal number or a string holding one")
+	}
+
+	fn visit_i64<E: de::Error>(self, value: i64) -> Result<Numeral, E> {
+		Ok(Numeral::Small(value.into()))
+	}
+
+	fn visit_u64<E: de::Error>(self, value: u64) -> Result<Numeral, E> {
+		Ok(Numeral::Small(value.into()))
+	}
+
+	fn visit_i128<E: de::Error>(self, value: i128) -> Result<Numeral, E> {
+		Self::parse(value)
+	}
+
+	fn visit_u128<E: de::Error>(self, value: u128) -> Result<Numeral, E> {
+		Self::parse(value)
+	}
+
+	/// Goes through the shortest string that round-trips, so `0.1` stays
+	/// `0.1` rather than its binary expansion.
+	fn visit_f64<E: de::Error>(self, value: f64) -> Result<Numeral, E> {
+		if !value.is_finite() {
+			return Err(E::custom(format!("{} is not a decimal", value)));
+		}
+		Self::parse(value)
+	}
+
+	fn visit_str<E: de::Error>(self, value: &str) -> Result<Numeral, E> {
+		Self::parse(value.trim())
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use crate::decimal::Numeral;
+	use serde::{Deserialize, Serialize};
+
+	#[derive(Debug, Serialize, Deserialize)]
+	struct Price {
+		amount: Numeral,
+	}
+
+	fn amount(json: &str) -> String {
+		let price: Price = serde_json::from_str(json).unwrap();
+		price.amount.to_string()
+	}
+
+	#[test]
+	fn test_serde_round_trips_exactly() {
+		assert_eq!(amount(r#"{"amount": 0.1}"#), "0.1");
+		assert_eq!(amount(r#"{"amount": 42}"#), "42");
+		assert_eq!(amount(r#"{"amount": "1,234.50"}"#), "1234.50");
+		assert!(serde_json::from_str::<Price>(r#"{"amount": "abc"}"#).is_err());
+
+		let exact = "0.1234567890123456789012345678";
+		let price: Price =
+			serde_json::from_str(&format!(r#"{{"amount": "{}"}}"#, exact))
+				.unwrap();
+		let json = serde_json::to_string(&price).unwrap();
+		assert_eq!(json, format!(r#"{{"amount":"{}"}}"#, exact));
+	}
+}
@@ -0,0 +1,308 @@
+use super::Rational;
+use crate::decimal::{Error, Numeral, Precision};
+use std::{
+	fmt,
+	ops::{Add, Div, Mul, Neg, Sub},
+	str::FromStr,
+};
+
+/// A decimal with exactly `SCALE` places, stored as an `i128` count of
+/// `10^-SCALE` units. Arithmetic never allocates; products and quotients
+/// round half to even.
+///
+/// ```
+/// use numba::Fixed;
+///
+/// type Cents = Fixed<2>;
+///
+/// let price: Cents = "19.99".parse().unwrap();
+/// let total = price * "3".parse().unwrap();
+/// assert_eq!(total.to_string(), "59.97");
+/// assert_eq!((total / "4".parse().unwrap()).to_string(), "14.99");
+/// ```
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Fixed<const SCALE: u32>(i128);
+
+impl<const SCALE: u32> Fixed<SCALE> {
+	/// `10^SCALE`, the raw value of one.
+	pub const FACTOR: i128 = {
+		assert!(SCALE <= 38, "Fixed supports at most 38 decimal places");
+		10_i128.pow(SCALE)
+	};
+	pub const ZERO: Self = Self(0);
+	pub const ONE: Self = Self(Self::FACTOR);
+	pub const MIN: Self = Self(i128::MIN);
+	pub const MAX: Self = Self(i128::MAX);
+
+	/// Wraps a count of `10^-SCALE` units, so `Fixed::<2>::from_raw(150)`
+	/// is `1.50`.
+	pub const fn from_raw(raw: i128) -> Self {
+		Self(raw)
+	}
+
+	pub const fn raw(self) -> i128 {
+		self.0
+	}
+
+	pub fn from_integer(value: i64) -> Option<Self> {
+		i128::from(value).checked_mul(Self::FACTOR).map(Self)
+	}
+
+	pub fn checked_add(self, rhs: Self) -> Option<Self> {
+		self.0.checked_add(rhs.0).map(Self)
+	}
+
+	pub fn checked_sub(self, rhs: Self) -> Option<Self> {
+		self.0.checked_sub(rhs.0).map(Self)
+	}
+
+	/// The product is held at double width before rescaling, so only a
+	/// result that doesn't fit overflows.
+	pub fn checked_mul(self, rhs: Self) -> Option<Self> {
+		mul_div_round(self.0, rhs.0, Self::FACTOR).map(Self)
+	}
+
+	pub fn checked_div(self, rhs: Self) -> Option<Self> {
+		mul_div_round(self.0, Self::FACTOR, rhs.0).map(Self)
+	}
+
+	/// `None` for `MIN`, whose negation doesn't fit.
+	pub fn checked_neg(self) -> Option<Self> {
+		self.0.checked_neg().map(Self)
+	}
+
+	pub fn to_numeral(self) -> Result<Numeral, Error<'static>> {
+		if let Ok(small) =
+			rust_decimal::Decimal::try_from_i128_with_scale(self.0, SCALE)
+		{
+			return Ok(Numeral::Small(small));
+		}
+
+		#[cfg(feature = "big-decimal")]
+		return Ok(Numeral::Large(bigdecimal::BigDecimal::new(
+			self.0.into(),
+			SCALE as i64,
+		)));
+
+		#[cfg(not(feature = "big-decimal"))]
+		Err(Error::Overflow("BigDecimal feature not enabled"))
+	}
+}
+
+/// `a * b / denom` rounded half to even, with a 256-bit intermediate.
+/// `None` on a zero `denom` or a result outside `i128`.
+fn mul_div_round(a: i128, b: i128, denom: i128) -> Option<i128> {
+	if denom == 0 {
+		return None;
+	}
+	let negative = ((a < 0) != (b < 0)) != (denom < 0);
+	let denom = denom.unsigned_abs();
+
+	let (hi, lo) = mul_wide(a.unsigned_abs(), b.unsigned_abs());
+	let (mut quotient, remainder) = div_wide(hi, lo, denom)?;
+	let rest = denom - remainder;
+	if remainder > rest || (remainder == rest && quotient % 2 != 0) {
+		quotient = quotient.checked_add(1)?;
+	}
+
+	if negative {
+		0_i128.checked_sub_unsigned(quotient)
+	} else {
+		i128::try_from(quotient).ok()
+	}
+}
+
+/// The full product as `(high, low)` halves.
+fn mul_wide(a: u128, b: u128) -> (u128, u128) {
+	const LOW: u128 = u64::MAX as u128;
+	let (a_hi, a_lo) = (a >> 64, a & LOW);
+	let (b_hi, b_lo) = (b >> 64, b & LOW);
+
+	let low = a_lo * b_lo;
+	let cross = a_lo * b_hi;
+	let cross_rev = a_hi * b_lo;
+	let middle = (low >> 64) + (cross & LOW) + (cross_rev & LOW);
+
+	let lo = (low & LOW) | (middle << 64);
+	let hi = a_hi * b_hi + (cross >> 64) + (cross_rev >> 64) + (middle >> 64);
+	(hi, lo)
+}
+
+/// Long division of `(hi, lo)` by `denom`, as quotient and remainder.
+/// `None` when the quotient needs more than 128 bits.
+fn div_wide(hi: u128, lo: u128, denom: u128) -> Option<(u128, u128)> {
+	if hi >= denom {
+		return None;
+	}
+	let (mut quotient, mut remainder) = (0_u128, hi);
+	for bit in (0..128).rev() {
+		let carry = remainder >> 127;
+		remainder = (remainder << 1) | ((lo >> bit) & 1);
+		quotient <<= 1;
+		if carry == 1 || remainder >= denom {
+			remainder = remainder.wrapping_sub(denom);
+			quotient |= 1;
+		}
+	}
+	Some((quotient, remainder))
+}
+
+/// Rounds half to even to `SCALE` places.
+impl<const SCALE: u32> TryFrom<&Numeral> for Fixed<SCALE> {
+	type Error = Error<'static>;
+
+	fn try_from(value: &Numeral) -> Result<Self, Self::Error> {
+		let overflow = || Error::Overflow("Value does not fit in Fixed");
+
+		match value.round(Precision::new(SCALE)) {
+			Numeral::Small(value) => SCALE
+				.checked_sub(value.scale())
+				.and_then(|shift| 10_i128.checked_pow(shift))
+				.and_then(|power| value.mantissa().checked_mul(power))
+				.map(Self)
+				.ok_or_else(overflow),
+			#[cfg(feature = "big-decimal")]
+			Numeral::Large(value) => num::ToPrimitive::to_i128(
+				&value.with_scale(SCALE as i64).into_bigint_and_exponent().0,
+			)
+			.map(Self)
+			.ok_or_else(overflow),
+		}
+	}
+}
+
+impl<const SCALE: u32> From<Fixed<SCALE>> for Rational {
+	fn from(value: Fixed<SCALE>) -> Self {
+		Rational::new(value.0, Fixed::<SCALE>::FACTOR)
+			.expect("FACTOR is never zero")
+	}
+}
+
+impl<const SCALE: u32> FromStr for Fixed<SCALE> {
+	type Err = Error<'static>;
+
+	fn from_str(input: &str) -> Result<Self, Self::Err> {
+		Self::try_from(&Numeral::parse(input.trim())?)
+	}
+}
+
+impl<const SCALE: u32> fmt::Display for Fixed<SCALE> {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		let sign = if self.0 < 0 { "-" } else { "" };
+		let factor = Self::FACTOR.unsigned_abs();
+		let (whole, fraction) = (
+			self.0.unsigned_abs() / factor,
+			self.0.unsigned_abs() % factor,
+		);
+
+		match SCALE {
+			0 => write!(f, "{}{}", sign, whole),
+			_ => write!(
+				f,
+				"{}{}.{:0width$}",
+				sign,
+				whole,
+				fraction,
+				width = SCALE as usize
+			),
+		}
+	}
+}
+
+impl<const SCALE: u32> Add for Fixed<SCALE> {
+	type Output = Self;
+
+	fn add(self, rhs: Self) -> Self {
+		self.checked_add(rhs).expect("Fixed addition overflowed")
+	}
+}
+
+impl<const SCALE: u32> Sub for Fixed<SCALE> {
+	type Output = Self;
+
+	fn sub(self, rhs: Self) -> Self {
+		self.checked_sub(rhs).expect("Fixed subtraction overflowed")
+	}
+}
+
+impl<const SCALE: u32> Mul for Fixed<SCALE> {
+	type Output = Self;
+
+	fn mul(self, rhs: Self) -> Self {
+		self.checked_mul(rhs)
+			.expect("Fixed multiplication overflowed")
+	}
+}
+
+impl<const SCALE: u32> Div for Fixed<SCALE> {
+	type Output = Self;
+
+	/// Panics on division by zero, like the integer types.
+	fn div(self, rhs: Self) -> Self {
+		self.checked_div(rhs)
+			.expect("Fixed division by zero or overflow")
+	}
+}
+
+impl<const SCALE: u32> Neg for Fixed<SCALE> {
+	type Output = Self;
+
+	fn neg(self) -> Self {
+		self.checked_neg().expect("Fixed negation overflowed")
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	type Cents = Fixed<2>;
+
+	fn cents(input: &str) -> Cents {
+		input.parse().unwrap()
+	}
+
+	#[test]
+	fn test_fixed_arithmetic_rounds_half_even() {
+		assert_eq!(cents("0.1") + cents("0.2"), cents("0.3"));
+		assert_eq!(cents("1.005").raw(), 100);
+		assert_eq!(cents("1.015").raw(), 102);
+		assert_eq!(cents("-0.05").to_string(), "-0.05");
+		assert_eq!(Cents::from_integer(7).unwrap().to_string(), "7.00");
+
+		assert_eq!((cents("10") / cents("3")).to_string(), "3.33");
+		assert_eq!((cents("-1.25") * cents("0.5")).to_string(), "-0.62");
+		assert_eq!(cents("1").checked_div(Cents::ZERO), None);
+		assert_eq!(Cents::MAX.checked_add(Cents::ONE), None);
+		assert!(cents("2.50") > cents("2.49"));
+
+		assert_eq!(cents("12.34").to_numeral().unwrap().to_string(), "12.34");
+		assert_eq!(Rational::from(cents("0.25")).to_string(), "1/4");
+		assert_eq!(Fixed::<0>::from_raw(42).to_string(), "42");
+	}
+
+	#[test]
+	fn test_fixed_large_scale_near_limit() {
+		type Fine = Fixed<30>;
+		let whole = |value| Fine::from_integer(value).unwrap();
+
+		assert_eq!(whole(10_000) * whole(10_000), whole(100_000_000));
+		assert_eq!(whole(-10_000) * whole(10_000), whole(-100_000_000));
+		assert_eq!(whole(20_000).checked_mul(whole(10_000)), None);
+		assert_eq!(whole(100_000_000) / whole(4), whole(25_000_000));
+		assert_eq!(whole(100_000_000).checked_div(whole(1) / whole(2)), None);
+
+		let half = Fine::from_raw(Fine::FACTOR / 2);
+		assert_eq!((Fine::from_raw(1) * half).raw(), 0);
+		assert_eq!((Fine::from_raw(3) * half).raw(), 2);
+		assert_eq!((Fine::from_raw(-3) * half).raw(), -2);
+		assert_eq!(Fine::MIN.checked_mul(Fine::ONE), Some(Fine::MIN));
+	}
+
+	#[test]
+	fn test_fixed_neg_at_min() {
+		assert_eq!(Cents::MIN.checked_neg(), None);
+		assert_eq!(-Cents::MAX, Cents::from_raw(-i128::MAX));
+		assert_eq!(-cents("1.50"), cents("-1.50"));
+	}
+}
@@ -16,6 +16,14 @@
 // pub use input::Input;
 
 pub mod decimal;
+pub mod radix;
+pub mod units;
 
 mod number;
 pub use number::Number;
+
+mod rational;
+pub use rational::Rational;
+
+mod fixed;
+pub use fixed::Fixed;
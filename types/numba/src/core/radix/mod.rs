@@ -0,0 +1,218 @@
+//! Integers written in bases other than ten.
+//!
+//! ```
+//! use numba::radix::{self, Format};
+//!
+//! let mask = radix::parse("0b1111_0000").unwrap();
+//! assert_eq!(mask.to_string(), "240");
+//! assert_eq!(radix::format(&mask, Format::new(16)), "0xf0");
+//! assert_eq!(radix::format(&mask, Format::new(2).with_group(4)), "0b1111_0000");
+//! ```
+
+mod numeral;
+
+use num::{BigInt, Num};
+use thiserror::Error;
+
+#[derive(Debug, Clone, PartialEq, Error)]
+pub enum Error {
+	#[error("No digits provided")]
+	Empty,
+	#[error("Radix must be between 2 and 36, got {0}")]
+	InvalidRadix(u32),
+	#[error("Invalid digit '{digit}' for radix {radix} in '{input}'")]
+	InvalidDigit {
+		digit: char,
+		radix: u32,
+		input: String,
+	},
+	#[error("Only whole numbers have a radix representation: '{0}'")]
+	NotInteger(String),
+	#[error("Number too large: '{0}'")]
+	Overflow(String),
+}
+
+/// How [`format`] writes a number.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Format {
+	pub radix: u32,
+	/// Write `0b`, `0o` or `0x` for bases that have one.
+	pub prefix: bool,
+	/// Digits between `_` separators, counted from the right; `0` for none.
+	pub group: usize,
+}
+
+impl Format {
+	pub fn new(radix: u32) -> Self {
+		Self {
+			radix,
+			prefix: true,
+			group: 0,
+		}
+	}
+
+	pub fn with_prefix(mut self, prefix: bool) -> Self {
+		self.prefix = prefix;
+		self
+	}
+
+	pub fn with_group(mut self, group: usize) -> Self {
+		self.group = group;
+		self
+	}
+}
+
+/// The prefix written for `radix`, if it has one.
+pub fn prefix(radix: u32) -> Option<&'static str> {
+	match radix {
+		2 => Some("0b"),
+		8 => Some("0o"),
+		16 => Some("0x"),
+		_ => None,
+	}
+}
+
+/// Parses an integer, reading the base from its `0b`, `0o` or `0x` prefix
+/// and defaulting to decimal. Underscores between digits are ignored.
+pub fn parse(input: &str) -> Result<BigInt, Error> {
+	let (_, digits) = sign(input.trim());
+	let radix = [2, 8, 16]
+		.into_iter()
+		.find(|&radix| has_prefix(digits, radix))
+		.unwrap_or(10);
+	parse_radix(input, radix)
+}
+
+/// Parses an integer in `radix`, accepting that base's prefix if present.
+pub fn parse_radix(input: &str, radix: u32) -> Result<BigInt, Error> {
+	check(radix)?;
+	let trimmed = input.trim();
+	let (negative, mut digits) = sign(trimmed);
+	if has_prefix(digits, radix) {
+		digits = &digits[2..];
+	}
+
+	if digits.is_empty() {
+		return Err(Error::Empty);
+	}
+	// Separators only go between digits
+	let invalid = |(i, c): &(usize, char)| {
+		!c.is_digit(radix) && (*c != '_' || *i == 0 || *i == digits.len() - 1)
+	};
+	if let Some((_, digit)) = digits.char_indices().find(invalid) {
+		return Err(Error::InvalidDigit {
+			digit,
+			radix,
+			input: trimmed.to_string(),
+		});
+	}
+
+	let digits = digits.replace('_', "");
+	if digits.is_empty() {
+		return Err(Error::Empty);
+	}
+	let value =
+		BigInt::from_str_radix(&digits, radix).map_err(|_| Error::Empty)?;
+	Ok(if negative { -value } else { value })
+}
+
+/// Writes `value` in `format.radix` with lowercase digits, e.g. `-0xff`.
+pub fn format(value: &BigInt, format: Format) -> String {
+	let radix = format.radix.clamp(2, 36);
+	let digits = value.magnitude().to_str_radix(radix);
+	let digits = group(&digits, format.group);
+
+	let mut output = String::with_capacity(digits.len() + 3);
+	if value.sign() == num::bigint::Sign::Minus {
+		output.push('-');
+	}
+	if format.prefix {
+		output.push_str(prefix(radix).unwrap_or_default());
+	}
+	output.push_str(&digits);
+	output
+}
+
+fn check(radix: u32) -> Result<(), Error> {
+	match radix {
+		2..=36 => Ok(()),
+		_ => Err(Error::InvalidRadix(radix)),
+	}
+}
+
+fn sign(input: &str) -> (bool, &str) {
+	match input.as_bytes().first() {
+		Some(b'-') => (true, &input[1..]),
+		Some(b'+') => (false, &input[1..]),
+		_ => (false, input),
+	}
+}
+
+fn has_prefix(digits: &str, radix: u32) -> bool {
+	prefix(radix).is_some_and(|prefix| {
+		digits
+			.as_bytes()
+			.get(..2)
+			.is_some_and(|start| start.eq_ignore_ascii_case(prefix.as_bytes()))
+	})
+}
+
+fn group(digits: &str, size: usize) -> String {
+	if size == 0 || digits.len() <= size {
+		return digits.to_string();
+	}
+
+	let mut grouped = String::with_capacity(digits.len() + digits.len() / size);
+	for (i, c) in digits.chars().enumerate() {
+		if i != 0 && (digits.len() - i).is_multiple_of(size) {
+			grouped.push('_');
+		}
+		grouped.push(c);
+	}
+	grouped
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn test_parse_and_format_round_trip() {
+		assert_eq!(parse("0xFF").unwrap(), BigInt::from(255));
+		assert_eq!(parse("-0o17").unwrap(), BigInt::from(-15));
+		assert_eq!(parse("1_000_000").unwrap(), BigInt::from(1_000_000));
+		assert_eq!(parse_radix("zz", 36).unwrap(), BigInt::from(1295));
+		assert_eq!(parse_radix("0x10", 16).unwrap(), BigInt::from(16));
+
+		let big =
+			parse("0xffff_ffff_ffff_ffff_ffff_ffff_ffff_ffff_ff").unwrap();
+		let hex = format(&big, Format::new(16).with_group(4));
+		assert_eq!(hex, "0xff_ffff_ffff_ffff_ffff_ffff_ffff_ffff_ffff");
+		assert_eq!(parse(&hex).unwrap(), big);
+
+		let value = BigInt::from(-1295);
+		assert_eq!(format(&value, Format::new(36)), "-zz");
+		assert_eq!(format(&value, Format::new(10).with_group(3)), "-1_295");
+		assert_eq!(
+			format(&BigInt::from(5), Format::new(2).with_prefix(false)),
+			"101"
+		);
+
+		assert_eq!(parse("0x"), Err(Error::Empty));
+		assert_eq!(parse_radix("12", 37), Err(Error::InvalidRadix(37)));
+		assert!(matches!(
+			parse("0b102"),
+			Err(Error::InvalidDigit { digit: '2', .. })
+		));
+		assert!(matches!(
+			parse("1é"),
+			Err(Error::InvalidDigit { digit: 'é', .. })
+		));
+		for input in ["_ff", "0x_ff", "ff_", "0b1_"] {
+			assert!(matches!(
+				parse_radix(input, 16),
+				Err(Error::InvalidDigit { digit: '_', .. })
+			));
+		}
+	}
+}
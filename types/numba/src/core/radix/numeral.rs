@@ -0,0 +1,78 @@
+use super::{format, parse, Error, Format};
+use crate::decimal::Numeral;
+use num::{BigInt, ToPrimitive};
+
+impl Numeral {
+	/// Parses a whole number written with a `0b`, `0o` or `0x` prefix, or in
+	/// decimal without one.
+	pub fn from_radix(input: &str) -> Result<Self, Error> {
+		Self::from_big_int(parse(input)?)
+	}
+
+	/// Writes the value in another base; fails for fractional values.
+	pub fn to_radix(&self, format: Format) -> Result<String, Error> {
+		Ok(super::format(&self.to_big_int()?, format))
+	}
+
+	fn from_big_int(value: BigInt) -> Result<Self, Error> {
+		if let Some(small) = value.to_i128().and_then(|value| {
+			rust_decimal::Decimal::try_from_i128_with_scale(value, 0).ok()
+		}) {
+			return Ok(Self::Small(small));
+		}
+
+		#[cfg(feature = "big-decimal")]
+		return Ok(Self::Large(bigdecimal::BigDecimal::from(value)));
+
+		#[cfg(not(feature = "big-decimal"))]
+		Err(Error::Overflow(value.to_string()))
+	}
+
+	fn to_big_int(&self) -> Result<BigInt, Error> {
+		match self {
+			Self::Small(value) => {
+				let value = value.normalize();
+				match value.scale() {
+					0 => Ok(BigInt::from(value.mantissa())),
+					_ => Err(Error::NotInteger(value.to_string())),
+				}
+			}
+			#[cfg(feature = "big-decimal")]
+			Self::Large(value) => match value.is_integer() {
+				true => Ok(value.with_scale(0).into_bigint_and_exponent().0),
+				false => Err(Error::NotInteger(value.to_string())),
+			},
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn test_numeral_radix_round_trip() {
+		let small = Numeral::from_radix("0xff").unwrap();
+		assert!(matches!(small, Numeral::Small(_)));
+		assert_eq!(small.to_string(), "255");
+		assert_eq!(small.to_radix(Format::new(2)).unwrap(), "0b11111111");
+
+		let whole = Numeral::parse("42.000").unwrap();
+		assert_eq!(whole.to_radix(Format::new(16)).unwrap(), "0x2a");
+		assert!(matches!(
+			Numeral::parse("1.5").unwrap().to_radix(Format::new(16)),
+			Err(Error::NotInteger(_))
+		));
+
+		#[cfg(feature = "big-decimal")]
+		{
+			let hex = "0x1_0000_0000_0000_0000_0000_0000_0000_0000";
+			let large = Numeral::from_radix(hex).unwrap();
+			assert!(matches!(large, Numeral::Large(_)));
+			assert_eq!(
+				large.to_radix(Format::new(16).with_group(4)).unwrap(),
+				hex
+			);
+		}
+	}
+}
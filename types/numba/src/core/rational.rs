@@ -0,0 +1,212 @@
+use crate::decimal::{Error, Numeral, Precision};
+use num::{
+	bigint::Sign, rational::BigRational, BigInt, One, Signed, ToPrimitive, Zero,
+};
+use std::{
+	fmt,
+	ops::{Add, Mul, Neg, Sub},
+	str::FromStr,
+};
+
+/// An exact fraction of two arbitrarily large integers, always kept in
+/// lowest terms with a positive denominator.
+///
+/// ```
+/// use numba::Rational;
+///
+/// let third: Rational = "1/3".parse().unwrap();
+/// let sum = third.clone() + third.clone() + third;
+/// assert_eq!(sum, Rational::from(1));
+/// assert_eq!("0.75".parse::<Rational>().unwrap().to_string(), "3/4");
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Rational(BigRational);
+
+impl Rational {
+	pub fn new(
+		numer: impl Into<BigInt>,
+		denom: impl Into<BigInt>,
+	) -> Result<Self, Error<'static>> {
+		let denom = denom.into();
+		if denom.is_zero() {
+			return Err(Error::DivisionByZero);
+		}
+		Ok(Self(BigRational::new(numer.into(), denom)))
+	}
+
+	pub fn numer(&self) -> &BigInt {
+		self.0.numer()
+	}
+
+	pub fn denom(&self) -> &BigInt {
+		self.0.denom()
+	}
+
+	pub fn is_zero(&self) -> bool {
+		self.0.is_zero()
+	}
+
+	pub fn is_integer(&self) -> bool {
+		self.0.is_integer()
+	}
+
+	pub fn is_negative(&self) -> bool {
+		self.numer().sign() == Sign::Minus
+	}
+
+	pub fn abs(&self) -> Self {
+		Self(self.0.abs())
+	}
+
+	pub fn recip(&self) -> Result<Self, Error<'static>> {
+		if self.is_zero() {
+			return Err(Error::DivisionByZero);
+		}
+		Ok(Self(self.0.recip()))
+	}
+
+	pub fn checked_div(&self, rhs: &Self) -> Result<Self, Error<'static>> {
+		if rhs.is_zero() {
+			return Err(Error::DivisionByZero);
+		}
+		Ok(Self(&self.0 / &rhs.0))
+	}
+
+	/// The nearest decimal with `precision.scale` places; exact when the
+	/// denominator divides a power of ten that fits.
+	pub fn to_numeral(
+		&self,
+		precision: Precision,
+	) -> Result<Numeral, Error<'static>> {
+		Numeral::parse(self.numer())?
+			.checked_div(&Numeral::parse(self.denom())?, precision)
+	}
+}
+
+impl From<i64> for Rational {
+	fn from(value: i64) -> Self {
+		Self(BigRational::from_integer(value.into()))
+	}
+}
+
+impl From<BigInt> for Rational {
+	fn from(value: BigInt) -> Self {
+		Self(BigRational::from_integer(value))
+	}
+}
+
+/// Exact: `1.25` becomes `5/4`.
+impl From<&Numeral> for Rational {
+	fn from(value: &Numeral) -> Self {
+		let (mantissa, scale) = match value {
+			Numeral::Small(value) => {
+				(BigInt::from(value.mantissa()), i64::from(value.scale()))
+			}
+			#[cfg(feature = "big-decimal")]
+			Numeral::Large(value) => value.as_bigint_and_exponent(),
+		};
+
+		let power = BigInt::from(10).pow(scale.unsigned_abs() as u32);
+		match scale.signum() {
+			-1 => Self(BigRational::from_integer(mantissa * power)),
+			_ => Self(BigRational::new(mantissa, power)),
+		}
+	}
+}
+
+impl TryFrom<&Rational> for f64 {
+	type Error = Error<'static>;
+
+	fn try_from(value: &Rational) -> Result<Self, Self::Error> {
+		value
+			.0
+			.to_f64()
+			.ok_or(Error::Overflow("Rational out of f64 range"))
+	}
+}
+
+/// Accepts `"3/4"`, `"-7"` and anything [`Numeral::parse`] does.
+impl FromStr for Rational {
+	type Err = Error<'static>;
+
+	fn from_str(input: &str) -> Result<Self, Self::Err> {
+		let Some((numer, denom)) = input.split_once('/') else {
+			return Ok(Self::from(&Numeral::parse(input.trim())?));
+		};
+
+		let integer = |part: &str| {
+			BigInt::from_str(part.trim()).map_err(|_| {
+				Error::InvalidFormat("Expected an integer fraction")
+			})
+		};
+		Self::new(integer(numer)?, integer(denom)?)
+	}
+}
+
+impl fmt::Display for Rational {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		if self.denom().is_one() {
+			write!(f, "{}", self.numer())
+		} else {
+			write!(f, "{}/{}", self.numer(), self.denom())
+		}
+	}
+}
+
+impl Add for Rational {
+	type Output = Self;
+
+	fn add(self, rhs: Self) -> Self {
+		Self(self.0 + rhs.0)
+	}
+}
+
+impl Sub for Rational {
+	type Output = Self;
+
+	fn sub(self, rhs: Self) -> Self {
+		Self(self.0 - rhs.0)
+	}
+}
+
+impl Mul for Rational {
+	type Output = Self;
+
+	fn mul(self, rhs: Self) -> Self {
+		Self(self.0 * rhs.0)
+	}
+}
+
+impl Neg for Rational {
+	type Output = Self;
+
+	fn neg(self) -> Self {
+		Self(-self.0)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn test_rational_is_exact() {
+		let tenth: Rational = "0.1".parse().unwrap();
+		let sum = tenth.clone() + tenth.clone() + tenth;
+		assert_eq!(sum, "3/10".parse().unwrap());
+		assert_eq!(sum.to_string(), "3/10");
+
+		let third = Rational::new(2, 6).unwrap();
+		assert_eq!(third.to_string(), "1/3");
+		assert!(third < "0.34".parse().unwrap());
+		assert_eq!(
+			third.to_numeral(Precision::new(4)).unwrap().to_string(),
+			"0.3333"
+		);
+
+		assert_eq!(Rational::new(3, -4).unwrap().to_string(), "-3/4");
+		assert!(Rational::new(1, 0).is_err());
+		assert!(third.checked_div(&Rational::from(0)).is_err());
+		assert_eq!("1.5e2".parse::<Rational>().unwrap(), Rational::from(150));
+	}
+}
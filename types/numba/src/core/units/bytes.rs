@@ -0,0 +1,100 @@
+use super::{split, trimmed, unknown, Error};
+use std::{fmt, str::FromStr};
+
+const BINARY: [&str; 7] = ["B", "KiB", "MiB", "GiB", "TiB", "PiB", "EiB"];
+const DECIMAL: [&str; 7] = ["B", "KB", "MB", "GB", "TB", "PB", "EB"];
+
+/// A size in bytes. Displays with binary (IEC) units.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Bytes(u64);
+
+impl Bytes {
+	pub const fn new(bytes: u64) -> Self {
+		Self(bytes)
+	}
+
+	pub const fn get(self) -> u64 {
+		self.0
+	}
+
+	/// The size in the given unit, e.g. `"MiB"` or `"GB"`.
+	pub fn to_unit(self, unit: &str) -> Result<f64, Error> {
+		multiplier(unit)
+			.map(|multiplier| self.0 as f64 / multiplier)
+			.ok_or_else(|| unknown(unit, unit))
+	}
+
+	/// Formats with decimal (SI) units, as drive vendors do.
+	pub fn decimal(self) -> String {
+		scaled(self.0, 1000.0, &DECIMAL)
+	}
+}
+
+impl From<u64> for Bytes {
+	fn from(bytes: u64) -> Self {
+		Self(bytes)
+	}
+}
+
+impl FromStr for Bytes {
+	type Err = Error;
+
+	fn from_str(input: &str) -> Result<Self, Self::Err> {
+		let (value, unit) = split(input)?;
+		if value < 0.0 {
+			return Err(Error::Negative(input.to_string()));
+		}
+
+		let multiplier =
+			multiplier(unit).ok_or_else(|| unknown(unit, input))?;
+		let bytes = (value * multiplier).round();
+		// u64::MAX as f64 rounds up to 2^64, which is itself out of range
+		if bytes >= u64::MAX as f64 {
+			return Err(Error::OutOfRange(input.to_string()));
+		}
+		Ok(Self(bytes as u64))
+	}
+}
+
+impl fmt::Display for Bytes {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		f.write_str(&scaled(self.0, 1024.0, &BINARY))
+	}
+}
+
+/// Bytes per unit. Bare `K`, `M`, ... are treated as binary.
+fn multiplier(unit: &str) -> Option<f64> {
+	let lower = unit.to_ascii_lowercase();
+	let (prefix, binary) = match lower.as_str() {
+		"" | "b" | "byte" | "bytes" => return Some(1.0),
+		unit => match unit.strip_suffix("ib") {
+			Some(prefix) => (prefix, true),
+			None => match unit.strip_suffix('b') {
+				Some(prefix) => (prefix, false),
+				None => (unit, true),
+			},
+		},
+	};
+
+	let power = ["k", "m", "g", "t", "p", "e"]
+		.iter()
+		.position(|&p| p == prefix)? as i32
+		+ 1;
+	let base: f64 = if binary { 1024.0 } else { 1000.0 };
+	Some(base.powi(power))
+}
+
+fn scaled(bytes: u64, base: f64, units: &[&str]) -> String {
+	let mut value = bytes as f64;
+	let mut index = 0;
+	while value >= base && index < units.len() - 1 {
+		value /= base;
+		index += 1;
+	}
+
+	if index == 0 {
+		format!("{} {}", bytes, units[0])
+	} else {
+		format!("{} {}", trimmed(value, 1), units[index])
+	}
+}
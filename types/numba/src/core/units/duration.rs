@@ -0,0 +1,76 @@
+use super::{unknown, Error};
+use std::{fmt, str::FromStr, time};
+
+/// A span of time that parses compound strings like `"1h30m"` or `"250ms"`.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Duration(time::Duration);
+
+const UNITS: [(&str, u64); 4] =
+	[("d", 86_400), ("h", 3_600), ("m", 60), ("s", 1)];
+
+impl Duration {
+	pub const fn new(duration: time::Duration) -> Self {
+		Self(duration)
+	}
+
+	pub const fn get(self) -> time::Duration {
+		self.0
+	}
+}
+
+impl From<time::Duration> for Duration {
+	fn from(duration: time::Duration) -> Self {
+		Self(duration)
+	}
+}
+
+impl From<Duration> for time::Duration {
+	fn from(duration: Duration) -> Self {
+		duration.0
+	}
+}
+
+impl FromStr for Duration {
+	type Err = Error;
+
+	/// Parses with [`intime::parse`], so both crates accept the same
+	/// grammar.
+	fn from_str(input: &str) -> Result<Self, Self::Err> {
+		if input.trim_start().starts_with('-') {
+			return Err(Error::Negative(input.to_string()));
+		}
+
+		intime::parse(input).map(Self).map_err(|e| match e {
+			intime::Error::Empty => Error::Empty,
+			intime::Error::UnknownUnit(unit) => unknown(&unit, input),
+			intime::Error::OutOfRange(_) => {
+				Error::OutOfRange(input.to_string())
+			}
+			_ => Error::InvalidNumber(input.to_string()),
+		})
+	}
+}
+
+impl fmt::Display for Duration {
+	/// Shows the two largest non-zero components, e.g. `1h 30m` or `250ms`.
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		let mut secs = self.0.as_secs();
+		if secs == 0 {
+			let nanos = self.0.subsec_nanos();
+			return match nanos {
+				n if n >= 1_000_000 => write!(f, "{}ms", n / 1_000_000),
+				n if n >= 1_000 => write!(f, "{}µs", n / 1_000),
+				n => write!(f, "{}ns", n),
+			};
+		}
+
+		let mut parts = Vec::new();
+		for (unit, size) in UNITS {
+			if secs >= size {
+				parts.push(format!("{}{}", secs / size, unit));
+				secs %= size;
+			}
+		}
+		f.write_str(&parts.into_iter().take(2).collect::<Vec<_>>().join(" "))
+	}
+}
@@ -0,0 +1,62 @@
+use super::{split, trimmed, unknown, Error};
+use std::{fmt, str::FromStr};
+
+const UNITS: [(&str, f64); 4] =
+	[("THz", 1e12), ("GHz", 1e9), ("MHz", 1e6), ("kHz", 1e3)];
+
+/// A frequency in hertz, e.g. a CPU clock speed or refresh rate.
+#[derive(Debug, Default, Clone, Copy, PartialEq, PartialOrd)]
+pub struct Frequency(f64);
+
+impl Frequency {
+	pub const fn from_hertz(hertz: f64) -> Self {
+		Self(hertz)
+	}
+
+	pub fn from_megahertz(megahertz: f64) -> Self {
+		Self(megahertz * 1e6)
+	}
+
+	pub const fn hertz(self) -> f64 {
+		self.0
+	}
+
+	pub fn megahertz(self) -> f64 {
+		self.0 / 1e6
+	}
+
+	pub fn gigahertz(self) -> f64 {
+		self.0 / 1e9
+	}
+}
+
+impl FromStr for Frequency {
+	type Err = Error;
+
+	fn from_str(input: &str) -> Result<Self, Self::Err> {
+		let (value, unit) = split(input)?;
+		if value < 0.0 {
+			return Err(Error::Negative(input.to_string()));
+		}
+
+		let multiplier = match unit.to_ascii_lowercase().as_str() {
+			"" | "hz" => 1.0,
+			"khz" => 1e3,
+			"mhz" => 1e6,
+			"ghz" => 1e9,
+			"thz" => 1e12,
+			_ => return Err(unknown(unit, input)),
+		};
+		Ok(Self(value * multiplier))
+	}
+}
+
+impl fmt::Display for Frequency {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		let (unit, size) = UNITS
+			.into_iter()
+			.find(|&(_, size)| self.0 >= size)
+			.unwrap_or(("Hz", 1.0));
+		write!(f, "{} {}", trimmed(self.0 / size, 2), unit)
+	}
+}
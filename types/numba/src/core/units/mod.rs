@@ -0,0 +1,128 @@
+//! Typed quantities that parse from and display as human-friendly strings.
+//!
+//! ```
+//! use numba::units::{Bytes, Temperature};
+//!
+//! let size: Bytes = "1.5GiB".parse().unwrap();
+//! assert_eq!(size.to_string(), "1.5 GiB");
+//!
+//! let temp: Temperature = "68°F".parse().unwrap();
+//! assert_eq!(temp.celsius(), 20.0);
+//! ```
+
+mod bytes;
+pub use bytes::Bytes;
+
+mod duration;
+pub use duration::Duration;
+
+mod frequency;
+pub use frequency::Frequency;
+
+mod temperature;
+pub use temperature::Temperature;
+
+use thiserror::Error;
+
+#[derive(Debug, Clone, PartialEq, Error)]
+pub enum Error {
+	#[error("No quantity provided")]
+	Empty,
+	#[error("Invalid number in '{0}'")]
+	InvalidNumber(String),
+	#[error("Unknown unit '{unit}' in '{input}'")]
+	UnknownUnit { unit: String, input: String },
+	#[error("Quantity cannot be negative: '{0}'")]
+	Negative(String),
+	#[error("Quantity out of range: '{0}'")]
+	OutOfRange(String),
+}
+
+/// Splits `"1.5 GiB"` into `(1.5, "GiB")`.
+fn split(input: &str) -> Result<(f64, &str), Error> {
+	let input = input.trim();
+	if input.is_empty() {
+		return Err(Error::Empty);
+	}
+
+	let end = input
+		.char_indices()
+		.find(|&(i, c)| {
+			!(c.is_ascii_digit()
+				|| c == '.' || c == '_'
+				|| (i == 0 && (c == '-' || c == '+')))
+		})
+		.map_or(input.len(), |(i, _)| i);
+	let (number, unit) = input.split_at(end);
+
+	let value = number
+		.replace('_', "")
+		.parse::<f64>()
+		.map_err(|_| Error::InvalidNumber(input.to_string()))?;
+	Ok((value, unit.trim()))
+}
+
+fn unknown(unit: &str, input: &str) -> Error {
+	Error::UnknownUnit {
+		unit: unit.to_string(),
+		input: input.trim().to_string(),
+	}
+}
+
+/// Formats with up to `decimals` places, dropping trailing zeros.
+fn trimmed(value: f64, decimals: usize) -> String {
+	let formatted = format!("{:.*}", decimals, value);
+	if formatted.contains('.') {
+		formatted
+			.trim_end_matches('0')
+			.trim_end_matches('.')
+			.to_string()
+	} else {
+		formatted
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn test_parse_and_display() {
+		let size: Bytes = "1.5GiB".parse().unwrap();
+		assert_eq!(size.get(), 1_610_612_736);
+		assert_eq!(size.to_string(), "1.5 GiB");
+		assert_eq!("2 MB".parse::<Bytes>().unwrap().get(), 2_000_000);
+		assert_eq!(Bytes::new(1_500_000).decimal(), "1.5 MB");
+
+		let wait: Duration = "1h30m".parse().unwrap();
+		assert_eq!(wait.get().as_secs(), 5400);
+		assert_eq!(wait.to_string(), "1h 30m");
+		assert_eq!("250ms".parse::<Duration>().unwrap().to_string(), "250ms");
+
+		let clock: Frequency = "3.2GHz".parse().unwrap();
+		assert_eq!(clock.hertz(), 3.2e9);
+		assert_eq!(clock.to_string(), "3.2 GHz");
+
+		let boiling: Temperature = "373.15 K".parse().unwrap();
+		assert!((boiling.fahrenheit() - 212.0).abs() < 1e-9);
+		assert_eq!(boiling.to_string(), "100 °C");
+
+		assert!(matches!(
+			"12 parsecs".parse::<Bytes>(),
+			Err(Error::UnknownUnit { .. })
+		));
+		assert_eq!("".parse::<Frequency>(), Err(Error::Empty));
+		assert!(matches!(
+			"99999999999999999999h".parse::<Duration>(),
+			Err(Error::OutOfRange(_))
+		));
+		assert!(matches!(
+			"-5s".parse::<Duration>(),
+			Err(Error::Negative(_))
+		));
+		assert!(matches!(
+			"99999999 EiB".parse::<Bytes>(),
+			Err(Error::OutOfRange(_))
+		));
+	}
+}
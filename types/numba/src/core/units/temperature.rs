@@ -0,0 +1,56 @@
+use super::{split, trimmed, unknown, Error};
+use std::{fmt, str::FromStr};
+
+/// A temperature, stored in degrees Celsius.
+#[derive(Debug, Default, Clone, Copy, PartialEq, PartialOrd)]
+pub struct Temperature(f64);
+
+impl Temperature {
+	pub const fn from_celsius(celsius: f64) -> Self {
+		Self(celsius)
+	}
+
+	pub fn from_fahrenheit(fahrenheit: f64) -> Self {
+		Self((fahrenheit - 32.0) * 5.0 / 9.0)
+	}
+
+	pub fn from_kelvin(kelvin: f64) -> Self {
+		Self(kelvin - 273.15)
+	}
+
+	pub const fn celsius(self) -> f64 {
+		self.0
+	}
+
+	pub fn fahrenheit(self) -> f64 {
+		self.0 * 9.0 / 5.0 + 32.0
+	}
+
+	pub fn kelvin(self) -> f64 {
+		self.0 + 273.15
+	}
+}
+
+impl FromStr for Temperature {
+	type Err = Error;
+
+	/// Accepts `C`, `F` or `K`, with or without a degree sign. Bare numbers
+	/// are Celsius.
+	fn from_str(input: &str) -> Result<Self, Self::Err> {
+		let (value, unit) = split(input)?;
+		let unit = unit.trim_start_matches('°');
+
+		match unit.to_ascii_lowercase().as_str() {
+			"" | "c" | "celsius" => Ok(Self::from_celsius(value)),
+			"f" | "fahrenheit" => Ok(Self::from_fahrenheit(value)),
+			"k" | "kelvin" => Ok(Self::from_kelvin(value)),
+			_ => Err(unknown(unit, input)),
+		}
+	}
+}
+
+impl fmt::Display for Temperature {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		write!(f, "{} °C", trimmed(self.0, 1))
+	}
+}
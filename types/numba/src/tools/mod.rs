@@ -1,2 +1,7 @@
+mod parse_many;
+pub use parse_many::{parse_many, parse_many_scalar, FAST_DIGITS};
+
 mod remove_commas;
 pub use remove_commas::remove_commas;
+
+pub mod stream;
@@ -13,6 +13,8 @@ use std::simd::prelude::*;
 /// # Example
 ///
 /// ```
+/// use numba::remove_commas;
+///
 /// let result = remove_commas("1,234,567.89");
 /// assert_eq!(result, "1234567.89");
 /// ```
@@ -22,15 +24,14 @@ pub fn remove_commas(input: &str) -> String {
 	let mut result = Vec::with_capacity(len);
 
 	// Process 32 bytes at a time
-	let chunks = bytes.chunks_exact(32);
-	let remainder = chunks.remainder();
+	let (chunks, remainder) = bytes.as_chunks::<32>();
 
 	// Create a vector with all elements set to the comma byte
 	let comma_vec = Simd::<u8, 32>::splat(b',');
 
 	for chunk in chunks {
 		// Load the chunk into a SIMD vector
-		let v = Simd::<u8, 32>::from_slice(chunk);
+		let v = Simd::<u8, 32>::from_array(*chunk);
 
 		// Create a mask where the bytes are not equal to the comma byte
 		let mask = v.simd_ne(comma_vec);
@@ -0,0 +1,110 @@
+use super::{names, output, run, Backend, Package, Update};
+use anyhow::Result;
+
+/// Debian and Ubuntu, via `apt-get` with `dpkg-query` for listings.
+pub struct Apt;
+
+impl Backend for Apt {
+    fn name(&self) -> &'static str {
+        "apt"
+    }
+
+    fn program(&self) -> &'static str {
+        "apt-get"
+    }
+
+    fn platforms(&self) -> &'static [&'static str] {
+        &["linux"]
+    }
+
+    fn search(&self, query: &str) -> Result<Vec<Package>> {
+        let stdout = output("apt-cache", &["search", "--names-only", query])?;
+        Ok(stdout
+            .lines()
+            .filter_map(|line| line.split(" - ").next())
+            .filter(|name| !name.is_empty())
+            .map(|name| Package::new(self.name(), name.trim(), None))
+            .collect())
+    }
+
+    fn install(&self, packages: &[String]) -> Result<()> {
+        run(self.program(), &["install", "-y"], packages)
+    }
+
+    fn uninstall(&self, packages: &[String]) -> Result<()> {
+        run(self.program(), &["remove", "-y"], packages)
+    }
+
+    fn upgrade(&self, packages: &[String]) -> Result<()> {
+        if packages.is_empty() {
+            run(self.program(), &["update"], &[])?;
+            run(self.program(), &["upgrade", "-y"], &[])
+        } else {
+            run(
+                self.program(),
+                &["install", "--only-upgrade", "-y"],
+                packages,
+            )
+        }
+    }
+
+    /// A trailing `*` lets apt match the full Debian version.
+    fn install_version(&self, package: &str, version: &str) -> Result<()> {
+        run(
+            self.program(),
+            &["install", "-y"],
+            &[format!("{}={}*", package, version)],
+        )
+    }
+
+    fn hold(&self, packages: &[String]) -> Result<()> {
+        run("apt-mark", &["hold"], packages)
+    }
+
+    fn unhold(&self, packages: &[String]) -> Result<()> {
+        run("apt-mark", &["unhold"], packages)
+    }
+
+    fn held(&self) -> Result<Vec<String>> {
+        let stdout = output("apt-mark", &["showhold"])?;
+        Ok(names(self.name(), &stdout)
+            .into_iter()
+            .map(|p| p.name)
+            .collect())
+    }
+
+    fn list(&self) -> Result<Vec<Package>> {
+        let stdout = output("dpkg-query", &["-W", "-f", "${Package}\t${Version}\n"])?;
+        Ok(parse_tabbed(self.name(), &stdout))
+    }
+
+    fn explicit(&self) -> Result<Vec<Package>> {
+        let stdout = output("apt-mark", &["showmanual"])?;
+        Ok(names(self.name(), &stdout))
+    }
+
+    fn outdated(&self) -> Result<Vec<Update>> {
+        let stdout = output("apt", &["list", "--upgradable"])?;
+        Ok(stdout
+            .lines()
+            .filter_map(|line| {
+                // bat/noble-updates 0.24.0-2 amd64 [upgradable from: 0.24.0-1]
+                let (name, rest) = line.split_once('/')?;
+                let available = rest.split_whitespace().nth(1)?;
+                let installed = line
+                    .split_once("from: ")
+                    .map(|(_, version)| version.trim_end_matches(']').to_string());
+                Some(Update::new(self.name(), name, installed, available))
+            })
+            .collect())
+    }
+}
+
+/// Parses `name<TAB>version` lines, as produced by `dpkg-query` and `rpm`.
+pub(super) fn parse_tabbed(backend: &'static str, output: &str) -> Vec<Package> {
+    output
+        .lines()
+        .filter_map(|line| line.split_once('\t'))
+        .map(|(name, version)| Package::new(backend, name, Some(version.to_string())))
+        .collect()
+}
@@ -0,0 +1,98 @@
+use super::{names, output, run, Backend, Package, Update};
+use anyhow::Result;
+
+/// Homebrew, preferred on macOS and available as a fallback on Linux.
+pub struct Brew;
+
+impl Backend for Brew {
+    fn name(&self) -> &'static str {
+        "brew"
+    }
+
+    fn program(&self) -> &'static str {
+        "brew"
+    }
+
+    fn platforms(&self) -> &'static [&'static str] {
+        &["macos", "linux"]
+    }
+
+    fn search(&self, query: &str) -> Result<Vec<Package>> {
+        let stdout = output(self.program(), &["search", query])?;
+        Ok(stdout
+            .lines()
+            .map(str::trim)
+            .filter(|line| !line.is_empty() && !line.starts_with("==>"))
+            .map(|name| Package::new(self.name(), name, None))
+            .collect())
+    }
+
+    fn install(&self, packages: &[String]) -> Result<()> {
+        run(self.program(), &["install"], packages)
+    }
+
+    fn uninstall(&self, packages: &[String]) -> Result<()> {
+        run(self.program(), &["uninstall"], packages)
+    }
+
+    fn upgrade(&self, packages: &[String]) -> Result<()> {
+        run(self.program(), &["upgrade"], packages)
+    }
+
+    fn hold(&self, packages: &[String]) -> Result<()> {
+        run(self.program(), &["pin"], packages)
+    }
+
+    fn unhold(&self, packages: &[String]) -> Result<()> {
+        run(self.program(), &["unpin"], packages)
+    }
+
+    fn held(&self) -> Result<Vec<String>> {
+        let stdout = output(self.program(), &["list", "--pinned"])?;
+        Ok(names(self.name(), &stdout)
+            .into_iter()
+            .map(|p| p.name)
+            .collect())
+    }
+
+    fn list(&self) -> Result<Vec<Package>> {
+        let stdout = output(self.program(), &["list", "--versions"])?;
+        Ok(stdout
+            .lines()
+            .filter_map(|line| {
+                let mut parts = line.split_whitespace();
+                let name = parts.next()?;
+                // Multiple installed versions are listed oldest first
+                Some(Package::new(
+                    self.name(),
+                    name,
+                    parts.last().map(String::from),
+                ))
+            })
+            .collect())
+    }
+
+    /// Formulae and casks installed on request, not as dependencies.
+    fn explicit(&self) -> Result<Vec<Package>> {
+        let stdout = output(self.program(), &["leaves", "--installed-on-request"])?;
+        Ok(names(self.name(), &stdout))
+    }
+
+    fn outdated(&self) -> Result<Vec<Update>> {
+        let stdout = output(self.program(), &["outdated", "--verbose"])?;
+        Ok(stdout
+            .lines()
+            .filter_map(|line| {
+                // bat (0.23.0, 0.24.0) < 0.25.0
+                let (name, rest) = line.split_once(' ')?;
+                let (installed, available) = rest.rsplit_once(" < ")?;
+                let installed = installed
+                    .trim_matches(|c| c == '(' || c == ')')
+                    .rsplit(", ")
+                    .next()
+                    .map(String::from);
+                Some(Update::new(self.name(), name, installed, available.trim()))
+            })
+            .collect())
+    }
+}
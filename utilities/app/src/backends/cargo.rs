@@ -0,0 +1,207 @@
+use super::{output, run, Backend, Package, Update};
+use anyhow::Result;
+use logline::debug;
+
+/// Binaries installed from crates.io with `cargo install`.
+pub struct Cargo;
+
+impl Backend for Cargo {
+    fn name(&self) -> &'static str {
+        "cargo"
+    }
+
+    fn program(&self) -> &'static str {
+        "cargo"
+    }
+
+    fn platforms(&self) -> &'static [&'static str] {
+        &["windows", "macos", "linux"]
+    }
+
+    /// Runs after the system managers, which may install the toolchain.
+    fn stage(&self) -> u8 {
+        1
+    }
+
+    fn search(&self, query: &str) -> Result<Vec<Package>> {
+        let stdout = output(self.program(), &["search", query, "--limit", "20"])?;
+        Ok(parse_search(self.name(), &stdout))
+    }
+
+    fn install(&self, packages: &[String]) -> Result<()> {
+        run(self.program(), &["install", "--locked"], packages)
+    }
+
+    /// `=14.1` makes cargo pick the newest `14.1.x`, matching how pins are
+    /// checked.
+    fn install_version(&self, package: &str, version: &str) -> Result<()> {
+        let requirement = format!("={}", version);
+        run(
+            self.program(),
+            &["install", "--locked", "--version", &requirement],
+            &[package.to_string()],
+        )
+    }
+
+    fn uninstall(&self, packages: &[String]) -> Result<()> {
+        run(self.program(), &["uninstall"], packages)
+    }
+
+    /// `cargo install` rebuilds a crate only when a newer version exists.
+    fn upgrade(&self, packages: &[String]) -> Result<()> {
+        if !packages.is_empty() {
+            return self.install(packages);
+        }
+
+        let installed: Vec<String> = self.upgradable()?.into_iter().map(|p| p.name).collect();
+        if installed.is_empty() {
+            return Ok(());
+        }
+        self.install(&installed)
+    }
+
+    fn list(&self) -> Result<Vec<Package>> {
+        let stdout = output(self.program(), &["install", "--list"])?;
+        Ok(parse_list(self.name(), &stdout))
+    }
+
+    /// Only crates.io installs; reinstalling a path or git crate by name
+    /// would fetch whatever crates.io has under that name instead.
+    fn upgradable(&self) -> Result<Vec<Package>> {
+        let stdout = output(self.program(), &["install", "--list"])?;
+        Ok(parse_entries(self.name(), &stdout)
+            .into_iter()
+            .filter_map(|(package, source)| match source {
+                Some(source) => {
+                    debug!("Skipping {} installed from {}", package.name, source);
+                    None
+                }
+                None => Some(package),
+            })
+            .collect())
+    }
+
+    /// Looks up each installed crate on crates.io, one search per crate.
+    fn outdated(&self) -> Result<Vec<Update>> {
+        let mut updates = Vec::new();
+
+        for package in self.list()? {
+            let stdout = output(self.program(), &["search", &package.name, "--limit", "1"])?;
+            let latest = parse_search(self.name(), &stdout)
+                .into_iter()
+                .find(|found| found.name == package.name)
+                .and_then(|found| found.version);
+
+            match latest {
+                Some(latest) if package.version.as_deref() != Some(latest.as_str()) => {
+                    updates.push(Update::new(
+                        self.name(),
+                        package.name,
+                        package.version,
+                        latest,
+                    ));
+                }
+                Some(_) => {}
+                // Crates installed from git or a path aren't on crates.io
+                None => debug!("No crates.io release found for {}", package.name),
+            }
+        }
+
+        Ok(updates)
+    }
+}
+
+/// Parses `name = "version"    # description` lines.
+fn parse_search(backend: &'static str, output: &str) -> Vec<Package> {
+    output
+        .lines()
+        .filter_map(|line| line.split_once(" = "))
+        .map(|(name, rest)| {
+            let version = rest.split('"').nth(1).map(String::from);
+            Package::new(backend, name.trim(), version)
+        })
+        .collect()
+}
+
+/// Parses `name v1.2.3:` headers, skipping the indented binary names.
+fn parse_list(backend: &'static str, output: &str) -> Vec<Package> {
+    parse_entries(backend, output)
+        .into_iter()
+        .map(|(package, _)| package)
+        .collect()
+}
+
+/// Like [`parse_list`], keeping the `(/path)` or `(https://repo#rev)` source
+/// cargo prints for crates not installed from crates.io.
+fn parse_entries<'a>(backend: &'static str, output: &'a str) -> Vec<(Package, Option<&'a str>)> {
+    output
+        .lines()
+        .filter(|line| !line.starts_with(char::is_whitespace))
+        .filter_map(|line| {
+            let line = line.trim_end_matches(':');
+            let (line, source) = match line.split_once(" (") {
+                Some((line, source)) => (line, source.strip_suffix(')')),
+                None => (line, None),
+            };
+            let mut parts = line.split_whitespace();
+            let name = parts.next()?;
+            let version = parts.next().map(|v| v.trim_start_matches('v').to_string());
+            Some((Package::new(backend, name, version), source))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_list() {
+        let output = "\
+bat v0.24.0:
+    bat
+ripgrep v14.1.0:
+    rg
+slink v0.1.0 (/home/me/ccutils/utilities/slink):
+    slink
+";
+        let packages = parse_list("cargo", output);
+
+        assert_eq!(packages.len(), 3);
+        assert_eq!(packages[0].name, "bat");
+        assert_eq!(packages[0].version.as_deref(), Some("0.24.0"));
+        assert_eq!(packages[1].name, "ripgrep");
+        assert_eq!(packages[2].version.as_deref(), Some("0.1.0"));
+    }
+
+    #[test]
+    fn test_parse_entries_keeps_sources() {
+        let output = "\
+bat v0.24.0:
+    bat
+slink v0.1.0 (/home/me/ccutils/utilities/slink):
+    slink
+helix-term v25.1.0 (https://github.com/helix-editor/helix#a1b2c3d4):
+    hx
+";
+        let sources: Vec<(String, Option<&str>)> = parse_entries("cargo", output)
+            .into_iter()
+            .map(|(package, source)| (package.name, source))
+            .collect();
+
+        assert_eq!(
+            sources,
+            [
+                ("bat".to_string(), None),
+                (
+                    "slink".to_string(),
+                    Some("/home/me/ccutils/utilities/slink")
+                ),
+                (
+                    "helix-term".to_string(),
+                    Some("https://github.com/helix-editor/helix#a1b2c3d4")
+                ),
+            ]
+        );
+    }
+}
@@ -0,0 +1,108 @@
+use super::{apt::parse_tabbed, names, output, output_with, run, Backend, Package, Update};
+use anyhow::Result;
+use std::collections::HashMap;
+
+/// Fedora and RHEL, via `dnf` with `rpm` for listings.
+pub struct Dnf;
+
+impl Backend for Dnf {
+    fn name(&self) -> &'static str {
+        "dnf"
+    }
+
+    fn program(&self) -> &'static str {
+        "dnf"
+    }
+
+    fn platforms(&self) -> &'static [&'static str] {
+        &["linux"]
+    }
+
+    fn search(&self, query: &str) -> Result<Vec<Package>> {
+        let stdout = output(self.program(), &["search", "--quiet", query])?;
+        Ok(stdout
+            .lines()
+            .filter(|line| !line.starts_with(' ') && !line.starts_with('='))
+            .filter_map(|line| line.split(" : ").next())
+            .filter_map(|name| name.trim().rsplit_once('.').map(|(name, _arch)| name))
+            .map(|name| Package::new(self.name(), name, None))
+            .collect())
+    }
+
+    fn install(&self, packages: &[String]) -> Result<()> {
+        run(self.program(), &["install", "-y"], packages)
+    }
+
+    fn uninstall(&self, packages: &[String]) -> Result<()> {
+        run(self.program(), &["remove", "-y"], packages)
+    }
+
+    fn upgrade(&self, packages: &[String]) -> Result<()> {
+        run(self.program(), &["upgrade", "-y"], packages)
+    }
+
+    fn install_version(&self, package: &str, version: &str) -> Result<()> {
+        run(
+            self.program(),
+            &["install", "-y"],
+            &[format!("{}-{}*", package, version)],
+        )
+    }
+
+    /// Needs the versionlock plugin.
+    fn hold(&self, packages: &[String]) -> Result<()> {
+        run(self.program(), &["versionlock", "add"], packages)
+    }
+
+    fn unhold(&self, packages: &[String]) -> Result<()> {
+        run(self.program(), &["versionlock", "delete"], packages)
+    }
+
+    /// Locks are listed as `name-epoch:version-release.*`.
+    fn held(&self) -> Result<Vec<String>> {
+        let stdout = output(self.program(), &["versionlock", "list", "--quiet"])?;
+        Ok(stdout
+            .lines()
+            .filter_map(|line| {
+                let (name, _) = line.trim().split_once(':')?;
+                name.rsplit_once('-').map(|(name, _epoch)| name.to_string())
+            })
+            .collect())
+    }
+
+    fn list(&self) -> Result<Vec<Package>> {
+        let stdout = output("rpm", &["-qa", "--qf", "%{NAME}\t%{VERSION}-%{RELEASE}\n"])?;
+        Ok(parse_tabbed(self.name(), &stdout))
+    }
+
+    fn explicit(&self) -> Result<Vec<Package>> {
+        let stdout = output(
+            self.program(),
+            &["repoquery", "--userinstalled", "--queryformat", "%{name}\n"],
+        )?;
+        Ok(names(self.name(), &stdout))
+    }
+
+    /// `dnf check-update` exits with 100 when updates are available.
+    fn outdated(&self) -> Result<Vec<Update>> {
+        let stdout = output_with(self.program(), &["check-update", "--quiet"], &[0, 100])?;
+        let installed: HashMap<String, Option<String>> = self
+            .list()?
+            .into_iter()
+            .map(|package| (package.name, package.version))
+            .collect();
+
+        Ok(stdout
+            .lines()
+            // Obsoleted packages follow the first blank line
+            .take_while(|line| !line.trim().is_empty())
+            .filter_map(|line| {
+                let mut parts = line.split_whitespace();
+                let (name, _arch) = parts.next()?.rsplit_once('.')?;
+                let available = parts.next()?;
+                let current = installed.get(name).cloned().flatten();
+                Some(Update::new(self.name(), name, current, available))
+            })
+            .collect())
+    }
+}
@@ -0,0 +1,428 @@
+mod apt;
+mod brew;
+mod cargo;
+mod dnf;
+mod pacman;
+mod scoop;
+mod winget;
+
+pub use apt::Apt;
+pub use brew::Brew;
+pub use cargo::Cargo;
+pub use dnf::Dnf;
+pub use pacman::Pacman;
+pub use scoop::Scoop;
+pub use winget::Winget;
+
+use anyhow::{bail, Context, Result};
+use logline::{debug, info};
+use serde::Serialize;
+use std::{collections::HashSet, env, process::Command};
+
+/// A package as reported by a backend.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Package {
+    pub name: String,
+    pub version: Option<String>,
+    pub backend: &'static str,
+}
+
+impl Package {
+    pub fn new(backend: &'static str, name: impl Into<String>, version: Option<String>) -> Self {
+        Self {
+            name: name.into(),
+            version,
+            backend,
+        }
+    }
+}
+
+/// An installed package with a newer version available.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Update {
+    pub backend: &'static str,
+    pub name: String,
+    pub installed: Option<String>,
+    pub available: String,
+}
+
+impl Update {
+    pub fn new(
+        backend: &'static str,
+        name: impl Into<String>,
+        installed: Option<String>,
+        available: impl Into<String>,
+    ) -> Self {
+        Self {
+            backend,
+            name: name.into(),
+            installed,
+            available: available.into(),
+        }
+    }
+}
+
+/// A package manager the installer can drive.
+pub trait Backend {
+    /// Name used on the command line and in config, e.g. `winget`.
+    fn name(&self) -> &'static str;
+
+    /// Executable that must be on PATH for the backend to be usable.
+    fn program(&self) -> &'static str;
+
+    /// Operating systems (`std::env::consts::OS`) the backend runs on.
+    fn platforms(&self) -> &'static [&'static str];
+
+    fn search(&self, query: &str) -> Result<Vec<Package>>;
+    fn install(&self, packages: &[String]) -> Result<()>;
+    fn uninstall(&self, packages: &[String]) -> Result<()>;
+
+    /// Upgrades the given packages, or everything when the list is empty.
+    fn upgrade(&self, packages: &[String]) -> Result<()>;
+
+    /// Installs `package` at `version`, which may leave out trailing parts
+    /// (`14.1` for `14.1.0`).
+    fn install_version(&self, package: &str, version: &str) -> Result<()> {
+        let _ = (package, version);
+        bail!("{} can't install a specific version", self.name())
+    }
+
+    /// Keeps the backend from upgrading `packages`.
+    fn hold(&self, packages: &[String]) -> Result<()> {
+        let _ = packages;
+        bail!("{} doesn't support holding packages", self.name())
+    }
+
+    fn unhold(&self, packages: &[String]) -> Result<()> {
+        let _ = packages;
+        bail!("{} doesn't support holding packages", self.name())
+    }
+
+    /// Names of held packages, empty for backends without holds.
+    fn held(&self) -> Result<Vec<String>> {
+        Ok(Vec::new())
+    }
+
+    /// Lists installed packages.
+    fn list(&self) -> Result<Vec<Package>>;
+
+    /// Lists installed packages an upgrade can reach, leaving out ones the
+    /// backend can't fetch again. Defaults to everything installed.
+    fn upgradable(&self) -> Result<Vec<Package>> {
+        self.list()
+    }
+
+    /// Lists packages the user asked for, leaving out those pulled in as
+    /// dependencies. Defaults to everything installed.
+    fn explicit(&self) -> Result<Vec<Package>> {
+        self.list()
+    }
+
+    /// Lists installed packages that have a newer version available.
+    fn outdated(&self) -> Result<Vec<Update>>;
+
+    /// Order in which backends run during a sync; later stages start once
+    /// earlier ones finish, so they can rely on what those installed.
+    fn stage(&self) -> u8 {
+        0
+    }
+
+    fn is_supported(&self) -> bool {
+        self.platforms().contains(&env::consts::OS)
+    }
+
+    fn is_available(&self) -> bool {
+        self.is_supported() && which::which(self.program()).is_ok()
+    }
+}
+
+/// Every known backend, most preferred first. Native managers come before
+/// Homebrew so it only wins on macOS, and cargo is always the last resort.
+pub fn all() -> Vec<Box<dyn Backend>> {
+    vec![
+        Box::new(Winget),
+        Box::new(Scoop),
+        Box::new(Pacman),
+        Box::new(Apt),
+        Box::new(Dnf),
+        Box::new(Brew),
+        Box::new(Cargo),
+    ]
+}
+
+/// Backends usable on this machine, most preferred first.
+pub fn detect() -> Vec<Box<dyn Backend>> {
+    let backends: Vec<_> = all().into_iter().filter(|b| b.is_available()).collect();
+    debug!(
+        "Detected backends: {:?}",
+        backends.iter().map(|b| b.name()).collect::<Vec<_>>()
+    );
+    backends
+}
+
+/// Looks up a backend by name, failing if it isn't usable here.
+pub fn find(name: &str) -> Result<Box<dyn Backend>> {
+    let Some(backend) = all().into_iter().find(|b| b.name() == name) else {
+        bail!("Unknown package manager: {}", name);
+    };
+    if !backend.is_available() {
+        bail!("Package manager '{}' is not available on this system", name);
+    }
+    Ok(backend)
+}
+
+/// The requested backend, or the most preferred one available.
+pub fn select(name: Option<&str>) -> Result<Box<dyn Backend>> {
+    match name {
+        Some(name) => find(name),
+        None => detect()
+            .into_iter()
+            .next()
+            .context("No package managers found"),
+    }
+}
+
+/// Installs whichever `packages` the preferred backend doesn't already list
+/// as installed, returning the ones it installed.
+pub fn ensure(packages: &[String]) -> Result<Vec<String>> {
+    if packages.is_empty() {
+        return Ok(Vec::new());
+    }
+    ensure_with(select(None)?.as_ref(), packages)
+}
+
+fn ensure_with(backend: &dyn Backend, packages: &[String]) -> Result<Vec<String>> {
+    let installed: HashSet<String> = backend
+        .list()?
+        .into_iter()
+        .map(|package| package.name.to_lowercase())
+        .collect();
+    let missing: Vec<String> = packages
+        .iter()
+        .filter(|package| !installed.contains(&package.to_lowercase()))
+        .cloned()
+        .collect();
+
+    if missing.is_empty() {
+        debug!("All {} packages already installed", packages.len());
+    } else {
+        backend.install(&missing)?;
+    }
+    Ok(missing)
+}
+
+/// Runs a command to completion with inherited stdio.
+pub(crate) fn run(program: &str, args: &[&str], packages: &[String]) -> Result<()> {
+    info!(
+        "Running: {} {} {}",
+        program,
+        args.join(" "),
+        packages.join(" ")
+    );
+
+    let status = Command::new(program)
+        .args(args)
+        .args(packages)
+        .status()
+        .with_context(|| format!("Failed to execute {}", program))?;
+
+    if !status.success() {
+        bail!("{} exited with {}", program, status);
+    }
+    Ok(())
+}
+
+/// Runs a command and returns its stdout.
+pub(crate) fn output(program: &str, args: &[&str]) -> Result<String> {
+    output_with(program, args, &[0])
+}
+
+/// Like [`output`], for commands that signal results through exit codes.
+pub(crate) fn output_with(program: &str, args: &[&str], codes: &[i32]) -> Result<String> {
+    debug!("Running: {} {}", program, args.join(" "));
+
+    let output = Command::new(program)
+        .args(args)
+        .output()
+        .with_context(|| format!("Failed to execute {}", program))?;
+
+    if !output
+        .status
+        .code()
+        .is_some_and(|code| codes.contains(&code))
+    {
+        let error = String::from_utf8_lossy(&output.stderr);
+        bail!("{} failed: {}", program, error.trim());
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+}
+
+/// Whether an installed version satisfies a pinned one, comparing only as
+/// many parts as the pin has: `14.1` accepts `14.1.0` and `14.1-2` but not
+/// `14.10`.
+pub fn satisfies(installed: &str, pin: &str) -> bool {
+    let parts = |version: &str| -> Vec<String> {
+        version
+            .split(['.', '-', '+', '~', ':'])
+            .map(String::from)
+            .collect()
+    };
+    let (installed, pin) = (parts(installed), parts(pin));
+    installed.len() >= pin.len() && installed[..pin.len()] == pin[..]
+}
+
+/// Reads one package name per line, without versions.
+pub(crate) fn names(backend: &'static str, output: &str) -> Vec<Package> {
+    output
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty())
+        .map(|name| Package::new(backend, name, None))
+        .collect()
+}
+
+/// Splits a fixed-width table (as printed by winget and scoop) into rows of
+/// columns, using the header line above the `---` separator for offsets.
+pub(crate) fn parse_table(output: &str) -> Vec<Vec<String>> {
+    let lines: Vec<&str> = output.lines().collect();
+    let Some(separator) = lines
+        .iter()
+        .position(|line| line.trim_start().starts_with("---"))
+    else {
+        return Vec::new();
+    };
+    let Some(header) = separator.checked_sub(1).map(|i| lines[i]) else {
+        return Vec::new();
+    };
+
+    // Progress spinners can precede the header on the same line
+    let header = header.rsplit('\r').next().unwrap_or(header);
+    let mut starts = Vec::new();
+    let mut previous = ' ';
+    for (index, c) in header.char_indices() {
+        if !c.is_whitespace() && previous.is_whitespace() {
+            starts.push(index);
+        }
+        previous = c;
+    }
+
+    lines[separator + 1..]
+        .iter()
+        .filter(|line| !line.trim().is_empty())
+        .map(|line| {
+            let chars: Vec<char> = line.chars().collect();
+            starts
+                .iter()
+                .enumerate()
+                .map(|(i, &start)| {
+                    let end = starts.get(i + 1).copied().unwrap_or(chars.len());
+                    chars
+                        .get(start.min(chars.len())..end.min(chars.len()))
+                        .map(|column| column.iter().collect::<String>().trim().to_string())
+                        .unwrap_or_default()
+                })
+                .collect()
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::cell::RefCell;
+
+    /// Lists `installed` and records what it is asked to install.
+    struct Fake {
+        installed: Vec<&'static str>,
+        requested: RefCell<Vec<String>>,
+    }
+
+    impl Backend for Fake {
+        fn name(&self) -> &'static str {
+            "fake"
+        }
+
+        fn program(&self) -> &'static str {
+            "fake"
+        }
+
+        fn platforms(&self) -> &'static [&'static str] {
+            &[]
+        }
+
+        fn search(&self, _query: &str) -> Result<Vec<Package>> {
+            Ok(Vec::new())
+        }
+
+        fn install(&self, packages: &[String]) -> Result<()> {
+            self.requested.borrow_mut().extend_from_slice(packages);
+            Ok(())
+        }
+
+        fn uninstall(&self, _packages: &[String]) -> Result<()> {
+            Ok(())
+        }
+
+        fn upgrade(&self, _packages: &[String]) -> Result<()> {
+            Ok(())
+        }
+
+        fn list(&self) -> Result<Vec<Package>> {
+            Ok(self
+                .installed
+                .iter()
+                .map(|name| Package::new("fake", *name, None))
+                .collect())
+        }
+
+        fn outdated(&self) -> Result<Vec<Update>> {
+            Ok(Vec::new())
+        }
+    }
+
+    #[test]
+    fn test_ensure_installs_only_missing_packages() {
+        let fake = Fake {
+            installed: vec!["Git", "ripgrep"],
+            requested: RefCell::new(Vec::new()),
+        };
+        let packages = ["git", "bat", "ripgrep", "fd"].map(String::from);
+
+        let installed = ensure_with(&fake, &packages).unwrap();
+        assert_eq!(installed, ["bat", "fd"]);
+        assert_eq!(*fake.requested.borrow(), ["bat", "fd"]);
+
+        fake.requested.borrow_mut().clear();
+        assert!(ensure_with(&fake, &packages[..1]).unwrap().is_empty());
+        assert!(fake.requested.borrow().is_empty());
+        assert!(ensure(&[]).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_satisfies_compares_pinned_parts() {
+        assert!(satisfies("14.1.0", "14.1"));
+        assert!(satisfies("14.1-2ubuntu1", "14.1"));
+        assert!(satisfies("14.1", "14.1"));
+        assert!(!satisfies("14.10.0", "14.1"));
+        assert!(!satisfies("14", "14.1"));
+    }
+
+    #[test]
+    fn test_parse_table() {
+        let output = "\
+Name               Id                Version Available Source
+-------------------------------------------------------------
+bat                sharkdp.bat       0.24.0  0.25.0    winget
+Windows Terminal   Microsoft.WT      1.21              winget
+";
+        let rows = parse_table(output);
+
+        assert_eq!(rows.len(), 2);
+        assert_eq!(
+            rows[0],
+            vec!["bat", "sharkdp.bat", "0.24.0", "0.25.0", "winget"]
+        );
+        assert_eq!(rows[1][0], "Windows Terminal");
+        assert_eq!(rows[1][3], "");
+    }
+}
@@ -0,0 +1,114 @@
+use super::{output, output_with, run, Backend, Package, Update};
+use anyhow::Result;
+
+/// Arch Linux and derivatives.
+pub struct Pacman;
+
+impl Backend for Pacman {
+    fn name(&self) -> &'static str {
+        "pacman"
+    }
+
+    fn program(&self) -> &'static str {
+        "pacman"
+    }
+
+    fn platforms(&self) -> &'static [&'static str] {
+        &["linux"]
+    }
+
+    fn search(&self, query: &str) -> Result<Vec<Package>> {
+        let stdout = output(self.program(), &["-Ss", query])?;
+        Ok(parse_search(self.name(), &stdout))
+    }
+
+    fn install(&self, packages: &[String]) -> Result<()> {
+        run(self.program(), &["-S", "--needed", "--noconfirm"], packages)
+    }
+
+    fn uninstall(&self, packages: &[String]) -> Result<()> {
+        run(self.program(), &["-Rs", "--noconfirm"], packages)
+    }
+
+    fn upgrade(&self, packages: &[String]) -> Result<()> {
+        if packages.is_empty() {
+            run(self.program(), &["-Syu", "--noconfirm"], &[])
+        } else {
+            run(self.program(), &["-S", "--noconfirm"], packages)
+        }
+    }
+
+    fn list(&self) -> Result<Vec<Package>> {
+        let stdout = output(self.program(), &["-Q"])?;
+        Ok(stdout
+            .lines()
+            .filter_map(|line| line.split_once(' '))
+            .map(|(name, version)| Package::new(self.name(), name, Some(version.to_string())))
+            .collect())
+    }
+
+    fn explicit(&self) -> Result<Vec<Package>> {
+        let stdout = output(self.program(), &["-Qe"])?;
+        Ok(stdout
+            .lines()
+            .filter_map(|line| line.split_once(' '))
+            .map(|(name, version)| Package::new(self.name(), name, Some(version.to_string())))
+            .collect())
+    }
+
+    /// Compares against the local sync database; run `pacman -Sy` to refresh it.
+    fn outdated(&self) -> Result<Vec<Update>> {
+        // pacman -Qu exits with 1 when nothing is outdated
+        let stdout = output_with(self.program(), &["-Qu"], &[0, 1])?;
+        Ok(stdout
+            .lines()
+            .filter_map(|line| {
+                // bat 0.24.0-1 -> 0.24.0-2
+                let mut parts = line.split_whitespace();
+                let name = parts.next()?;
+                let installed = parts.next()?;
+                let available = parts.nth(1)?;
+                Some(Update::new(
+                    self.name(),
+                    name,
+                    Some(installed.to_string()),
+                    available,
+                ))
+            })
+            .collect())
+    }
+}
+
+/// Parses `repo/name version [installed]` lines, skipping indented descriptions.
+fn parse_search(backend: &'static str, output: &str) -> Vec<Package> {
+    output
+        .lines()
+        .filter(|line| !line.starts_with(char::is_whitespace))
+        .filter_map(|line| {
+            let mut parts = line.split_whitespace();
+            let name = parts.next()?.rsplit('/').next()?;
+            Some(Package::new(backend, name, parts.next().map(String::from)))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_search() {
+        let output = "\
+extra/bat 0.24.0-2 [installed]
+    Cat clone with syntax highlighting and git integration
+extra/bat-extras 2024.08.24-1
+    Bash scripts that integrate bat with various command line tools
+";
+        let packages = parse_search("pacman", output);
+
+        assert_eq!(packages.len(), 2);
+        assert_eq!(packages[0].name, "bat");
+        assert_eq!(packages[0].version.as_deref(), Some("0.24.0-2"));
+        assert_eq!(packages[1].name, "bat-extras");
+    }
+}
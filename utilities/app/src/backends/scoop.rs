@@ -0,0 +1,100 @@
+use super::{output, parse_table, run, Backend, Package, Update};
+use anyhow::Result;
+
+/// Scoop, a user-level installer for Windows.
+pub struct Scoop;
+
+impl Scoop {
+    fn packages(&self, stdout: &str) -> Vec<Package> {
+        parse_table(stdout)
+            .into_iter()
+            .filter_map(|row| {
+                let mut columns = row.into_iter();
+                let name = columns.next().filter(|name| !name.is_empty())?;
+                let version = columns.next().filter(|version| !version.is_empty());
+                Some(Package::new(self.name(), name, version))
+            })
+            .collect()
+    }
+}
+
+impl Backend for Scoop {
+    fn name(&self) -> &'static str {
+        "scoop"
+    }
+
+    fn program(&self) -> &'static str {
+        "scoop"
+    }
+
+    fn platforms(&self) -> &'static [&'static str] {
+        &["windows"]
+    }
+
+    fn search(&self, query: &str) -> Result<Vec<Package>> {
+        let stdout = output(self.program(), &["search", query])?;
+        Ok(self.packages(&stdout))
+    }
+
+    fn install(&self, packages: &[String]) -> Result<()> {
+        run(self.program(), &["install"], packages)
+    }
+
+    fn uninstall(&self, packages: &[String]) -> Result<()> {
+        run(self.program(), &["uninstall"], packages)
+    }
+
+    fn upgrade(&self, packages: &[String]) -> Result<()> {
+        if packages.is_empty() {
+            run(self.program(), &["update", "*"], &[])
+        } else {
+            run(self.program(), &["update"], packages)
+        }
+    }
+
+    fn install_version(&self, package: &str, version: &str) -> Result<()> {
+        run(
+            self.program(),
+            &["install"],
+            &[format!("{}@{}", package, version)],
+        )
+    }
+
+    fn hold(&self, packages: &[String]) -> Result<()> {
+        run(self.program(), &["hold"], packages)
+    }
+
+    fn unhold(&self, packages: &[String]) -> Result<()> {
+        run(self.program(), &["unhold"], packages)
+    }
+
+    /// `scoop list` marks held apps in its Info column.
+    fn held(&self) -> Result<Vec<String>> {
+        let stdout = output(self.program(), &["list"])?;
+        Ok(parse_table(&stdout)
+            .into_iter()
+            .filter(|row| row.last().is_some_and(|info| info.contains("Held")))
+            .filter_map(|row| row.into_iter().next())
+            .collect())
+    }
+
+    fn list(&self) -> Result<Vec<Package>> {
+        let stdout = output(self.program(), &["list"])?;
+        Ok(self.packages(&stdout))
+    }
+
+    fn outdated(&self) -> Result<Vec<Update>> {
+        let stdout = output(self.program(), &["status"])?;
+        Ok(parse_table(&stdout)
+            .into_iter()
+            .filter_map(|row| {
+                // Name, Installed Version, Latest Version, ...
+                let mut columns = row.into_iter();
+                let name = columns.next().filter(|name| !name.is_empty())?;
+                let installed = columns.next().filter(|version| !version.is_empty());
+                let available = columns.next().filter(|version| !version.is_empty())?;
+                Some(Update::new(self.name(), name, installed, available))
+            })
+            .collect())
+    }
+}
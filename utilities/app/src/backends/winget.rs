@@ -0,0 +1,134 @@
+use super::{output, parse_table, run, Backend, Package, Update};
+use anyhow::Result;
+
+/// The Windows Package Manager. Packages are addressed by their exact id.
+pub struct Winget;
+
+const AGREEMENTS: [&str; 2] = ["--accept-package-agreements", "--accept-source-agreements"];
+
+impl Winget {
+    /// winget only accepts one package per invocation.
+    fn each(&self, command: &str, packages: &[String], extra: &[&str]) -> Result<()> {
+        for package in packages {
+            let mut args = vec![command, "--exact", "--id", package.as_str()];
+            args.extend_from_slice(extra);
+            run(self.program(), &args, &[])?;
+        }
+        Ok(())
+    }
+
+    fn packages(&self, stdout: &str) -> Vec<Package> {
+        parse_table(stdout)
+            .into_iter()
+            .filter_map(|row| {
+                let mut columns = row.into_iter().skip(1);
+                let id = columns.next().filter(|id| !id.is_empty())?;
+                let version = columns.next().filter(|version| !version.is_empty());
+                Some(Package::new(self.name(), id, version))
+            })
+            .collect()
+    }
+}
+
+impl Backend for Winget {
+    fn name(&self) -> &'static str {
+        "winget"
+    }
+
+    fn program(&self) -> &'static str {
+        "winget"
+    }
+
+    fn platforms(&self) -> &'static [&'static str] {
+        &["windows"]
+    }
+
+    fn search(&self, query: &str) -> Result<Vec<Package>> {
+        let stdout = output(
+            self.program(),
+            &["search", query, "--accept-source-agreements"],
+        )?;
+        Ok(self.packages(&stdout))
+    }
+
+    fn install(&self, packages: &[String]) -> Result<()> {
+        self.each("install", packages, &AGREEMENTS)
+    }
+
+    fn uninstall(&self, packages: &[String]) -> Result<()> {
+        self.each("uninstall", packages, &[])
+    }
+
+    fn upgrade(&self, packages: &[String]) -> Result<()> {
+        if packages.is_empty() {
+            let mut args = vec!["upgrade", "--all"];
+            args.extend_from_slice(&AGREEMENTS);
+            run(self.program(), &args, &[])
+        } else {
+            self.each("upgrade", packages, &AGREEMENTS)
+        }
+    }
+
+    fn install_version(&self, package: &str, version: &str) -> Result<()> {
+        let mut args = vec!["--version", version];
+        args.extend_from_slice(&AGREEMENTS);
+        self.each("install", &[package.to_string()], &args)
+    }
+
+    fn hold(&self, packages: &[String]) -> Result<()> {
+        for package in packages {
+            run(
+                self.program(),
+                &["pin", "add", "--exact", "--id", package.as_str()],
+                &[],
+            )?;
+        }
+        Ok(())
+    }
+
+    fn unhold(&self, packages: &[String]) -> Result<()> {
+        for package in packages {
+            run(
+                self.program(),
+                &["pin", "remove", "--exact", "--id", package.as_str()],
+                &[],
+            )?;
+        }
+        Ok(())
+    }
+
+    fn held(&self) -> Result<Vec<String>> {
+        let stdout = output(self.program(), &["pin", "list"])?;
+        Ok(self.packages(&stdout).into_iter().map(|p| p.name).collect())
+    }
+
+    fn list(&self) -> Result<Vec<Package>> {
+        let stdout = output(self.program(), &["list", "--accept-source-agreements"])?;
+        Ok(self.packages(&stdout))
+    }
+
+    /// Only packages winget can install again, not every program Windows
+    /// knows about.
+    fn explicit(&self) -> Result<Vec<Package>> {
+        let stdout = output(
+            self.program(),
+            &["list", "--source", "winget", "--accept-source-agreements"],
+        )?;
+        Ok(self.packages(&stdout))
+    }
+
+    fn outdated(&self) -> Result<Vec<Update>> {
+        let stdout = output(self.program(), &["upgrade", "--accept-source-agreements"])?;
+        Ok(parse_table(&stdout)
+            .into_iter()
+            .filter_map(|row| {
+                // Name, Id, Version, Available, Source
+                let mut columns = row.into_iter().skip(1);
+                let id = columns.next().filter(|id| !id.is_empty())?;
+                let installed = columns.next().filter(|version| !version.is_empty());
+                let available = columns.next().filter(|version| !version.is_empty())?;
+                Some(Update::new(self.name(), id, installed, available))
+            })
+            .collect())
+    }
+}
@@ -1,90 +1,69 @@
 use anyhow::{Context, Result};
-use logline::{debug, info};
-use std::{fs, path::Path, process::Command};
+use logline::info;
+use std::{fs, path::Path};
 
-use crate::utilities::{detect_package_managers, PackageManager};
+use crate::backends;
 
-pub fn add(pkgs: &[String], file: &Option<String>) -> Result<()> {
-    if let Some(file_path) = file {
-        from_file(file_path)
+pub fn add(pkgs: &[String], file: &Option<String>, manager: Option<&str>) -> Result<()> {
+    if let Some(file_path) = file.as_deref().filter(|path| path.ends_with(".toml")) {
+        super::sync(Some(file_path))
+    } else if let Some(file_path) = file {
+        from_file(file_path, manager)
     } else {
-        from_list(pkgs)
+        from_list(pkgs, manager)
     }
 }
 
-fn from_file(file_path: &str) -> Result<()> {
+/// Reads a package list, one name per line, ignoring blanks and `#` comments.
+pub fn read_list(file_path: &str) -> Result<Vec<String>> {
     let path = Path::new(file_path);
     let content = fs::read_to_string(path)
         .with_context(|| format!("Failed to read package list from {}", file_path))?;
 
-    let packages: Vec<String> = content
+    Ok(content
         .lines()
         .filter(|line| !line.trim().is_empty() && !line.trim().starts_with('#'))
         .map(|s| s.trim().to_string())
-        .collect();
+        .collect())
+}
+
+fn from_file(file_path: &str, manager: Option<&str>) -> Result<()> {
+    let packages = read_list(file_path)?;
 
     if packages.is_empty() {
         info!("No packages found in {}", file_path);
         return Ok(());
     }
 
-    from_list(&packages)
+    from_list(&packages, manager)
 }
 
-fn from_list(pkgs: &[String]) -> Result<()> {
+fn from_list(pkgs: &[String], manager: Option<&str>) -> Result<()> {
     if pkgs.is_empty() {
         info!("No packages specified");
         return Ok(());
     }
 
-    let package_managers = detect_package_managers()?;
-    if package_managers.is_empty() {
-        anyhow::bail!("No package managers found");
+    // An explicit manager is used as-is; otherwise fall through by priority
+    if manager.is_some() {
+        let backend = backends::select(manager)?;
+        return backend.install(pkgs);
     }
 
-    // Try each package manager in order of priority
-    for pm in package_managers.iter().filter(|pm| pm.available) {
-        debug!("Trying to install with {}", pm.name);
-        return Ok(());
-        // match install_with_manager(pm, pkgs) {
-        //     Ok(_) => return Ok(()),
-        //     Err(e) => info!("Failed to install with {}: {}", pm.name, e),
-        // }
+    let available = backends::detect();
+    if available.is_empty() {
+        anyhow::bail!("No package managers found");
     }
 
-    anyhow::bail!("Failed to install packages with any available package manager")
-}
-
-fn install_with_manager(pm: &PackageManager, pkgs: &[String]) -> Result<()> {
-    let (cmd, args) = match pm.name.as_str() {
-        "winget" => ("winget", vec!["install", "--accept-package-agreements"]),
-        "chocolatey" => ("choco", vec!["install", "-y"]),
-        "scoop" => ("scoop", vec!["install"]),
-        "apt" => ("apt", vec!["install", "-y"]),
-        "dnf" => ("dnf", vec!["install", "-y"]),
-        "pacman" => ("pacman", vec!["-S", "--noconfirm"]),
-        "paru" => ("paru", vec!["-S", "--noconfirm"]),
-        "yay" => ("yay", vec!["-S", "--noconfirm"]),
-        "nix-env" => ("nix-env", vec!["-i"]),
-        "flatpak" => ("flatpak", vec!["install", "-y"]),
-        _ => anyhow::bail!("Unsupported package manager: {}", pm.name),
-    };
-
-    let mut command = Command::new(cmd);
-    command.args(&args);
-    command.args(pkgs);
-
-    info!("Installing with {}: {:?}", pm.name, pkgs);
-
-    let output = command
-        .output()
-        .with_context(|| format!("Failed to execute {} command", pm.name))?;
-
-    if !output.status.success() {
-        let error = String::from_utf8_lossy(&output.stderr);
-        anyhow::bail!("Installation failed: {}", error);
+    for backend in &available {
+        match backend.install(pkgs) {
+            Ok(_) => {
+                info!("Successfully installed packages with {}", backend.name());
+                return Ok(());
+            }
+            Err(e) => info!("Failed to install with {}: {:#}", backend.name(), e),
+        }
     }
 
-    info!("Successfully installed packages with {}", pm.name);
-    Ok(())
+    anyhow::bail!("Failed to install packages with any available package manager")
 }
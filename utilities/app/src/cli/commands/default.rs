@@ -24,12 +24,13 @@ pub enum Commands {
         /// Packages to install
         packages: Vec<String>,
 
-        /// Install from file
+        /// Install from a package list, or a manifest ending in .toml
         #[arg(short, long)]
         file: Option<String>,
     },
 
-    /// Update packages
+    /// Update packages, leaving held ones alone
+    #[command(alias = "upgrade")]
     Update {
         /// Packages to update
         packages: Vec<String>,
@@ -39,12 +40,49 @@ pub enum Commands {
         file: Option<String>,
     },
 
+    /// Install everything listed in a manifest that is missing
+    Sync {
+        /// Manifest to read (defaults to ./apps.toml)
+        #[arg(short, long)]
+        file: Option<String>,
+    },
+
+    /// Write a manifest of the packages installed on this machine
+    Export {
+        /// Where to write it (defaults to ./apps.toml, `-` for stdout)
+        #[arg(short, long)]
+        file: Option<String>,
+
+        /// Overwrite an existing manifest
+        #[arg(long)]
+        force: bool,
+    },
+
+    /// Stop packages from being upgraded
+    Hold {
+        /// Packages to hold
+        packages: Vec<String>,
+    },
+
+    /// Let held packages be upgraded again
+    Unhold {
+        /// Packages to release
+        packages: Vec<String>,
+    },
+
     /// Remove/uninstall packages
     Remove {
         /// Packages to remove
         packages: Vec<String>,
     },
 
+    /// Show installed packages with newer versions available
+    Outdated {
+        /// Print the results as JSON
+        #[arg(long)]
+        json: bool,
+    },
+
     /// List packages or package managers
     List {
         #[command(subcommand)]
@@ -65,4 +103,4 @@ pub enum ListCommands {
     Outdated,
     /// List available package managers
     Managers,
-}
\ No newline at end of file
+}
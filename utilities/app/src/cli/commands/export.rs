@@ -0,0 +1,59 @@
+use crate::{
+    backends,
+    manifest::{Manifest, MANIFEST_FILE},
+};
+use anyhow::{bail, Result};
+use logline::{debug, info, warn};
+use std::path::Path;
+
+/// Writes a manifest of the explicitly installed packages of each detected
+/// backend, or only `manager`. Prints it instead when `file` is `-`.
+pub fn export(file: Option<&str>, manager: Option<&str>, force: bool) -> Result<()> {
+    let path = Path::new(file.unwrap_or(MANIFEST_FILE));
+    let stdout = path == Path::new("-");
+    if !stdout && !force && path.exists() {
+        bail!(
+            "{} already exists, pass --force to overwrite it",
+            path.display()
+        );
+    }
+
+    let targets = match manager {
+        Some(name) => vec![backends::find(name)?],
+        None => backends::detect(),
+    };
+    if targets.is_empty() {
+        bail!("No package managers found");
+    }
+
+    let mut manifest = Manifest::default();
+    for backend in &targets {
+        debug!("Listing explicit packages for {}", backend.name());
+        match backend.explicit() {
+            Ok(packages) => {
+                let mut names: Vec<String> = packages.into_iter().map(|p| p.name).collect();
+                names.sort();
+                names.dedup();
+                if !names.is_empty() {
+                    manifest
+                        .backends
+                        .insert(backend.name().to_string(), names.into());
+                }
+            }
+            Err(e) => warn!("Failed to list packages for {}: {:#}", backend.name(), e),
+        }
+    }
+
+    if stdout {
+        print!("{}", manifest.to_toml()?);
+    } else {
+        manifest.save(path)?;
+        info!(
+            "Wrote {} packages from {} managers to {}",
+            manifest.len(),
+            manifest.backends.len(),
+            path.display()
+        );
+    }
+    Ok(())
+}
@@ -0,0 +1,30 @@
+use crate::backends;
+use anyhow::Result;
+use logline::info;
+
+/// Keeps `packages` at their installed version during upgrades.
+pub fn hold(packages: &[String], manager: Option<&str>) -> Result<()> {
+    if packages.is_empty() {
+        info!("No packages specified");
+        return Ok(());
+    }
+
+    let backend = backends::select(manager)?;
+    backend.hold(packages)?;
+
+    info!("Held {} with {}", packages.join(", "), backend.name());
+    Ok(())
+}
+
+pub fn unhold(packages: &[String], manager: Option<&str>) -> Result<()> {
+    if packages.is_empty() {
+        info!("No packages specified");
+        return Ok(());
+    }
+
+    let backend = backends::select(manager)?;
+    backend.unhold(packages)?;
+
+    info!("Released {} with {}", packages.join(", "), backend.name());
+    Ok(())
+}
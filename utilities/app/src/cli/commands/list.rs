@@ -1,79 +1,76 @@
-use super::ListCommands;
-use crate::utilities::{detect_package_managers, PackageManager};
-use anyhow::{Context, Result};
+use super::{outdated, ListCommands};
+use crate::{
+    backends::{self, Backend, Package},
+    utilities::detect_package_managers,
+};
+use anyhow::Result;
 use logline::{debug, info};
-use std::process::Command;
 
-pub fn list(cmd: &ListCommands) -> Result<()> {
+pub fn list(cmd: &ListCommands, manager: Option<&str>) -> Result<()> {
     match cmd {
-        ListCommands::Installed => installed(),
-        ListCommands::Available { query } => available(query),
-        ListCommands::Outdated => outdated(),
+        ListCommands::Installed => installed(manager),
+        ListCommands::Available { query } => available(query, manager),
+        ListCommands::Outdated => outdated(manager, false),
         ListCommands::Managers => managers(),
     }
 }
 
-fn installed() -> Result<()> {
-    let package_managers = detect_package_managers()?;
-    if package_managers.is_empty() {
+/// The requested backend, or every available one.
+fn targets(manager: Option<&str>) -> Result<Vec<Box<dyn Backend>>> {
+    let backends = match manager {
+        Some(name) => vec![backends::find(name)?],
+        None => backends::detect(),
+    };
+    if backends.is_empty() {
         anyhow::bail!("No package managers found");
     }
+    Ok(backends)
+}
 
-    for pm in package_managers.iter().filter(|pm| pm.available) {
-        debug!("Listing installed packages for {}", pm.name);
-        match list_installed_for_manager(pm) {
-            Ok(_) => (),
-            Err(e) => info!("Failed to list packages for {}: {}", pm.name, e),
+fn installed(manager: Option<&str>) -> Result<()> {
+    for backend in targets(manager)? {
+        debug!("Listing installed packages for {}", backend.name());
+        match backend.list() {
+            Ok(packages) => print(&packages),
+            Err(e) => info!("Failed to list packages for {}: {:#}", backend.name(), e),
         }
     }
 
     Ok(())
 }
 
-fn list_installed_for_manager(pm: &PackageManager) -> Result<()> {
-    let (cmd, args) = match pm.name.as_str() {
-        "winget" => ("winget", vec!["list"]),
-        "chocolatey" => ("choco", vec!["list", "--local-only"]),
-        "scoop" => ("scoop", vec!["list"]),
-        "apt" => ("apt", vec!["list", "--installed"]),
-        "dnf" => ("dnf", vec!["list", "installed"]),
-        "pacman" => ("pacman", vec!["-Q"]),
-        "paru" => ("paru", vec!["-Q"]),
-        "yay" => ("yay", vec!["-Q"]),
-        "nix-env" => ("nix-env", vec!["--query"]),
-        "flatpak" => ("flatpak", vec!["list"]),
-        _ => anyhow::bail!("Unsupported package manager: {}", pm.name),
+fn available(query: &Option<String>, manager: Option<&str>) -> Result<()> {
+    let Some(query) = query else {
+        anyhow::bail!("A search query is required");
     };
 
-    info!("Installed packages from {}:", pm.name);
-
-    let output = Command::new(cmd)
-        .args(args)
-        .output()
-        .with_context(|| format!("Failed to execute {} command", pm.name))?;
-
-    if !output.status.success() {
-        let error = String::from_utf8_lossy(&output.stderr);
-        anyhow::bail!("Failed to list packages: {}", error);
+    for backend in targets(manager)? {
+        debug!("Searching {} for {}", backend.name(), query);
+        match backend.search(query) {
+            Ok(packages) => print(&packages),
+            Err(e) => info!("Failed to search {}: {:#}", backend.name(), e),
+        }
     }
 
-    let output_str = String::from_utf8_lossy(&output.stdout);
-    println!("{}", output_str);
-
     Ok(())
 }
 
-fn available(query: &Option<String>) -> Result<()> {
-    // Implementation for listing/searching available packages
-    todo!()
-}
+fn managers() -> Result<()> {
+    for pm in detect_package_managers()? {
+        let status = if pm.available { "available" } else { "missing" };
+        println!("{:<3} {:<8} {}", pm.priority + 1, pm.name, status);
+    }
 
-fn outdated() -> Result<()> {
-    // Implementation for listing outdated packages
-    todo!()
+    Ok(())
 }
 
-fn managers() -> Result<()> {
-    // Implementation for listing available package managers
-    todo!()
+fn print(packages: &[Package]) {
+    for package in packages {
+        println!(
+            "{:<8} {} {}",
+            package.backend,
+            package.name,
+            package.version.as_deref().unwrap_or_default()
+        );
+    }
 }
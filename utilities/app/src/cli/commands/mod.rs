@@ -1,11 +1,20 @@
 mod add;
 mod default;
+mod export;
+mod hold;
 mod list;
+mod outdated;
 mod remove;
+mod sync;
+mod table;
 mod update;
 
 pub use add::add;
 pub use default::*;
+pub use export::export;
+pub use hold::{hold, unhold};
 pub use list::list;
+pub use outdated::outdated;
 pub use remove::remove;
+pub use sync::sync;
 pub use update::update;
@@ -0,0 +1,49 @@
+use super::table::table;
+use crate::backends::{self, Update};
+use anyhow::Result;
+use logline::{debug, warn};
+
+/// Prints every outdated package across the requested or detected backends.
+pub fn outdated(manager: Option<&str>, json: bool) -> Result<()> {
+    let targets = match manager {
+        Some(name) => vec![backends::find(name)?],
+        None => backends::detect(),
+    };
+    if targets.is_empty() {
+        anyhow::bail!("No package managers found");
+    }
+
+    let mut updates = Vec::new();
+    for backend in &targets {
+        debug!("Checking {} for updates", backend.name());
+        match backend.outdated() {
+            Ok(found) => updates.extend(found),
+            Err(e) => warn!("Failed to check {} for updates: {:#}", backend.name(), e),
+        }
+    }
+
+    if json {
+        println!("{}", serde_json::to_string_pretty(&updates)?);
+    } else if updates.is_empty() {
+        println!("Everything is up to date");
+    } else {
+        let headers = ["MANAGER", "NAME", "INSTALLED", "AVAILABLE"];
+        print!("{}", table(headers, &rows(&updates)));
+    }
+
+    Ok(())
+}
+
+fn rows(updates: &[Update]) -> Vec<[&str; 4]> {
+    updates
+        .iter()
+        .map(|update| {
+            [
+                update.backend,
+                update.name.as_str(),
+                update.installed.as_deref().unwrap_or("?"),
+                update.available.as_str(),
+            ]
+        })
+        .collect()
+}
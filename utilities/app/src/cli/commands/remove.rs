@@ -1,6 +1,16 @@
+use crate::backends;
 use anyhow::Result;
+use logline::info;
 
-pub fn remove(packages: &[String]) -> Result<()> {
-    // Implementation for removing packages
-    todo!()
+pub fn remove(packages: &[String], manager: Option<&str>) -> Result<()> {
+    if packages.is_empty() {
+        info!("No packages specified");
+        return Ok(());
+    }
+
+    let backend = backends::select(manager)?;
+    backend.uninstall(packages)?;
+
+    info!("Removed packages with {}", backend.name());
+    Ok(())
 }
@@ -0,0 +1,34 @@
+use super::table::table;
+use crate::{
+    manifest::{Manifest, MANIFEST_FILE},
+    sync,
+};
+use anyhow::{bail, Result};
+use logline::info;
+use std::path::Path;
+
+/// Installs whatever the manifest lists that isn't installed yet, then
+/// prints the outcome for every package.
+pub fn sync(file: Option<&str>) -> Result<()> {
+    let path = Path::new(file.unwrap_or(MANIFEST_FILE));
+    let manifest = Manifest::load(path)?;
+    if manifest.is_empty() {
+        info!("No packages found in {}", path.display());
+        return Ok(());
+    }
+
+    let rows = sync::run(&manifest);
+    let statuses: Vec<String> = rows.iter().map(|row| row.outcome.to_string()).collect();
+    let cells: Vec<[&str; 3]> = rows
+        .iter()
+        .zip(&statuses)
+        .map(|(row, status)| [row.backend.as_str(), row.package.as_str(), status.as_str()])
+        .collect();
+    print!("{}", table(["MANAGER", "NAME", "STATUS"], &cells));
+
+    let failed = rows.iter().filter(|row| row.outcome.is_failure()).count();
+    if failed > 0 {
+        bail!("{} of {} packages failed", failed, rows.len());
+    }
+    Ok(())
+}
@@ -1,19 +1,84 @@
-use anyhow::Result;
+use super::add::read_list;
+use crate::{
+    backends::{self, Backend},
+    manifest::{Manifest, MANIFEST_FILE},
+};
+use anyhow::{bail, Result};
+use logline::{info, warn};
+use std::path::Path;
 
-pub fn update(pkgs: &[String], file: &Option<String>) -> Result<()> {
+pub fn update(pkgs: &[String], file: &Option<String>, manager: Option<&str>) -> Result<()> {
     if let Some(file_path) = file {
-        from_file(file_path)
+        from_file(file_path, manager)
     } else {
-        packages(pkgs)
+        packages(pkgs, manager)
     }
 }
 
-fn from_file(file_path: &str) -> Result<()> {
-    // Implementation for updateing from file
-    todo!()
+fn from_file(file_path: &str, manager: Option<&str>) -> Result<()> {
+    let packages = read_list(file_path)?;
+
+    if packages.is_empty() {
+        info!("No packages found in {}", file_path);
+        return Ok(());
+    }
+
+    self::packages(&packages, manager)
 }
 
-fn packages(pkgs: &[String]) -> Result<()> {
-    // Implementation for installing packages
-    todo!()
+/// Upgrades the given packages, or everything the backend manages when empty,
+/// refusing any that are held.
+fn packages(pkgs: &[String], manager: Option<&str>) -> Result<()> {
+    let backend = backends::select(manager)?;
+    let native = backend.held().unwrap_or_else(|e| {
+        warn!("Failed to list held {} packages: {:#}", backend.name(), e);
+        Vec::new()
+    });
+    let declared = declared_holds(backend.as_ref());
+
+    if !pkgs.is_empty() {
+        let held: Vec<&str> = pkgs
+            .iter()
+            .filter(|pkg| native.contains(pkg) || declared.contains(pkg))
+            .map(String::as_str)
+            .collect();
+        if !held.is_empty() {
+            bail!("Refusing to upgrade held packages: {}", held.join(", "));
+        }
+        backend.upgrade(pkgs)?;
+    } else if declared.iter().all(|pkg| native.contains(pkg)) {
+        // The backend already skips everything it holds
+        backend.upgrade(&[])?;
+    } else {
+        // Holds the backend can't enforce are left out by naming the rest
+        let rest: Vec<String> = backend
+            .upgradable()?
+            .into_iter()
+            .map(|package| package.name)
+            .filter(|name| !declared.contains(name) && !native.contains(name))
+            .collect();
+        info!("Leaving held packages alone: {}", declared.join(", "));
+        // An empty list would mean everything, holds included
+        if !rest.is_empty() {
+            backend.upgrade(&rest)?;
+        }
+    }
+
+    info!("Updated packages with {}", backend.name());
+    Ok(())
+}
+
+/// Packages `apps.toml` in the current directory marks as held for `backend`.
+fn declared_holds(backend: &dyn Backend) -> Vec<String> {
+    let path = Path::new(MANIFEST_FILE);
+    if !path.exists() {
+        return Vec::new();
+    }
+    match Manifest::load(path) {
+        Ok(manifest) => manifest.held(backend.name()),
+        Err(e) => {
+            warn!("{:#}", e);
+            Vec::new()
+        }
+    }
 }
@@ -1,35 +1,54 @@
-use crate::cli::commands::{add, list, remove, update, Commands, Default, ListCommands};
+use crate::cli::commands::{
+    add, export, hold, list, outdated, remove, sync, unhold, update, Commands, Default,
+    ListCommands,
+};
 use anyhow::Result;
 use clap::Parser;
 use logline::info;
 
 pub fn init() -> Result<()> {
-    let mut cli = Default::parse();
+    let cli = Default::parse();
+    let manager = cli.manager.as_deref();
 
     info!("Config: {:#?}", cli.command);
 
     match cli.command {
         Some(Commands::Add { packages, file }) => {
             info!("Adding packages: {:?}, file: {:?}", packages, file);
-            add(&packages, &file);
+            add(&packages, &file, manager)?;
         }
         Some(Commands::Update { packages, file }) => {
             info!("Updating packages: {:?}, file: {:?}", packages, file);
-            update(&packages, &file);
+            update(&packages, &file, manager)?;
+        }
+        Some(Commands::Sync { file }) => {
+            sync(file.as_deref())?;
+        }
+        Some(Commands::Export { file, force }) => {
+            export(file.as_deref(), manager, force)?;
+        }
+        Some(Commands::Hold { packages }) => {
+            hold(&packages, manager)?;
+        }
+        Some(Commands::Unhold { packages }) => {
+            unhold(&packages, manager)?;
         }
         Some(Commands::Remove { packages }) => {
             info!("Removing packages: {:?}", packages);
-            remove(&packages);
+            remove(&packages, manager)?;
+        }
+        Some(Commands::Outdated { json }) => {
+            outdated(manager, json)?;
         }
         Some(Commands::List { command }) => {
             info!("List command: {:?}", command);
-            list(&command);
+            list(&command, manager)?;
         }
         None => {
             // Default to list available if a query is provided
             if let Some(query) = cli.query {
                 info!("Default list available with query: {}", query);
-                list(&ListCommands::Available { query: Some(query) });
+                list(&ListCommands::Available { query: Some(query) }, manager)?;
             } else {
                 info!("No command or query provided");
             }
@@ -6,6 +6,9 @@
 // pub use data::*;
 // pub use error::Error;
 // pub use tools::*;
+pub mod backends;
 pub mod cli;
 pub mod config;
+pub mod manifest;
+pub mod sync;
 pub mod utilities;
@@ -0,0 +1,193 @@
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::{collections::BTreeMap, fs, path::Path};
+
+/// Manifest read by `app sync` when no file is given.
+pub const MANIFEST_FILE: &str = "apps.toml";
+
+/// The packages a machine should have, grouped by the backend that
+/// installs them.
+///
+/// ```toml
+/// apt = ["ripgrep", "bat"]
+///
+/// [cargo]
+/// just = "*"
+/// ripgrep = "14.1"
+/// bat = { version = "0.24", hold = true }
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(transparent)]
+pub struct Manifest {
+    pub backends: BTreeMap<String, Packages>,
+}
+
+/// One backend's packages, either plain names or a table of pins.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(untagged)]
+pub enum Packages {
+    List(Vec<String>),
+    Table(BTreeMap<String, Spec>),
+}
+
+impl From<Vec<String>> for Packages {
+    fn from(names: Vec<String>) -> Self {
+        Self::List(names)
+    }
+}
+
+impl Packages {
+    pub fn entries(&self) -> Vec<Entry> {
+        match self {
+            Self::List(names) => names.iter().map(Entry::new).collect(),
+            Self::Table(specs) => specs.iter().map(|(name, spec)| spec.entry(name)).collect(),
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        match self {
+            Self::List(names) => names.len(),
+            Self::Table(specs) => specs.len(),
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+/// A version pin, `"*"` for any, or a table that can also hold it.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(untagged)]
+pub enum Spec {
+    Version(String),
+    Detailed {
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        version: Option<String>,
+        /// Keep the backend from upgrading the package.
+        #[serde(default)]
+        hold: bool,
+    },
+}
+
+impl Spec {
+    fn entry(&self, name: &str) -> Entry {
+        let pin = |version: &str| (version != "*").then(|| version.to_string());
+        match self {
+            Self::Version(version) => Entry {
+                version: pin(version),
+                ..Entry::new(name)
+            },
+            Self::Detailed { version, hold } => Entry {
+                version: version.as_deref().and_then(pin),
+                hold: *hold,
+                ..Entry::new(name)
+            },
+        }
+    }
+}
+
+/// A package as the manifest asks for it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Entry {
+    pub name: String,
+    /// Required version, `None` for any.
+    pub version: Option<String>,
+    pub hold: bool,
+}
+
+impl Entry {
+    pub fn new(name: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            version: None,
+            hold: false,
+        }
+    }
+}
+
+impl Manifest {
+    pub fn from_toml(content: &str) -> Result<Self> {
+        toml::from_str(content).context("Failed to parse manifest")
+    }
+
+    pub fn load(path: &Path) -> Result<Self> {
+        let content = fs::read_to_string(path)
+            .with_context(|| format!("Failed to read manifest {}", path.display()))?;
+        Self::from_toml(&content).with_context(|| format!("Invalid manifest {}", path.display()))
+    }
+
+    pub fn to_toml(&self) -> Result<String> {
+        toml::to_string_pretty(self).context("Failed to serialize manifest")
+    }
+
+    pub fn save(&self, path: &Path) -> Result<()> {
+        fs::write(path, self.to_toml()?)
+            .with_context(|| format!("Failed to write manifest {}", path.display()))
+    }
+
+    /// Number of packages across every backend.
+    pub fn len(&self) -> usize {
+        self.backends.values().map(Packages::len).sum()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Names marked `hold` under `backend`.
+    pub fn held(&self, backend: &str) -> Vec<String> {
+        self.backends
+            .get(backend)
+            .map(Packages::entries)
+            .unwrap_or_default()
+            .into_iter()
+            .filter(|entry| entry.hold)
+            .map(|entry| entry.name)
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_manifest_groups_by_backend() {
+        let manifest = Manifest::from_toml(
+            r#"
+            apt = ["ripgrep", "bat"]
+            cargo = ["just"]
+            "#,
+        )
+        .unwrap();
+
+        assert_eq!(manifest.len(), 3);
+        assert_eq!(manifest.backends["cargo"], vec!["just".to_string()].into());
+        assert_eq!(
+            manifest.to_toml().unwrap(),
+            "apt = [\n    \"ripgrep\",\n    \"bat\",\n]\ncargo = [\"just\"]\n"
+        );
+    }
+
+    #[test]
+    fn test_pins_and_holds() {
+        let manifest = Manifest::from_toml(
+            r#"
+            [cargo]
+            just = "*"
+            ripgrep = "14.1"
+            bat = { version = "0.24", hold = true }
+            "#,
+        )
+        .unwrap();
+
+        let entries = manifest.backends["cargo"].entries();
+        assert_eq!(entries[0].name, "bat");
+        assert_eq!(entries[0].version.as_deref(), Some("0.24"));
+        assert_eq!(entries[1], Entry::new("just"));
+        assert_eq!(entries[2].version.as_deref(), Some("14.1"));
+        assert_eq!(manifest.held("cargo"), ["bat"]);
+        assert!(manifest.held("apt").is_empty());
+    }
+}
@@ -0,0 +1,341 @@
+use crate::{
+    backends::{self, Backend},
+    manifest::{Entry, Manifest},
+};
+use anyhow::Result;
+use logline::{debug, info, warn};
+use std::{
+    collections::{BTreeMap, HashMap},
+    fmt, slice,
+    sync::atomic::{AtomicUsize, Ordering},
+    thread,
+};
+
+/// What happened to one package during a sync.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Outcome {
+    Installed,
+    Present,
+    Failed(String),
+    /// The backend couldn't be used, so nothing was attempted.
+    Skipped(String),
+}
+
+impl Outcome {
+    pub fn is_failure(&self) -> bool {
+        matches!(self, Self::Failed(_) | Self::Skipped(_))
+    }
+}
+
+impl fmt::Display for Outcome {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::Installed => write!(f, "installed"),
+            Self::Present => write!(f, "already installed"),
+            Self::Failed(error) => write!(f, "failed: {}", error),
+            Self::Skipped(reason) => write!(f, "skipped: {}", reason),
+        }
+    }
+}
+
+/// The result for one package in the manifest.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Row {
+    pub backend: String,
+    pub package: String,
+    pub outcome: Outcome,
+}
+
+/// Counts finished packages across all workers and logs each one.
+struct Progress {
+    total: usize,
+    done: AtomicUsize,
+}
+
+impl Progress {
+    fn tick(&self, backend: &str, package: &str, outcome: &Outcome) {
+        let done = self.done.fetch_add(1, Ordering::SeqCst) + 1;
+        info!(
+            "[{}/{}] {} {}: {}",
+            done, self.total, backend, package, outcome
+        );
+    }
+}
+
+/// Installs everything in `manifest` that is missing.
+///
+/// Backends run one stage at a time, so language managers such as cargo
+/// can use toolchains the system managers just installed. Within a stage
+/// each backend gets its own thread and installs its packages one by one,
+/// since most managers lock their database.
+pub fn run(manifest: &Manifest) -> Vec<Row> {
+    run_with(manifest, backends::find)
+}
+
+/// [`run`] with backends looked up through `find` instead of
+/// [`backends::find`].
+fn run_with<F>(manifest: &Manifest, find: F) -> Vec<Row>
+where
+    F: Fn(&str) -> Result<Box<dyn Backend>> + Sync,
+{
+    let progress = Progress {
+        total: manifest.len(),
+        done: AtomicUsize::new(0),
+    };
+
+    let mut stages: BTreeMap<u8, Vec<(&str, Vec<Entry>)>> = BTreeMap::new();
+    for (name, packages) in &manifest.backends {
+        let stage = find(name).map_or(0, |backend| backend.stage());
+        stages
+            .entry(stage)
+            .or_default()
+            .push((name.as_str(), packages.entries()));
+    }
+
+    let mut rows = Vec::new();
+    for (stage, jobs) in stages {
+        debug!(
+            "Stage {}: {:?}",
+            stage,
+            jobs.iter().map(|(name, _)| name).collect::<Vec<_>>()
+        );
+        thread::scope(|scope| {
+            let workers: Vec<_> = jobs
+                .iter()
+                .map(|(name, packages)| {
+                    let (progress, find) = (&progress, &find);
+                    (
+                        *name,
+                        packages,
+                        scope.spawn(move || install(find(name), name, packages, progress)),
+                    )
+                })
+                .collect();
+
+            for (name, packages, worker) in workers {
+                match worker.join() {
+                    Ok(results) => rows.extend(results),
+                    Err(_) => rows.extend(packages.iter().map(|entry| Row {
+                        backend: name.to_string(),
+                        package: entry.name.clone(),
+                        outcome: Outcome::Failed("worker panicked".into()),
+                    })),
+                }
+            }
+        });
+    }
+    rows
+}
+
+/// Installs the missing `packages` with one backend, in order, then holds
+/// those marked for it.
+fn install(
+    backend: Result<Box<dyn Backend>>,
+    name: &str,
+    packages: &[Entry],
+    progress: &Progress,
+) -> Vec<Row> {
+    let row = |entry: &Entry, outcome: Outcome| {
+        progress.tick(name, &entry.name, &outcome);
+        Row {
+            backend: name.to_string(),
+            package: entry.name.clone(),
+            outcome,
+        }
+    };
+
+    let backend = match backend {
+        Ok(backend) => backend,
+        Err(e) => {
+            let reason = e.to_string();
+            return packages
+                .iter()
+                .map(|entry| row(entry, Outcome::Skipped(reason.clone())))
+                .collect();
+        }
+    };
+
+    let installed: HashMap<String, Option<String>> = match backend.list() {
+        Ok(list) => list
+            .into_iter()
+            .map(|package| (package.name.to_lowercase(), package.version))
+            .collect(),
+        Err(e) => {
+            warn!("Failed to list {} packages: {:#}", name, e);
+            HashMap::new()
+        }
+    };
+
+    packages
+        .iter()
+        .map(|entry| {
+            let current = installed.get(&entry.name.to_lowercase());
+            let result = match (current, &entry.version) {
+                (Some(_), None) => None,
+                (Some(Some(version)), Some(pin)) if backends::satisfies(version, pin) => None,
+                (_, None) => Some(backend.install(slice::from_ref(&entry.name))),
+                (_, Some(pin)) => Some(backend.install_version(&entry.name, pin)),
+            };
+            let outcome = match result {
+                None => Outcome::Present,
+                Some(Ok(())) => Outcome::Installed,
+                Some(Err(e)) => Outcome::Failed(format!("{:#}", e)),
+            };
+
+            if entry.hold && !outcome.is_failure() {
+                // `app upgrade` still skips it through the manifest
+                if let Err(e) = backend.hold(slice::from_ref(&entry.name)) {
+                    warn!("{} is not held by {}: {:#}", entry.name, name, e);
+                }
+            }
+            row(entry, outcome)
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::backends::{Package, Update};
+    use anyhow::bail;
+    use std::sync::{Arc, Mutex};
+
+    /// Lists `installed` and records every install in a log shared with
+    /// the other fakes.
+    struct Fake {
+        name: &'static str,
+        stage: u8,
+        installed: Vec<(&'static str, &'static str)>,
+        log: Arc<Mutex<Vec<String>>>,
+    }
+
+    impl Fake {
+        fn record(&self, action: String) {
+            self.log
+                .lock()
+                .unwrap()
+                .push(format!("{} {}", self.name, action));
+        }
+    }
+
+    impl Backend for Fake {
+        fn name(&self) -> &'static str {
+            self.name
+        }
+
+        fn program(&self) -> &'static str {
+            self.name
+        }
+
+        fn platforms(&self) -> &'static [&'static str] {
+            &[]
+        }
+
+        fn search(&self, _query: &str) -> Result<Vec<Package>> {
+            Ok(Vec::new())
+        }
+
+        fn install(&self, packages: &[String]) -> Result<()> {
+            self.record(packages.join(" "));
+            Ok(())
+        }
+
+        fn install_version(&self, package: &str, version: &str) -> Result<()> {
+            self.record(format!("{}@{}", package, version));
+            Ok(())
+        }
+
+        fn uninstall(&self, _packages: &[String]) -> Result<()> {
+            Ok(())
+        }
+
+        fn upgrade(&self, _packages: &[String]) -> Result<()> {
+            Ok(())
+        }
+
+        fn list(&self) -> Result<Vec<Package>> {
+            Ok(self
+                .installed
+                .iter()
+                .map(|(name, version)| Package::new(self.name, *name, Some(version.to_string())))
+                .collect())
+        }
+
+        fn outdated(&self) -> Result<Vec<Update>> {
+            Ok(Vec::new())
+        }
+
+        fn stage(&self) -> u8 {
+            self.stage
+        }
+    }
+
+    fn sync(manifest: &str) -> (Vec<Row>, Vec<String>) {
+        let log = Arc::new(Mutex::new(Vec::new()));
+        let find = |name: &str| -> Result<Box<dyn Backend>> {
+            let (name, stage, installed) = match name {
+                "system" => ("system", 0, vec![("gcc", "14.1.0")]),
+                "lang" => ("lang", 1, vec![("just", "1.36.0")]),
+                _ => bail!("Unknown package manager: {}", name),
+            };
+            Ok(Box::new(Fake {
+                name,
+                stage,
+                installed,
+                log: Arc::clone(&log),
+            }))
+        };
+
+        let rows = run_with(&Manifest::from_toml(manifest).unwrap(), find);
+        let log = log.lock().unwrap().clone();
+        (rows, log)
+    }
+
+    #[test]
+    fn test_stages_run_in_order() {
+        // `lang` sorts first by name but runs in the later stage
+        let (rows, log) = sync(
+            r#"
+            lang = ["ripgrep"]
+            system = ["make"]
+            "#,
+        );
+
+        assert_eq!(log, ["system make", "lang ripgrep"]);
+        let backends: Vec<_> = rows.iter().map(|row| row.backend.as_str()).collect();
+        assert_eq!(backends, ["system", "lang"]);
+    }
+
+    #[test]
+    fn test_satisfied_pin_is_skipped() {
+        let (rows, log) = sync(
+            r#"
+            [system]
+            gcc = "14.1"
+
+            [lang]
+            just = "1.38"
+            "#,
+        );
+
+        assert_eq!(log, ["lang just@1.38"]);
+        let outcome = |package: &str| {
+            rows.iter()
+                .find(|row| row.package == package)
+                .map(|row| row.outcome.clone())
+        };
+        assert_eq!(outcome("gcc"), Some(Outcome::Present));
+        assert_eq!(outcome("just"), Some(Outcome::Installed));
+    }
+
+    #[test]
+    fn test_unknown_backend_is_skipped() {
+        let (rows, log) = sync(r#"nix = ["hello"]"#);
+
+        assert!(log.is_empty());
+        assert_eq!(
+            rows[0].outcome,
+            Outcome::Skipped("Unknown package manager: nix".into())
+        );
+    }
+}
@@ -1,3 +1,4 @@
+use crate::backends;
 use anyhow::Result;
 use std::collections::HashMap;
 
@@ -7,9 +8,18 @@ pub struct PackageManager {
     pub available: bool,
 }
 
+/// Backends supported on this platform, ranked by preference.
 pub fn detect_package_managers() -> Result<Vec<PackageManager>> {
-    // Implementation for detecting available package managers
-    todo!()
+    Ok(backends::all()
+        .into_iter()
+        .filter(|backend| backend.is_supported())
+        .enumerate()
+        .map(|(priority, backend)| PackageManager {
+            name: backend.name().to_string(),
+            priority: priority as i32,
+            available: backend.is_available(),
+        })
+        .collect())
 }
 
 pub fn get_default_managers() -> HashMap<String, Vec<String>> {
@@ -1,68 +1,38 @@
-// use crate::cli::commands::{add, list, remove, update};
-use anyhow::Result;
 use clap::{Parser, Subcommand};
-use logline::info;
+use std::path::PathBuf;
 
 #[derive(Parser)]
 #[command(author, version, about, long_about = None)]
 pub struct Default {
-    /// Optional package manager to use
-    #[arg(short, long)]
-    pub manager: Option<String>,
+    /// Profile to use (defaults to $DOTS_PROFILE, ./dots.toml or the last one applied)
+    #[arg(short, long, global = true)]
+    pub profile: Option<PathBuf>,
 
     #[command(subcommand)]
     pub command: Option<Commands>,
-
-    /// Package name or search query (defaults to list available)
-    pub query: Option<String>,
 }
 
 #[derive(Debug, Subcommand)]
 pub enum Commands {
-    /// Add/install packages
-    Add {
-        /// Packages to install
-        packages: Vec<String>,
+    /// Link files and write the environment script described by the profile
+    Apply,
 
-        /// Install from file
-        #[arg(short, long)]
-        file: Option<String>,
-    },
+    /// Show what `apply` would change without touching anything
+    Diff,
 
-    /// Update packages
-    Update {
-        /// Packages to update
-        packages: Vec<String>,
+    /// Undo the last apply, restoring any files it backed up
+    Revert,
 
-        /// Update from file
-        #[arg(short, long)]
-        file: Option<String>,
-    },
+    /// Clone a dotfiles repository and apply its profile
+    Bootstrap {
+        /// Git URL of the repository
+        url: String,
 
-    /// Remove/uninstall packages
-    Remove {
-        /// Packages to remove
-        packages: Vec<String>,
+        /// Where to clone it (defaults to ~/.dots)
+        #[arg(short, long)]
+        dir: Option<PathBuf>,
     },
 
-    /// List packages or package managers
-    List {
-        #[command(subcommand)]
-        command: ListCommands,
-    },
+    /// Update the repository cloned by `bootstrap`
+    Pull,
 }
-
-#[derive(Debug, Subcommand)]
-pub enum ListCommands {
-    /// List installed packages
-    Installed,
-    /// List available packages (default)
-    Available {
-        /// Search query
-        query: Option<String>,
-    },
-    /// List outdated packages
-    Outdated,
-    /// List available package managers
-    Managers,
-}
\ No newline at end of file
@@ -1,2 +1,7 @@
 mod default;
+mod profile;
+mod repo;
+
 pub use default::*;
+pub use profile::{apply, diff, revert};
+pub use repo::{bootstrap, pull};
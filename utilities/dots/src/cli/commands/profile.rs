@@ -0,0 +1,67 @@
+use crate::profile::{self, Plan, Profile, State};
+use anyhow::Result;
+use logline::info;
+use std::path::Path;
+
+pub fn diff(path: &Path) -> Result<()> {
+    let profile = Profile::load(path)?.for_machine();
+    let state = State::load(&profile::state_path()?)?;
+    let plan = Plan::new(&profile, &state)?;
+
+    for action in &plan.actions {
+        println!("{}", action);
+    }
+    println!(
+        "{} to change, {} unchanged",
+        plan.actions.len(),
+        plan.unchanged
+    );
+
+    Ok(())
+}
+
+pub fn apply(path: &Path) -> Result<()> {
+    let profile = Profile::load(path)?.for_machine();
+    let state_path = profile::state_path()?;
+    let mut state = State::load(&state_path)?;
+    let plan = Plan::new(&profile, &state)?;
+
+    let installed = app::backends::ensure(&profile.packages)?;
+    if !installed.is_empty() {
+        info!("Installed {}", installed.join(", "));
+    }
+
+    if plan.is_empty() {
+        info!("Nothing to do, {} entries up to date", plan.unchanged);
+        return Ok(());
+    }
+
+    // Record whatever succeeded, even if a later action fails
+    let result = plan.apply(&mut state);
+    state.profile = path.canonicalize().ok();
+    state.save(&state_path)?;
+    result?;
+
+    if let Some(script) = &state.env_script {
+        info!(
+            "Source {} from your shell rc to load the environment",
+            script.display()
+        );
+    }
+
+    Ok(())
+}
+
+pub fn revert() -> Result<()> {
+    let state_path = profile::state_path()?;
+    let mut state = State::load(&state_path)?;
+
+    if state.is_empty() {
+        info!("Nothing to revert");
+        return Ok(());
+    }
+
+    let result = profile::revert(&mut state);
+    state.save(&state_path)?;
+    result
+}
@@ -0,0 +1,137 @@
+use super::apply;
+use crate::profile::{self, State, PROFILE_FILE};
+use anyhow::{bail, Context, Result};
+use logline::{debug, info};
+use std::{ffi::OsStr, path::Path, process::Command};
+
+/// Clones `url` (unless it is already checked out in `dir`), records the
+/// checkout and applies its profile.
+pub fn bootstrap(url: &str, dir: Option<&Path>) -> Result<()> {
+    let dir = match dir {
+        Some(dir) => dir.to_path_buf(),
+        None => profile::repo_dir()?,
+    };
+
+    checkout(url, &dir)?;
+
+    let profile = dir.join(PROFILE_FILE);
+    if !profile.is_file() {
+        bail!("{} has no {}", url, PROFILE_FILE);
+    }
+
+    let state_path = profile::state_path()?;
+    let mut state = State::load(&state_path)?;
+    state.repo = Some(dir.canonicalize().unwrap_or(dir));
+    state.save(&state_path)?;
+
+    apply(&profile)
+}
+
+/// Clones `url` into `dir` unless a checkout is already there.
+fn checkout(url: &str, dir: &Path) -> Result<()> {
+    if dir.join(".git").exists() {
+        info!("Using the existing checkout in {}", dir.display());
+        return Ok(());
+    }
+
+    info!("Cloning {} into {}", url, dir.display());
+    // `--` keeps a URL starting with `-` from being read as an option
+    git(
+        None,
+        &[
+            "clone".as_ref(),
+            "--".as_ref(),
+            url.as_ref(),
+            dir.as_os_str(),
+        ],
+    )
+}
+
+/// Fast-forwards the checkout recorded by `bootstrap`.
+pub fn pull() -> Result<()> {
+    let state = State::load(&profile::state_path()?)?;
+    let Some(dir) = state.repo else {
+        bail!("No dotfiles repository recorded, run `dots bootstrap <url>` first");
+    };
+
+    fast_forward(&dir)?;
+    info!("Run `dots apply` to bring the machine up to date");
+    Ok(())
+}
+
+fn fast_forward(dir: &Path) -> Result<()> {
+    git(Some(dir), &["pull".as_ref(), "--ff-only".as_ref()])
+}
+
+fn git(dir: Option<&Path>, args: &[&OsStr]) -> Result<()> {
+    let mut command = Command::new("git");
+    if let Some(dir) = dir {
+        command.arg("-C").arg(dir);
+    }
+    let line: Vec<_> = args.iter().map(|arg| arg.to_string_lossy()).collect();
+    debug!("Running: git {}", line.join(" "));
+
+    let status = command
+        .args(args)
+        .status()
+        .context("Failed to execute git")?;
+    if !status.success() {
+        bail!("git {} exited with {}", line[0], status);
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+
+    /// Commits a new `file` to the repository in `dir`, creating it if needed.
+    fn commit(dir: &Path, file: &str) {
+        let run = |args: &[&str]| {
+            let args: Vec<&OsStr> = args.iter().map(OsStr::new).collect();
+            git(Some(dir), &args).unwrap();
+        };
+        fs::create_dir_all(dir).unwrap();
+        fs::write(dir.join(file), file).unwrap();
+        run(&["init", "--quiet"]);
+        run(&["add", file]);
+        run(&[
+            "-c",
+            "user.name=dots",
+            "-c",
+            "user.email=dots@localhost",
+            "commit",
+            "--quiet",
+            "-m",
+            file,
+        ]);
+    }
+
+    #[test]
+    fn test_checkout_clones_then_fast_forwards() {
+        let temp = tempfile::tempdir().unwrap();
+        let origin = temp.path().join("origin");
+        let clone = temp.path().join("clone");
+        commit(&origin, PROFILE_FILE);
+
+        checkout(&origin.to_string_lossy(), &clone).unwrap();
+        assert!(clone.join(PROFILE_FILE).is_file());
+        // An existing checkout is left alone
+        checkout("/nowhere", &clone).unwrap();
+
+        commit(&origin, "later.toml");
+        fast_forward(&clone).unwrap();
+        assert!(clone.join("later.toml").is_file());
+    }
+
+    #[test]
+    fn test_checkout_treats_url_as_operand() {
+        let temp = tempfile::tempdir().unwrap();
+        let marker = temp.path().join("marker");
+        let url = format!("--upload-pack=touch {}", marker.display());
+
+        assert!(checkout(&url, &temp.path().join("clone")).is_err());
+        assert!(!marker.exists());
+    }
+}
@@ -1,40 +1,28 @@
-use crate::cli::commands::{Commands, Default, ListCommands};
+use crate::{
+    cli::commands::{apply, bootstrap, diff, pull, revert, Commands, Default},
+    profile::{self, State},
+};
 use anyhow::Result;
 use clap::Parser;
 use logline::info;
 
 pub fn init() -> Result<()> {
-    let mut cli = Default::parse();
+    let cli = Default::parse();
 
     info!("Config: {:#?}", cli.command);
 
-    // match cli.command {
-    //     Some(Commands::Add { packages, file }) => {
-    //         info!("Adding packages: {:?}, file: {:?}", packages, file);
-    //         add(&packages, &file);
-    //     }
-    //     Some(Commands::Update { packages, file }) => {
-    //         info!("Updating packages: {:?}, file: {:?}", packages, file);
-    //         update(&packages, &file);
-    //     }
-    //     Some(Commands::Remove { packages }) => {
-    //         info!("Removing packages: {:?}", packages);
-    //         remove(&packages);
-    //     }
-    //     Some(Commands::List { command }) => {
-    //         info!("List command: {:?}", command);
-    //         list(&command);
-    //     }
-    //     None => {
-    //         // Default to list available if a query is provided
-    //         if let Some(query) = cli.query {
-    //             info!("Default list available with query: {}", query);
-    //             list(&ListCommands::Available { query: Some(query) });
-    //         } else {
-    //             info!("No command or query provided");
-    //         }
-    //     }
-    // }
+    let recorded = State::load(&profile::state_path()?)?.profile;
+    let path = profile::locate(cli.profile.as_deref(), recorded.as_deref());
 
-    Ok(())
+    match cli.command {
+        Some(Commands::Apply) => apply(&path),
+        Some(Commands::Diff) => diff(&path),
+        Some(Commands::Revert) => revert(),
+        Some(Commands::Bootstrap { url, dir }) => bootstrap(&url, dir.as_deref()),
+        Some(Commands::Pull) => pull(),
+        None => {
+            info!("No command provided");
+            Ok(())
+        }
+    }
 }
@@ -0,0 +1,81 @@
+use crate::path::Syntax;
+use directories::{BaseDirs, UserDirs};
+use serde::Deserialize;
+use std::{fmt, path::PathBuf};
+
+/// A shell whose startup file dots writes to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Shell {
+    Bash,
+    Zsh,
+    Fish,
+    #[serde(alias = "nu")]
+    Nushell,
+    #[serde(alias = "pwsh")]
+    PowerShell,
+}
+
+impl Shell {
+    pub const ALL: [Self; 5] = [
+        Self::Bash,
+        Self::Zsh,
+        Self::Fish,
+        Self::Nushell,
+        Self::PowerShell,
+    ];
+
+    /// The language PATH changes are written in for this shell.
+    pub fn syntax(&self) -> Syntax {
+        match self {
+            Self::Bash | Self::Zsh => Syntax::Posix,
+            Self::Fish => Syntax::Fish,
+            Self::Nushell => Syntax::Nushell,
+            Self::PowerShell => Syntax::PowerShell,
+        }
+    }
+
+    /// Where the shell reads its interactive configuration from.
+    pub fn rc(&self) -> Option<PathBuf> {
+        let dirs = BaseDirs::new()?;
+        let home = dirs.home_dir();
+        let config = dirs.config_dir();
+
+        match self {
+            Self::Bash => Some(home.join(".bashrc")),
+            Self::Zsh => Some(home.join(".zshrc")),
+            Self::Fish => Some(config.join("fish").join("config.fish")),
+            Self::Nushell => Some(config.join("nushell").join("config.nu")),
+            Self::PowerShell => {
+                let dir = if cfg!(windows) {
+                    UserDirs::new()?.document_dir()?.join("PowerShell")
+                } else {
+                    config.join("powershell")
+                };
+                Some(dir.join("Microsoft.PowerShell_profile.ps1"))
+            }
+        }
+    }
+
+    /// Shells whose rc file already exists; missing files are not created.
+    pub fn installed() -> Vec<(Self, PathBuf)> {
+        Self::ALL
+            .into_iter()
+            .filter_map(|shell| shell.rc().map(|rc| (shell, rc)))
+            .filter(|(_, rc)| rc.is_file())
+            .collect()
+    }
+}
+
+impl fmt::Display for Shell {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let name = match self {
+            Self::Bash => "bash",
+            Self::Zsh => "zsh",
+            Self::Fish => "fish",
+            Self::Nushell => "nushell",
+            Self::PowerShell => "powershell",
+        };
+        write!(f, "{}", name)
+    }
+}
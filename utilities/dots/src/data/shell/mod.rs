@@ -4,6 +4,10 @@
 // mod process;
 
 // mod bash;
+mod kind;
+
+pub use kind::Shell;
+
 pub fn test() {
     println!("Testing shell");
 }
@@ -1,3 +1,6 @@
 pub mod cli;
 pub mod data;
+pub mod path;
+pub mod profile;
+pub mod snippet;
 pub mod utils;
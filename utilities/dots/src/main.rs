@@ -7,11 +7,6 @@ fn main() -> anyhow::Result<()> {
 
     // Shell Definitions
     logline::trace!("Identified installed shells and their associated rc files (bash, zsh, pwsh, powershell, fish, nushell, etc.)");
-    utils::test();
 
-    // Define directories that should be added to path
-
-    // Update the config rc for each shell
-
-    Ok(())
+    cli::init()
 }
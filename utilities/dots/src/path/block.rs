@@ -0,0 +1,69 @@
+/// First line of the block dots manages inside a shell rc file.
+pub const BEGIN: &str = "# >>> dots >>>";
+
+/// Last line of the managed block.
+pub const END: &str = "# <<< dots <<<";
+
+/// Puts `body` between the markers in `content`, replacing an existing
+/// block in place or appending one at the end. Running it twice with the
+/// same body returns the same text.
+pub fn inject(content: &str, body: &str) -> String {
+    let mut block = format!("{}\n{}", BEGIN, body);
+    if !block.ends_with('\n') {
+        block.push('\n');
+    }
+    block.push_str(END);
+    block.push('\n');
+
+    match span(content) {
+        Some((start, end)) => format!("{}{}{}", &content[..start], block, &content[end..]),
+        None if content.is_empty() => block,
+        None if content.ends_with('\n') => format!("{}\n{}", content, block),
+        None => format!("{}\n\n{}", content, block),
+    }
+}
+
+/// Strips the managed block, along with the blank line `inject` put
+/// before it. Returns `None` when there is no block.
+pub fn remove(content: &str) -> Option<String> {
+    let (start, end) = span(content)?;
+    let before = &content[..start];
+    let before = before
+        .strip_suffix("\n\n")
+        .map_or(before, |b| &before[..b.len() + 1]);
+    Some(format!("{}{}", before, &content[end..]))
+}
+
+/// Byte range of the block, including the newline after the end marker.
+fn span(content: &str) -> Option<(usize, usize)> {
+    let start = content.find(BEGIN)?;
+    let end = start + content[start..].find(END)? + END.len();
+    let end = match content[end..].starts_with('\n') {
+        true => end + 1,
+        false => end,
+    };
+    Some((start, end))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_inject_is_idempotent_and_removable() {
+        let rc = "alias ll='ls -l'\n";
+        let once = inject(rc, "export PATH\n");
+        assert_eq!(
+            once,
+            "alias ll='ls -l'\n\n# >>> dots >>>\nexport PATH\n# <<< dots <<<\n"
+        );
+        assert_eq!(inject(&once, "export PATH\n"), once);
+
+        let updated = inject(&once, "fish_add_path /opt/bin\n");
+        assert!(updated.contains("fish_add_path /opt/bin\n# <<< dots <<<"));
+        assert!(!updated.contains("export PATH"));
+
+        assert_eq!(remove(&updated).as_deref(), Some(rc));
+        assert_eq!(remove(rc), None);
+    }
+}
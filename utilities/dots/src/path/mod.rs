@@ -0,0 +1,165 @@
+mod block;
+
+pub use block::{inject, remove, BEGIN, END};
+
+use crate::profile::expand;
+use std::fmt;
+
+/// Shell language a PATH fragment is written in.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Syntax {
+    /// sh, bash and zsh.
+    Posix,
+    Fish,
+    Nushell,
+    PowerShell,
+}
+
+impl fmt::Display for Syntax {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let name = match self {
+            Self::Posix => "posix",
+            Self::Fish => "fish",
+            Self::Nushell => "nushell",
+            Self::PowerShell => "powershell",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+/// Directories to put in front of PATH, in order and each listed once.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct PathSet {
+    dirs: Vec<String>,
+}
+
+impl PathSet {
+    /// Expands `~` and drops repeats, keeping the first occurrence.
+    pub fn new<I, S>(dirs: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        let mut set = Self::default();
+        for dir in dirs {
+            let dir = expand(dir.as_ref()).to_string_lossy().into_owned();
+            let dir = match dir.trim_end_matches(['/', '\\']) {
+                "" => dir,
+                trimmed => trimmed.to_string(),
+            };
+            if !set.dirs.contains(&dir) {
+                set.dirs.push(dir);
+            }
+        }
+        set
+    }
+
+    pub fn dirs(&self) -> &[String] {
+        &self.dirs
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.dirs.is_empty()
+    }
+
+    /// A fragment that prepends the directories missing from PATH, so
+    /// sourcing it again changes nothing.
+    pub fn render(&self, syntax: Syntax) -> String {
+        if self.dirs.is_empty() {
+            return String::new();
+        }
+
+        match syntax {
+            Syntax::Posix => {
+                // Prepend in reverse so the first directory ends up first
+                let mut fragment: String = self
+                    .dirs
+                    .iter()
+                    .rev()
+                    .map(|dir| {
+                        let dir = posix_quote(dir);
+                        format!(
+                            "case \":$PATH:\" in *\":{0}:\"*) ;; *) PATH=\"{0}:$PATH\" ;; esac\n",
+                            dir
+                        )
+                    })
+                    .collect();
+                fragment.push_str("export PATH\n");
+                fragment
+            }
+            Syntax::Fish => {
+                let dirs: Vec<String> = self.dirs.iter().map(|dir| fish_quote(dir)).collect();
+                format!("fish_add_path --global --path {}\n", dirs.join(" "))
+            }
+            Syntax::Nushell => {
+                // `uniq` keeps the first occurrence, so earlier entries win
+                let dirs: Vec<String> = self.dirs.iter().map(|dir| nu_quote(dir)).collect();
+                format!(
+                    "$env.PATH = ($env.PATH | split row (char esep) | prepend [{}] | uniq)\n",
+                    dirs.join(" ")
+                )
+            }
+            Syntax::PowerShell => {
+                let dirs: Vec<String> = self.dirs.iter().rev().map(|dir| pwsh_quote(dir)).collect();
+                format!(
+                    "foreach ($dir in @({})) {{\n    \
+                     if (($env:PATH -split [IO.Path]::PathSeparator) -notcontains $dir) {{\n        \
+                     $env:PATH = $dir + [IO.Path]::PathSeparator + $env:PATH\n    \
+                     }}\n}}\n",
+                    dirs.join(", ")
+                )
+            }
+        }
+    }
+}
+
+fn posix_quote(value: &str) -> String {
+    value
+        .replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace('$', "\\$")
+        .replace('`', "\\`")
+}
+
+fn fish_quote(value: &str) -> String {
+    format!("'{}'", value.replace('\\', "\\\\").replace('\'', "\\'"))
+}
+
+fn nu_quote(value: &str) -> String {
+    format!("\"{}\"", value.replace('\\', "\\\\").replace('"', "\\\""))
+}
+
+fn pwsh_quote(value: &str) -> String {
+    format!("'{}'", value.replace('\'', "''"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_render_keeps_order_and_drops_repeats() {
+        let set = PathSet::new(["/opt/bin", "/usr/local/bin/", "/opt/bin"]);
+        assert_eq!(set.dirs(), ["/opt/bin", "/usr/local/bin"]);
+
+        let posix = set.render(Syntax::Posix);
+        let lines: Vec<_> = posix.lines().collect();
+        assert_eq!(
+            lines[0],
+            "case \":$PATH:\" in *\":/usr/local/bin:\"*) ;; *) PATH=\"/usr/local/bin:$PATH\" ;; esac"
+        );
+        assert!(lines[1].contains("PATH=\"/opt/bin:$PATH\""));
+
+        assert_eq!(
+            set.render(Syntax::Fish),
+            "fish_add_path --global --path '/opt/bin' '/usr/local/bin'\n"
+        );
+        assert_eq!(
+            set.render(Syntax::Nushell),
+            "$env.PATH = ($env.PATH | split row (char esep) | prepend [\"/opt/bin\" \"/usr/local/bin\"] | uniq)\n"
+        );
+        assert!(set
+            .render(Syntax::PowerShell)
+            .starts_with("foreach ($dir in @('/usr/local/bin', '/opt/bin'))"));
+    }
+}
@@ -0,0 +1,43 @@
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+
+/// A file or directory to link into place via slink.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct Link {
+    /// Path to link, relative to the profile file.
+    pub source: PathBuf,
+
+    /// Directory the link is created in, defaults to the user config dir.
+    pub target: Option<String>,
+
+    /// Operating systems this link applies to, all when empty.
+    #[serde(default)]
+    pub os: Os,
+}
+
+impl Link {
+    /// The source path resolved against the profile directory.
+    pub fn source_in(&self, root: &Path) -> PathBuf {
+        root.join(&self.source)
+    }
+}
+
+/// A single OS name or a list of them, matched against `std::env::consts::OS`.
+#[derive(Debug, Clone, Default, PartialEq, Deserialize, Serialize)]
+#[serde(untagged)]
+pub enum Os {
+    #[default]
+    Any,
+    One(String),
+    Many(Vec<String>),
+}
+
+impl Os {
+    pub fn matches(&self, os: &str) -> bool {
+        match self {
+            Self::Any => true,
+            Self::One(name) => name == os,
+            Self::Many(names) => names.is_empty() || names.iter().any(|name| name == os),
+        }
+    }
+}
@@ -0,0 +1,369 @@
+mod link;
+mod overlay;
+mod plan;
+mod state;
+
+pub use link::{Link, Os};
+pub use overlay::Overlay;
+pub use plan::{revert, Action, Plan};
+pub use state::State;
+
+use crate::{
+    data::shell::Shell,
+    path::{PathSet, Syntax},
+    snippet::{self, Snippet},
+};
+use anyhow::{anyhow, Context, Result};
+use directories::BaseDirs;
+use serde::Deserialize;
+use std::{
+    collections::BTreeMap,
+    env, fs,
+    path::{Path, PathBuf},
+};
+
+/// Profile file used when neither `--profile` nor `DOTS_PROFILE` is set.
+pub const PROFILE_FILE: &str = "dots.toml";
+
+/// A declarative description of a machine's dotfiles, read from `dots.toml`.
+///
+/// ```toml
+/// path = ["~/.local/bin"]
+/// packages = ["ripgrep", "bat"]
+///
+/// [env]
+/// EDITOR = "hx"
+///
+/// [[links]]
+/// source = "config/helix"
+///
+/// [[links]]
+/// source = "home/.bashrc"
+/// target = "~"
+/// os = ["linux", "macos"]
+///
+/// [[snippets]]
+/// name = "ll"
+/// command = "ls -la"
+///
+/// [os.windows.env]
+/// EDITOR = "code"
+///
+/// [host."work-laptop"]
+/// path = ["C:/tools/bin"]
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Deserialize)]
+pub struct Profile {
+    /// Directory the profile was loaded from; link sources are relative to it.
+    #[serde(skip)]
+    pub root: PathBuf,
+
+    /// Directories prepended to PATH, in order.
+    #[serde(default)]
+    pub path: Vec<String>,
+
+    /// Environment variables exported by the generated env script.
+    #[serde(default)]
+    pub env: BTreeMap<String, String>,
+
+    #[serde(default)]
+    pub links: Vec<Link>,
+
+    /// Installed with the first available package manager if missing.
+    #[serde(default)]
+    pub packages: Vec<String>,
+
+    /// Aliases written into each shell's rc file.
+    #[serde(default)]
+    pub snippets: Vec<Snippet>,
+
+    /// Replacements for the built-in snippet templates, keyed by shell.
+    #[serde(default)]
+    pub templates: BTreeMap<Shell, String>,
+
+    /// Overlays keyed by OS name, as reported by `std::env::consts::OS`.
+    #[serde(default)]
+    pub os: BTreeMap<String, Overlay>,
+
+    /// Overlays keyed by hostname, applied after the OS overlay.
+    #[serde(default)]
+    pub host: BTreeMap<String, Overlay>,
+}
+
+impl Profile {
+    pub fn from_toml(content: &str, root: &Path) -> Result<Self> {
+        let mut profile: Self = toml::from_str(content).context("Failed to parse profile")?;
+        profile.root = root.to_path_buf();
+        Ok(profile)
+    }
+
+    pub fn load(path: &Path) -> Result<Self> {
+        let content = fs::read_to_string(path)
+            .with_context(|| format!("Failed to read profile {}", path.display()))?;
+        let root = path
+            .canonicalize()
+            .ok()
+            .and_then(|path| path.parent().map(Path::to_path_buf))
+            .unwrap_or_default();
+
+        Self::from_toml(&content, &root)
+            .with_context(|| format!("Invalid profile {}", path.display()))
+    }
+
+    /// Flattens the overlays for the running OS and this machine's hostname.
+    pub fn for_machine(&self) -> Self {
+        let host = sysinfo::System::host_name();
+        self.overlaid(env::consts::OS, host.as_deref())
+    }
+
+    /// Returns the base profile with the `os` overlay, then the `host` overlay,
+    /// merged in. Host names are matched case-insensitively.
+    pub fn overlaid(&self, os: &str, host: Option<&str>) -> Self {
+        let mut profile = Self {
+            root: self.root.clone(),
+            path: self.path.clone(),
+            env: self.env.clone(),
+            links: self.links.clone(),
+            packages: self.packages.clone(),
+            snippets: self.snippets.clone(),
+            templates: self.templates.clone(),
+            ..Self::default()
+        };
+
+        if let Some(overlay) = self.os.get(os) {
+            profile.merge(overlay);
+        }
+
+        let host_overlay = host.and_then(|host| {
+            self.host
+                .iter()
+                .find(|(name, _)| name.eq_ignore_ascii_case(host))
+                .map(|(_, overlay)| overlay)
+        });
+        if let Some(overlay) = host_overlay {
+            profile.merge(overlay);
+        }
+
+        profile
+    }
+
+    fn merge(&mut self, overlay: &Overlay) {
+        for dir in &overlay.path {
+            if !self.path.contains(dir) {
+                self.path.push(dir.clone());
+            }
+        }
+
+        for package in &overlay.packages {
+            if !self.packages.contains(package) {
+                self.packages.push(package.clone());
+            }
+        }
+
+        self.env.extend(overlay.env.clone());
+
+        for link in &overlay.links {
+            self.links.retain(|base| base.source != link.source);
+            self.links.push(link.clone());
+        }
+
+        for snippet in &overlay.snippets {
+            self.snippets.retain(|base| base.name != snippet.name);
+            self.snippets.push(snippet.clone());
+        }
+    }
+
+    /// Links that apply to the running operating system.
+    pub fn active_links(&self) -> impl Iterator<Item = &Link> {
+        self.links
+            .iter()
+            .filter(|link| link.os.matches(env::consts::OS))
+    }
+
+    /// Maps each active link to (link path, source path), using slink's naming.
+    pub fn resolved_links(&self) -> Result<Vec<(PathBuf, PathBuf)>> {
+        let default_base = config_dir()?;
+
+        self.active_links()
+            .map(|link| {
+                let source = link.source_in(&self.root);
+                let base = link.target.as_deref().map_or(default_base.clone(), expand);
+                let config = slink::Config::new(false, vec![source.clone()], base);
+                let path = config.resolve_link_path(&source)?;
+                Ok((path, source))
+            })
+            .collect()
+    }
+
+    /// The PATH directories with `~` expanded and repeats dropped.
+    pub fn path_set(&self) -> PathSet {
+        PathSet::new(&self.path)
+    }
+
+    /// What goes in the managed block of `shell`'s rc file: the PATH
+    /// fragment and then the snippets. `None` when there is neither.
+    pub fn rc_block(&self, shell: Shell) -> Option<String> {
+        let paths = self.path_set();
+        if paths.is_empty() && self.snippets.is_empty() {
+            return None;
+        }
+
+        Some(format!(
+            "{}{}",
+            paths.render(shell.syntax()),
+            snippet::render(&self.snippets, &self.templates, shell)
+        ))
+    }
+
+    /// Renders a POSIX script exporting `env` and `path`, if either is set.
+    pub fn env_script(&self) -> Option<String> {
+        if self.env.is_empty() && self.path.is_empty() {
+            return None;
+        }
+
+        let mut script = String::from("# Generated by dots, do not edit.\n");
+        for (key, value) in &self.env {
+            script.push_str(&format!("export {}=\"{}\"\n", key, escape(value)));
+        }
+        script.push_str(&self.path_set().render(Syntax::Posix));
+
+        Some(script)
+    }
+}
+
+/// Resolves the profile path from an explicit argument, `DOTS_PROFILE`, the
+/// cwd, or else the profile `recorded` by the last apply.
+pub fn locate(explicit: Option<&Path>, recorded: Option<&Path>) -> PathBuf {
+    let local = PathBuf::from(PROFILE_FILE);
+
+    explicit
+        .map(Path::to_path_buf)
+        .or_else(|| env::var_os("DOTS_PROFILE").map(PathBuf::from))
+        .or_else(|| local.exists().then(|| local.clone()))
+        .or_else(|| recorded.map(Path::to_path_buf))
+        .unwrap_or(local)
+}
+
+/// Where `dots bootstrap` clones to when no directory is given.
+pub fn repo_dir() -> Result<PathBuf> {
+    BaseDirs::new()
+        .map(|dirs| dirs.home_dir().join(".dots"))
+        .ok_or_else(|| anyhow!("Could not determine home directory"))
+}
+
+/// Directory holding the apply state and generated env script.
+pub fn data_dir() -> Result<PathBuf> {
+    BaseDirs::new()
+        .map(|dirs| dirs.data_dir().join("dots"))
+        .ok_or_else(|| anyhow!("Could not determine data directory"))
+}
+
+pub fn state_path() -> Result<PathBuf> {
+    Ok(data_dir()?.join("state.toml"))
+}
+
+pub fn env_script_path() -> Result<PathBuf> {
+    Ok(data_dir()?.join("env.sh"))
+}
+
+fn config_dir() -> Result<PathBuf> {
+    BaseDirs::new()
+        .map(|dirs| dirs.config_dir().to_path_buf())
+        .ok_or_else(|| anyhow!("Could not determine config directory"))
+}
+
+/// Expands a leading `~` to the home directory.
+pub fn expand(path: &str) -> PathBuf {
+    let home = || BaseDirs::new().map(|dirs| dirs.home_dir().to_path_buf());
+
+    match path.strip_prefix('~') {
+        Some("") => home().unwrap_or_else(|| PathBuf::from(path)),
+        Some(rest) if rest.starts_with('/') || rest.starts_with('\\') => match home() {
+            Some(home) => home.join(&rest[1..]),
+            None => PathBuf::from(path),
+        },
+        _ => PathBuf::from(path),
+    }
+}
+
+fn escape(value: &str) -> String {
+    value.replace('\\', "\\\\").replace('"', "\\\"")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_profile_parsing_and_os_filter() {
+        let profile = Profile::from_toml(
+            r#"
+            path = ["/opt/bin"]
+
+            [env]
+            EDITOR = "hx"
+
+            [[links]]
+            source = "config/helix"
+
+            [[links]]
+            source = "config/windows-terminal"
+            os = "windows"
+            "#,
+            Path::new("/dotfiles"),
+        )
+        .unwrap();
+
+        let active: Vec<_> = profile.active_links().collect();
+        if cfg!(windows) {
+            assert_eq!(active.len(), 2);
+        } else {
+            assert_eq!(active.len(), 1);
+            assert_eq!(
+                active[0].source_in(&profile.root),
+                Path::new("/dotfiles/config/helix")
+            );
+        }
+
+        let script = profile.env_script().unwrap();
+        assert!(script.contains("export EDITOR=\"hx\"\n"));
+        assert!(script.contains("*) PATH=\"/opt/bin:$PATH\" ;; esac\nexport PATH\n"));
+    }
+
+    #[test]
+    fn test_host_overlay_applies_after_os() {
+        let profile = Profile::from_toml(
+            r#"
+            path = ["/opt/bin"]
+
+            [env]
+            EDITOR = "hx"
+            PAGER = "less"
+
+            [[links]]
+            source = "config/git"
+
+            [os.linux.env]
+            EDITOR = "nvim"
+
+            [host."Work-Laptop"]
+            path = ["/opt/work/bin", "/opt/bin"]
+            env = { EDITOR = "code" }
+            links = [{ source = "config/git", target = "~/work" }]
+            "#,
+            Path::new("/dotfiles"),
+        )
+        .unwrap();
+
+        let home = profile.overlaid("linux", Some("home-desktop"));
+        assert_eq!(home.env["EDITOR"], "nvim");
+        assert_eq!(home.path, vec!["/opt/bin"]);
+
+        let work = profile.overlaid("linux", Some("work-laptop"));
+        assert_eq!(work.env["EDITOR"], "code");
+        assert_eq!(work.env["PAGER"], "less");
+        assert_eq!(work.path, vec!["/opt/bin", "/opt/work/bin"]);
+        assert_eq!(work.links.len(), 1);
+        assert_eq!(work.links[0].target.as_deref(), Some("~/work"));
+    }
+}
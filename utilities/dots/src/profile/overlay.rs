@@ -0,0 +1,26 @@
+use super::Link;
+use crate::snippet::Snippet;
+use serde::Deserialize;
+use std::collections::BTreeMap;
+
+/// Settings layered over the base profile for a specific OS or host.
+///
+/// PATH entries and packages are appended, env vars override, links replace any base
+/// link with the same source and snippets any with the same name.
+#[derive(Debug, Clone, Default, PartialEq, Deserialize)]
+pub struct Overlay {
+    #[serde(default)]
+    pub path: Vec<String>,
+
+    #[serde(default)]
+    pub env: BTreeMap<String, String>,
+
+    #[serde(default)]
+    pub links: Vec<Link>,
+
+    #[serde(default)]
+    pub snippets: Vec<Snippet>,
+
+    #[serde(default)]
+    pub packages: Vec<String>,
+}
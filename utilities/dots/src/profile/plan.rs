@@ -0,0 +1,454 @@
+use super::{env_script_path, Profile, State};
+use crate::{data::shell::Shell, path};
+use anyhow::{bail, Context, Result};
+use logline::{debug, info, warn};
+use std::{
+    fmt, fs, io,
+    path::{Path, PathBuf},
+};
+
+/// A single change needed to bring the machine in line with the profile.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Action {
+    /// Nothing exists at `link` yet.
+    CreateLink {
+        link: PathBuf,
+        source: PathBuf,
+    },
+    /// Something else occupies `link`; it is backed up unless dots created it.
+    ReplaceLink {
+        link: PathBuf,
+        source: PathBuf,
+        backup: bool,
+    },
+    /// A link from a previous apply that the profile no longer declares.
+    RemoveLink {
+        link: PathBuf,
+        source: PathBuf,
+    },
+    WriteEnv {
+        path: PathBuf,
+        content: String,
+    },
+    RemoveEnv {
+        path: PathBuf,
+    },
+    /// Adds or updates the managed block in a shell rc file.
+    WriteBlock {
+        rc: PathBuf,
+        content: String,
+    },
+    /// Takes the managed block out of a shell rc file.
+    RemoveBlock {
+        rc: PathBuf,
+        content: String,
+    },
+}
+
+impl fmt::Display for Action {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::CreateLink { link, source } => {
+                write!(f, "+ {} -> {}", link.display(), source.display())
+            }
+            Self::ReplaceLink {
+                link,
+                source,
+                backup,
+            } => {
+                write!(f, "~ {} -> {}", link.display(), source.display())?;
+                if *backup {
+                    write!(f, " (backing up existing)")?;
+                }
+                Ok(())
+            }
+            Self::RemoveLink { link, .. } => write!(f, "- {}", link.display()),
+            Self::WriteEnv { path, .. } => write!(f, "~ {}", path.display()),
+            Self::RemoveEnv { path } => write!(f, "- {}", path.display()),
+            Self::WriteBlock { rc, .. } => write!(f, "~ {} (dots block)", rc.display()),
+            Self::RemoveBlock { rc, .. } => write!(f, "- {} (dots block)", rc.display()),
+        }
+    }
+}
+
+/// The difference between a profile and what the last apply left behind.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Plan {
+    pub actions: Vec<Action>,
+    pub unchanged: usize,
+}
+
+impl Plan {
+    pub fn new(profile: &Profile, state: &State) -> Result<Self> {
+        let mut plan = Self::default();
+        let desired = profile.resolved_links()?;
+
+        for (link, source) in &desired {
+            match fs::symlink_metadata(link) {
+                Err(e) if e.kind() == io::ErrorKind::NotFound => {
+                    plan.actions.push(Action::CreateLink {
+                        link: link.clone(),
+                        source: source.clone(),
+                    });
+                }
+                Err(e) => {
+                    return Err(e).with_context(|| format!("Failed to inspect {}", link.display()))
+                }
+                Ok(_) if points_to(link, source) => plan.unchanged += 1,
+                Ok(metadata) => plan.actions.push(Action::ReplaceLink {
+                    link: link.clone(),
+                    source: source.clone(),
+                    backup: !(metadata.file_type().is_symlink() && state.links.contains_key(link)),
+                }),
+            }
+        }
+
+        for (link, source) in &state.links {
+            if !desired.iter().any(|(path, _)| path == link) {
+                plan.actions.push(Action::RemoveLink {
+                    link: link.clone(),
+                    source: source.clone(),
+                });
+            }
+        }
+
+        let env_path = env_script_path()?;
+        match profile.env_script() {
+            Some(content) => {
+                if fs::read_to_string(&env_path).ok().as_deref() == Some(content.as_str()) {
+                    plan.unchanged += 1;
+                } else {
+                    plan.actions.push(Action::WriteEnv {
+                        path: env_path,
+                        content,
+                    });
+                }
+            }
+            None => {
+                if let Some(path) = &state.env_script {
+                    plan.actions.push(Action::RemoveEnv { path: path.clone() });
+                }
+            }
+        }
+
+        for (shell, rc) in Shell::installed() {
+            let current = fs::read_to_string(&rc)
+                .with_context(|| format!("Failed to read {}", rc.display()))?;
+
+            let Some(block) = profile.rc_block(shell) else {
+                if let Some(content) = path::remove(&current) {
+                    plan.actions.push(Action::RemoveBlock { rc, content });
+                }
+                continue;
+            };
+
+            let content = path::inject(&current, &block);
+            if content == current {
+                plan.unchanged += 1;
+            } else {
+                plan.actions.push(Action::WriteBlock { rc, content });
+            }
+        }
+
+        Ok(plan)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.actions.is_empty()
+    }
+
+    /// Performs every action, recording what was done in `state`.
+    pub fn apply(&self, state: &mut State) -> Result<()> {
+        for action in &self.actions {
+            info!("{}", action);
+
+            match action {
+                Action::CreateLink { link, source } => {
+                    create_link(link, source)?;
+                    state.links.insert(link.clone(), source.clone());
+                }
+                Action::ReplaceLink {
+                    link,
+                    source,
+                    backup,
+                } => {
+                    if *backup {
+                        let backup_path = backup_path(link);
+                        if backup_path.exists() {
+                            bail!(
+                                "Cannot back up {}: {} already exists",
+                                link.display(),
+                                backup_path.display()
+                            );
+                        }
+                        fs::rename(link, &backup_path)
+                            .with_context(|| format!("Failed to back up {}", link.display()))?;
+                        state.backups.insert(link.clone(), backup_path);
+                    } else {
+                        remove_symlink(link)?;
+                    }
+                    create_link(link, source)?;
+                    state.links.insert(link.clone(), source.clone());
+                }
+                Action::RemoveLink { link, source } => {
+                    remove_link(link, source)?;
+                    restore_backup(link, state)?;
+                    state.links.remove(link);
+                }
+                Action::WriteEnv { path, content } => {
+                    if let Some(parent) = path.parent() {
+                        fs::create_dir_all(parent)?;
+                    }
+                    fs::write(path, content)
+                        .with_context(|| format!("Failed to write {}", path.display()))?;
+                    state.env_script = Some(path.clone());
+                }
+                Action::RemoveEnv { path } => {
+                    remove_file_if_exists(path)?;
+                    state.env_script = None;
+                }
+                Action::WriteBlock { rc, content } => {
+                    fs::write(rc, content)
+                        .with_context(|| format!("Failed to write {}", rc.display()))?;
+                    state.blocks.insert(rc.clone());
+                }
+                Action::RemoveBlock { rc, content } => {
+                    fs::write(rc, content)
+                        .with_context(|| format!("Failed to write {}", rc.display()))?;
+                    state.blocks.remove(rc);
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Undoes everything recorded in `state`: removes links, restores backups,
+/// deletes the generated env script and strips managed blocks from rc files.
+pub fn revert(state: &mut State) -> Result<()> {
+    let links: Vec<(PathBuf, PathBuf)> = state
+        .links
+        .iter()
+        .map(|(link, source)| (link.clone(), source.clone()))
+        .collect();
+
+    for (link, source) in links {
+        info!("- {}", link.display());
+        remove_link(&link, &source)?;
+        restore_backup(&link, state)?;
+        state.links.remove(&link);
+    }
+
+    if let Some(path) = state.env_script.take() {
+        info!("- {}", path.display());
+        remove_file_if_exists(&path)?;
+    }
+
+    for rc in std::mem::take(&mut state.blocks) {
+        let Ok(current) = fs::read_to_string(&rc) else {
+            continue;
+        };
+        if let Some(content) = path::remove(&current) {
+            info!("- {} (dots block)", rc.display());
+            fs::write(&rc, content).with_context(|| format!("Failed to write {}", rc.display()))?;
+        }
+    }
+
+    Ok(())
+}
+
+fn create_link(link: &Path, source: &Path) -> Result<()> {
+    if !source.exists() {
+        bail!("Source does not exist: {}", source.display());
+    }
+
+    let base = link
+        .parent()
+        .map(Path::to_path_buf)
+        .context("Link has no parent directory")?;
+    let config = slink::Config::new(false, vec![source.to_path_buf()], base)
+        .with_conflict(slink::ConflictPolicy::FailFast);
+    let report = slink::process_links(&config)
+        .with_context(|| format!("Failed to link {}", link.display()))?;
+    if let Some(failed) = report.failed.first() {
+        bail!(
+            "Failed to link {}: {}",
+            link.display(),
+            failed.reason.as_deref().unwrap_or("unknown error")
+        );
+    }
+
+    Ok(())
+}
+
+/// Removes `link` only if it is still the symlink dots created.
+fn remove_link(link: &Path, source: &Path) -> Result<()> {
+    if points_to(link, source) {
+        remove_symlink(link)
+    } else {
+        warn!(
+            "Leaving {} alone, it no longer points at {}",
+            link.display(),
+            source.display()
+        );
+        Ok(())
+    }
+}
+
+fn restore_backup(link: &Path, state: &mut State) -> Result<()> {
+    let Some(backup) = state.backups.remove(link) else {
+        return Ok(());
+    };
+
+    if fs::symlink_metadata(link).is_ok() {
+        warn!("Cannot restore {}, the path is occupied", backup.display());
+        state.backups.insert(link.to_path_buf(), backup);
+        return Ok(());
+    }
+
+    debug!("Restoring {} from {}", link.display(), backup.display());
+    fs::rename(&backup, link).with_context(|| format!("Failed to restore {}", backup.display()))
+}
+
+fn points_to(link: &Path, source: &Path) -> bool {
+    fs::read_link(link).is_ok_and(|target| target == source)
+}
+
+fn backup_path(link: &Path) -> PathBuf {
+    let mut name = link.file_name().unwrap_or_default().to_os_string();
+    name.push(".dots-backup");
+    link.with_file_name(name)
+}
+
+fn remove_symlink(path: &Path) -> Result<()> {
+    fs::remove_file(path)
+        .or_else(|e| {
+            // Windows directory symlinks must be removed as directories
+            if path.is_dir() {
+                fs::remove_dir(path)
+            } else {
+                Err(e)
+            }
+        })
+        .with_context(|| format!("Failed to remove {}", path.display()))
+}
+
+fn remove_file_if_exists(path: &Path) -> Result<()> {
+    match fs::remove_file(path) {
+        Err(e) if e.kind() != io::ErrorKind::NotFound => {
+            Err(e).with_context(|| format!("Failed to remove {}", path.display()))
+        }
+        _ => Ok(()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A dotfile in `repo/` and an existing file where its link goes.
+    fn fixture(dir: &Path) -> (PathBuf, PathBuf) {
+        let (repo, home) = (dir.join("repo"), dir.join("home"));
+        fs::create_dir_all(&repo).unwrap();
+        fs::create_dir_all(&home).unwrap();
+        let source = repo.join(".bashrc");
+        let link = home.join(".bashrc");
+        fs::write(&source, "# managed\n").unwrap();
+        fs::write(&link, "# original\n").unwrap();
+        (link, source)
+    }
+
+    #[test]
+    fn test_apply_backs_up_existing_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let (link, source) = fixture(dir.path());
+        let plan = Plan {
+            actions: vec![Action::ReplaceLink {
+                link: link.clone(),
+                source: source.clone(),
+                backup: true,
+            }],
+            unchanged: 0,
+        };
+
+        let mut state = State::default();
+        plan.apply(&mut state).unwrap();
+
+        let backup = backup_path(&link);
+        assert!(points_to(&link, &source));
+        assert_eq!(fs::read_to_string(&backup).unwrap(), "# original\n");
+        assert_eq!(state.links.get(&link), Some(&source));
+        assert_eq!(state.backups.get(&link), Some(&backup));
+
+        // A second backup would clobber the first
+        fs::remove_file(&link).unwrap();
+        fs::write(&link, "# newer\n").unwrap();
+        assert!(plan.apply(&mut state).is_err());
+        assert_eq!(fs::read_to_string(&backup).unwrap(), "# original\n");
+    }
+
+    #[test]
+    fn test_revert_restores_backup_and_removes_link() {
+        let dir = tempfile::tempdir().unwrap();
+        let (link, source) = fixture(dir.path());
+        let plan = Plan {
+            actions: vec![Action::ReplaceLink {
+                link: link.clone(),
+                source: source.clone(),
+                backup: true,
+            }],
+            unchanged: 0,
+        };
+        let mut state = State::default();
+        plan.apply(&mut state).unwrap();
+
+        revert(&mut state).unwrap();
+
+        assert!(!fs::symlink_metadata(&link)
+            .unwrap()
+            .file_type()
+            .is_symlink());
+        assert_eq!(fs::read_to_string(&link).unwrap(), "# original\n");
+        assert!(!backup_path(&link).exists());
+        assert_eq!(fs::read_to_string(&source).unwrap(), "# managed\n");
+        assert!(state.links.is_empty());
+        assert!(state.backups.is_empty());
+    }
+
+    #[test]
+    fn test_revert_leaves_repointed_link() {
+        let dir = tempfile::tempdir().unwrap();
+        let (link, source) = fixture(dir.path());
+        fs::remove_file(&link).unwrap();
+        let mut state = State::default();
+        Plan {
+            actions: vec![Action::CreateLink {
+                link: link.clone(),
+                source: source.clone(),
+            }],
+            unchanged: 0,
+        }
+        .apply(&mut state)
+        .unwrap();
+
+        let elsewhere = dir.path().join("elsewhere");
+        fs::write(&elsewhere, "").unwrap();
+        remove_symlink(&link).unwrap();
+        slink_to(&elsewhere, &link);
+
+        revert(&mut state).unwrap();
+        assert!(points_to(&link, &elsewhere));
+        assert!(state.links.is_empty());
+    }
+
+    #[cfg(unix)]
+    fn slink_to(target: &Path, link: &Path) {
+        std::os::unix::fs::symlink(target, link).unwrap();
+    }
+
+    #[cfg(windows)]
+    fn slink_to(target: &Path, link: &Path) {
+        std::os::windows::fs::symlink_file(target, link).unwrap();
+    }
+}
@@ -0,0 +1,62 @@
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    fs,
+    path::{Path, PathBuf},
+};
+
+/// What the last `dots apply` changed, so it can be diffed against and reverted.
+#[derive(Debug, Clone, Default, PartialEq, Deserialize, Serialize)]
+pub struct State {
+    /// Profile the state was produced from.
+    pub profile: Option<PathBuf>,
+
+    /// Dotfiles checkout created by `dots bootstrap`, updated by `dots pull`.
+    pub repo: Option<PathBuf>,
+
+    /// Link path to the source it points at.
+    #[serde(default)]
+    pub links: BTreeMap<PathBuf, PathBuf>,
+
+    /// Original path to where it was moved before a link replaced it.
+    #[serde(default)]
+    pub backups: BTreeMap<PathBuf, PathBuf>,
+
+    /// Generated environment script, if one was written.
+    pub env_script: Option<PathBuf>,
+
+    /// Shell rc files holding a managed block.
+    #[serde(default)]
+    pub blocks: BTreeSet<PathBuf>,
+}
+
+impl State {
+    pub fn load(path: &Path) -> Result<Self> {
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+
+        let content = fs::read_to_string(path)
+            .with_context(|| format!("Failed to read state from {}", path.display()))?;
+        toml::from_str(&content).with_context(|| format!("Invalid state in {}", path.display()))
+    }
+
+    pub fn save(&self, path: &Path) -> Result<()> {
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)
+                .with_context(|| format!("Failed to create {}", parent.display()))?;
+        }
+
+        let content = toml::to_string_pretty(self).context("Failed to serialize state")?;
+        fs::write(path, content)
+            .with_context(|| format!("Failed to write state to {}", path.display()))
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.links.is_empty()
+            && self.backups.is_empty()
+            && self.env_script.is_none()
+            && self.blocks.is_empty()
+    }
+}
@@ -0,0 +1,124 @@
+use crate::data::shell::Shell;
+use serde::Deserialize;
+use std::collections::BTreeMap;
+
+/// An alias declared once and written into every shell's rc file.
+///
+/// ```toml
+/// [[snippets]]
+/// name = "ll"
+/// command = "ls -la"
+///
+/// [[snippets]]
+/// name = "mkcd"
+/// command = "mkdir -p"
+/// shells.bash = 'mkcd() { mkdir -p "$1" && cd "$1"; }'
+/// shells.fish = 'function mkcd; mkdir -p $argv[1]; and cd $argv[1]; end'
+/// ```
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct Snippet {
+    pub name: String,
+
+    /// What the snippet runs; arguments given to it are passed along.
+    pub command: String,
+
+    /// Definitions written as-is instead of rendering the template.
+    #[serde(default)]
+    pub shells: BTreeMap<Shell, String>,
+}
+
+impl Snippet {
+    /// The definition for `shell`, using its override if there is one.
+    /// Templates may use `{name}`, `{command}` and `{quoted}`, the command
+    /// quoted for that shell.
+    pub fn render(&self, shell: Shell, template: &str) -> String {
+        if let Some(definition) = self.shells.get(&shell) {
+            return definition.trim_end().to_string();
+        }
+
+        template
+            .replace("{name}", &self.name)
+            .replace("{quoted}", &quote(shell, &self.command))
+            .replace("{command}", &self.command)
+    }
+}
+
+/// Built-in template for an alias in `shell`.
+pub fn template(shell: Shell) -> &'static str {
+    match shell {
+        Shell::Bash | Shell::Zsh => "alias {name}={quoted}",
+        Shell::Fish => "alias {name} {quoted}",
+        Shell::Nushell => "alias {name} = {command}",
+        Shell::PowerShell => "function {name} { {command} @args }",
+    }
+}
+
+/// Renders every snippet for `shell`, one definition per line, preferring
+/// `templates` over the built-in ones.
+pub fn render(snippets: &[Snippet], templates: &BTreeMap<Shell, String>, shell: Shell) -> String {
+    let template = templates
+        .get(&shell)
+        .map_or(template(shell), String::as_str);
+
+    snippets
+        .iter()
+        .map(|snippet| format!("{}\n", snippet.render(shell, template)))
+        .collect()
+}
+
+fn quote(shell: Shell, value: &str) -> String {
+    match shell {
+        Shell::Bash | Shell::Zsh => format!("'{}'", value.replace('\'', r"'\''")),
+        Shell::Fish => format!("'{}'", value.replace('\\', r"\\").replace('\'', r"\'")),
+        Shell::Nushell => format!("\"{}\"", value.replace('\\', r"\\").replace('"', "\\\"")),
+        Shell::PowerShell => format!("'{}'", value.replace('\'', "''")),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_one_snippet_renders_per_shell() {
+        let snippets: Vec<Snippet> = toml::from_str::<BTreeMap<String, Vec<Snippet>>>(
+            r#"
+            [[snippets]]
+            name = "gs"
+            command = "git status --short"
+
+            [[snippets]]
+            name = "up"
+            command = "cd .."
+            shells.nu = "def up [] { cd .. }"
+            "#,
+        )
+        .unwrap()
+        .remove("snippets")
+        .unwrap();
+
+        let templates = BTreeMap::from([(Shell::Zsh, "abbr {name}={quoted}".to_string())]);
+        let render = |shell| render(&snippets, &templates, shell);
+
+        assert_eq!(
+            render(Shell::Bash),
+            "alias gs='git status --short'\nalias up='cd ..'\n"
+        );
+        assert_eq!(
+            render(Shell::Zsh).lines().next(),
+            Some("abbr gs='git status --short'")
+        );
+        assert_eq!(
+            render(Shell::Fish).lines().next(),
+            Some("alias gs 'git status --short'")
+        );
+        assert_eq!(
+            render(Shell::Nushell),
+            "alias gs = git status --short\ndef up [] { cd .. }\n"
+        );
+        assert_eq!(
+            render(Shell::PowerShell).lines().next(),
+            Some("function gs { git status --short @args }")
+        );
+    }
+}
@@ -0,0 +1,79 @@
+use crate::{compare, context};
+use clap::{Parser, Subcommand};
+use std::path::PathBuf;
+
+#[derive(Parser)]
+#[command(author, version, about, long_about = None)]
+pub struct Cli {
+    /// Model to use, defaults to the first one configured in the environment
+    #[arg(short, long, global = true)]
+    pub model: Option<String>,
+
+    /// Comma-separated models to try when the main one fails, overriding GENNA_FALLBACK_MODELS
+    #[arg(long, global = true, value_delimiter = ',')]
+    pub fallback: Vec<String>,
+
+    /// Seconds to wait for each reply, overriding GENNA_TIMEOUT
+    #[arg(long, global = true)]
+    pub timeout: Option<u64>,
+
+    #[command(subcommand)]
+    pub command: Option<Commands>,
+}
+
+#[derive(Debug, Subcommand)]
+pub enum Commands {
+    /// Ask a question, attaching piped stdin and any --file contents as context
+    Ask {
+        /// The question or instruction for the model
+        question: String,
+
+        /// File to attach as context (repeatable)
+        #[arg(short, long = "file", value_name = "PATH")]
+        files: Vec<PathBuf>,
+
+        /// Maximum bytes kept from each attachment
+        #[arg(long, default_value_t = context::MAX_BYTES)]
+        max_bytes: usize,
+
+        /// Size of each chunk an attachment is split into
+        #[arg(long, default_value_t = context::CHUNK_SIZE)]
+        chunk_size: usize,
+    },
+
+    /// Render a prompt template and send it to the model
+    Run {
+        /// Template name (looked up in the template directories) or path
+        template: String,
+
+        /// Template variable as key=value, or key=@file to read the value from a file
+        #[arg(short, long = "var", value_name = "KEY=VALUE")]
+        vars: Vec<String>,
+
+        /// Additional directory to search for templates
+        #[arg(short, long)]
+        templates: Option<PathBuf>,
+
+        /// Print the rendered prompt instead of sending it
+        #[arg(long)]
+        dry_run: bool,
+    },
+
+    /// Send one prompt to several models at once and show the replies together
+    Compare {
+        /// The prompt sent to every model
+        prompt: String,
+
+        /// Comma-separated models to ask, defaults to every configured one
+        #[arg(long, value_delimiter = ',')]
+        models: Vec<String>,
+
+        /// Print the replies as JSON instead of side by side
+        #[arg(long)]
+        json: bool,
+
+        /// Total width of the side-by-side view
+        #[arg(long, default_value_t = compare::WIDTH)]
+        width: usize,
+    },
+}
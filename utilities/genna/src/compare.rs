@@ -0,0 +1,161 @@
+use serde_json::{json, Value};
+use std::time::Duration;
+
+/// Default width of the side-by-side view.
+pub const WIDTH: usize = 120;
+
+/// One model's reply to a prompt sent to several models at once.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Answer {
+    pub model: String,
+    pub elapsed: Duration,
+    /// The response text, or the error message if the request failed.
+    pub result: Result<String, String>,
+}
+
+impl Answer {
+    pub fn new(
+        model: impl Into<String>,
+        elapsed: Duration,
+        result: Result<String, String>,
+    ) -> Self {
+        Self {
+            model: model.into(),
+            elapsed,
+            result,
+        }
+    }
+
+    fn header(&self) -> String {
+        format!("{} ({:.1}s)", self.model, self.elapsed.as_secs_f64())
+    }
+
+    fn body(&self) -> String {
+        match &self.result {
+            Ok(text) => text.clone(),
+            Err(error) => format!("error: {}", error),
+        }
+    }
+}
+
+/// Lays the answers out in columns that share `width`, separated by `|`.
+pub fn side_by_side(answers: &[Answer], width: usize) -> String {
+    if answers.is_empty() {
+        return String::new();
+    }
+
+    let gaps = (answers.len() - 1) * 3;
+    let column = (width.saturating_sub(gaps) / answers.len()).max(10);
+    let columns: Vec<Vec<String>> = answers
+        .iter()
+        .map(|answer| {
+            let mut lines = wrap(&answer.header(), column);
+            lines.push("-".repeat(column));
+            lines.extend(wrap(&answer.body(), column));
+            lines
+        })
+        .collect();
+    let height = columns.iter().map(Vec::len).max().unwrap_or(0);
+
+    (0..height)
+        .map(|row| {
+            columns
+                .iter()
+                .map(|lines| {
+                    let line = lines.get(row).map(String::as_str).unwrap_or("");
+                    format!("{:<column$}", line)
+                })
+                .collect::<Vec<_>>()
+                .join(" | ")
+                .trim_end()
+                .to_string()
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// The answers as a JSON array, in the order the models were given.
+pub fn to_json(answers: &[Answer]) -> Value {
+    answers
+        .iter()
+        .map(|answer| {
+            let mut value = json!({
+                "model": answer.model,
+                "elapsed_ms": answer.elapsed.as_millis() as u64,
+            });
+            match &answer.result {
+                Ok(text) => value["response"] = json!(text),
+                Err(error) => value["error"] = json!(error),
+            }
+            value
+        })
+        .collect()
+}
+
+/// Breaks `text` into lines of at most `width` characters, on spaces
+/// where possible, keeping blank lines.
+fn wrap(text: &str, width: usize) -> Vec<String> {
+    let mut lines = Vec::new();
+    for paragraph in text.lines() {
+        let mut line = String::new();
+        for word in paragraph.split_whitespace() {
+            let mut word = word;
+            while word.chars().count() > width {
+                if !line.is_empty() {
+                    lines.push(std::mem::take(&mut line));
+                }
+                let split = word
+                    .char_indices()
+                    .nth(width)
+                    .map_or(word.len(), |(i, _)| i);
+                lines.push(word[..split].to_string());
+                word = &word[split..];
+            }
+
+            let needed =
+                line.chars().count() + word.chars().count() + usize::from(!line.is_empty());
+            if needed > width && !line.is_empty() {
+                lines.push(std::mem::take(&mut line));
+            }
+            if !line.is_empty() {
+                line.push(' ');
+            }
+            line.push_str(word);
+        }
+        lines.push(line);
+    }
+    lines
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_side_by_side_pads_columns() {
+        let answers = [
+            Answer::new("a", Duration::from_millis(1200), Ok("one two three".into())),
+            Answer::new("b", Duration::from_millis(300), Err("rate limited".into())),
+        ];
+        let view = side_by_side(&answers, 33);
+        let lines: Vec<_> = view.lines().collect();
+
+        assert_eq!(lines[0], "a (1.2s)        | b (0.3s)");
+        assert_eq!(lines[2], "one two three   | error: rate");
+        assert_eq!(lines[3], "                | limited");
+    }
+
+    #[test]
+    fn test_json_keeps_errors_apart() {
+        let answers = [Answer::new(
+            "b",
+            Duration::from_millis(5),
+            Err("timeout".into()),
+        )];
+        let value = to_json(&answers);
+
+        assert_eq!(value[0]["model"], "b");
+        assert_eq!(value[0]["error"], "timeout");
+        assert!(value[0].get("response").is_none());
+    }
+}
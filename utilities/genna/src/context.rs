@@ -0,0 +1,155 @@
+use anyhow::{Context, Result};
+use std::{
+    fs,
+    io::{self, IsTerminal, Read},
+    path::Path,
+};
+
+/// Default cap on the bytes kept from a single attachment.
+pub const MAX_BYTES: usize = 64 * 1024;
+
+/// Default size of each chunk sent to the model.
+pub const CHUNK_SIZE: usize = 16 * 1024;
+
+/// Text attached to a prompt, either piped on stdin or read from a file.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Attachment {
+    pub name: String,
+    pub content: String,
+    pub truncated: bool,
+}
+
+impl Attachment {
+    pub fn new(name: impl Into<String>, content: impl Into<String>, max_bytes: usize) -> Self {
+        let mut content = content.into();
+        let truncated = content.len() > max_bytes;
+        if truncated {
+            content.truncate(floor_char_boundary(&content, max_bytes));
+        }
+
+        Self {
+            name: name.into(),
+            content,
+            truncated,
+        }
+    }
+
+    /// Reads stdin when it is piped, returning `None` on an interactive terminal.
+    pub fn from_stdin(max_bytes: usize) -> Result<Option<Self>> {
+        let mut stdin = io::stdin();
+        if stdin.is_terminal() {
+            return Ok(None);
+        }
+
+        let mut content = String::new();
+        stdin
+            .read_to_string(&mut content)
+            .context("Failed to read from stdin")?;
+        if content.trim().is_empty() {
+            return Ok(None);
+        }
+
+        Ok(Some(Self::new("stdin", content, max_bytes)))
+    }
+
+    pub fn from_file(path: &Path, max_bytes: usize) -> Result<Self> {
+        let content = fs::read_to_string(path)
+            .with_context(|| format!("Failed to read attachment {}", path.display()))?;
+
+        Ok(Self::new(path.display().to_string(), content, max_bytes))
+    }
+
+    /// Splits the content into labelled blocks no larger than `chunk_size`.
+    pub fn blocks(&self, chunk_size: usize) -> Vec<String> {
+        let chunks = chunk(&self.content, chunk_size);
+        let total = chunks.len();
+
+        chunks
+            .into_iter()
+            .enumerate()
+            .map(|(index, text)| {
+                let mut label = self.name.clone();
+                if total > 1 {
+                    label.push_str(&format!(" (part {}/{})", index + 1, total));
+                }
+                if self.truncated && index + 1 == total {
+                    label.push_str(" [truncated]");
+                }
+                format!("<{}>\n{}\n</{}>", label, text, self.name)
+            })
+            .collect()
+    }
+}
+
+/// Builds the user messages for a question: attachments first, question last.
+pub fn messages(question: &str, attachments: &[Attachment], chunk_size: usize) -> Vec<String> {
+    attachments
+        .iter()
+        .flat_map(|attachment| attachment.blocks(chunk_size))
+        .chain(std::iter::once(question.to_string()))
+        .collect()
+}
+
+/// Splits text into pieces of at most `size` bytes, preferring line breaks.
+pub fn chunk(text: &str, size: usize) -> Vec<&str> {
+    let size = size.max(1);
+    let mut chunks = Vec::new();
+    let mut rest = text;
+
+    while rest.len() > size {
+        let limit = floor_char_boundary(rest, size);
+        let split = match rest[..limit].rfind('\n') {
+            Some(newline) if newline > 0 => newline + 1,
+            _ if limit > 0 => limit,
+            _ => rest.chars().next().map_or(rest.len(), char::len_utf8),
+        };
+        let (head, tail) = rest.split_at(split);
+        chunks.push(head);
+        rest = tail;
+    }
+
+    if !rest.is_empty() || chunks.is_empty() {
+        chunks.push(rest);
+    }
+
+    chunks
+}
+
+fn floor_char_boundary(text: &str, index: usize) -> usize {
+    if index >= text.len() {
+        return text.len();
+    }
+    (0..=index)
+        .rev()
+        .find(|&i| text.is_char_boundary(i))
+        .unwrap_or(0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_chunk_prefers_line_breaks() {
+        let chunks = chunk("one\ntwo\nthree\n", 9);
+        assert_eq!(chunks, vec!["one\ntwo\n", "three\n"]);
+        assert_eq!(chunks.concat(), "one\ntwo\nthree\n");
+    }
+
+    #[test]
+    fn test_truncation_respects_char_boundaries() {
+        let attachment = Attachment::new("log", "ééé", 3);
+        assert!(attachment.truncated);
+        assert_eq!(attachment.content, "é");
+    }
+
+    #[test]
+    fn test_messages_put_question_last() {
+        let attachment = Attachment::new("stdin", "line\n".repeat(4), MAX_BYTES);
+        let messages = messages("explain this", &[attachment], 10);
+
+        assert_eq!(messages.len(), 3);
+        assert!(messages[0].starts_with("<stdin (part 1/2)>"));
+        assert_eq!(messages[2], "explain this");
+    }
+}
@@ -0,0 +1,10 @@
+pub mod cli;
+pub mod compare;
+pub mod context;
+pub mod models;
+pub mod policy;
+pub mod templates;
+
+pub use context::Attachment;
+pub use policy::Policy;
+pub use templates::Template;
@@ -1,3 +1,4 @@
+use clap::Parser;
 use dotenvy::dotenv;
 // use dotenv_vault::dotenv;
 use genai::{
@@ -8,26 +9,113 @@ use genai::{
     },
     Client, ServiceTarget,
 };
-use std::env::var;
-
-const MODELS: &[(&str, &str)] = &[
-    ("MODEL_TAG", "MODEL_KEY"),
-    ("OPENAI_MODEL", "OPENAI_API_KEY"),
-    ("ANTHROPIC_MODEL", "ANTHROPIC_API_KEY"),
-    ("GEMINI_MODEL", "GEMINI_API_KEY"),
-    ("COHERE_MODEL", "COHERE_API_KEY"),
-    ("XAI_MODEL", "XAI_API_KEY"),
-    ("GROQ_MODEL", "GROQ_API_KEY"),
-    ("OLLAMA_MODEL", "OLLAMA_API_KEY"),
-    // (MODEL_OLLAMA, ""),
-];
+use genna::{
+    cli::{Cli, Commands},
+    compare::{self, Answer},
+    context,
+    models::{self, MODELS},
+    templates, Attachment, Policy, Template,
+};
+use std::{
+    collections::HashMap,
+    env::var,
+    time::{Duration, Instant},
+};
+use tokio::task::JoinSet;
 
 #[tokio::main]
 async fn main() -> Result<(), Box<dyn std::error::Error>> {
     dotenv()?; // Load variables from .env
     logline::init(); // Initialize logging
 
+    let cli = Cli::parse();
     let client = Client::default();
+    let mut policy = Policy::from_env();
+    if !cli.fallback.is_empty() {
+        policy = policy.with_fallbacks(cli.fallback.clone());
+    }
+    if let Some(secs) = cli.timeout {
+        policy = policy.with_timeout(Duration::from_secs(secs));
+    }
+
+    match cli.command {
+        Some(Commands::Ask {
+            question,
+            files,
+            max_bytes,
+            chunk_size,
+        }) => {
+            let mut attachments: Vec<Attachment> =
+                Attachment::from_stdin(max_bytes)?.into_iter().collect();
+            for path in &files {
+                attachments.push(Attachment::from_file(path, max_bytes)?);
+            }
+            for attachment in attachments.iter().filter(|a| a.truncated) {
+                logline::warn!("Truncated {} to {} bytes", attachment.name, max_bytes);
+            }
+
+            let messages = context::messages(&question, &attachments, chunk_size)
+                .into_iter()
+                .map(ChatMessage::user)
+                .collect();
+            let model = models::resolve(cli.model.as_deref())?;
+            return send(&client, &policy, &model, ChatRequest::new(messages)).await;
+        }
+        Some(Commands::Run {
+            template,
+            vars,
+            templates: extra_dir,
+            dry_run,
+        }) => {
+            let mut dirs = templates::directories();
+            if let Some(dir) = extra_dir {
+                dirs.insert(0, dir);
+            }
+
+            let template = Template::load(&template, &dirs)?;
+            let vars = vars
+                .iter()
+                .map(|arg| templates::parse_var(arg))
+                .collect::<anyhow::Result<HashMap<_, _>>>()?;
+            let prompt = template.render(&vars)?;
+
+            if dry_run {
+                println!("{}", prompt);
+                return Ok(());
+            }
+
+            let model = models::resolve(cli.model.as_deref())?;
+            logline::debug!("Running template '{}' with {}", template.name, model);
+            let request = ChatRequest::new(vec![ChatMessage::user(prompt)]);
+            return send(&client, &policy, &model, request).await;
+        }
+        Some(Commands::Compare {
+            prompt,
+            models,
+            json,
+            width,
+        }) => {
+            let models = match models.is_empty() {
+                true => models::configured(),
+                false => models,
+            };
+            if models.is_empty() {
+                models::resolve(None)?;
+            }
+
+            let answers = compare(&client, &policy, &models, &prompt).await;
+            match json {
+                true => println!(
+                    "{}",
+                    serde_json::to_string_pretty(&compare::to_json(&answers))?
+                ),
+                false => println!("{}", compare::side_by_side(&answers, width)),
+            }
+            return Ok(());
+        }
+        None => {}
+    }
+
     let request = ChatRequest::new(vec![
         ChatMessage::system("Answer the following question!"),
         ChatMessage::user("What is the meaning of life?"),
@@ -69,6 +157,61 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     Ok(())
 }
 
+async fn send(
+    client: &Client,
+    policy: &Policy,
+    model: &str,
+    request: ChatRequest,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let (answered, response) = policy.exec(client, model, request).await?;
+    if answered != model {
+        logline::info!("{} failed, answered by {}", model, answered);
+    }
+    println!("{}", response.content_text_as_str().unwrap_or_default());
+    Ok(())
+}
+
+/// Sends `prompt` to every model concurrently, keeping the given order.
+/// A model that doesn't reply within the policy's timeout is reported as
+/// timed out without holding up the others.
+async fn compare(client: &Client, policy: &Policy, models: &[String], prompt: &str) -> Vec<Answer> {
+    let mut tasks = JoinSet::new();
+    for (index, model) in models.iter().enumerate() {
+        let client = client.clone();
+        let model = model.clone();
+        let timeout = policy.timeout;
+        let request = ChatRequest::new(vec![ChatMessage::user(prompt)]);
+        tasks.spawn(async move {
+            let started = Instant::now();
+            let result = match tokio::time::timeout(
+                timeout,
+                client.exec_chat(&model, request, None),
+            )
+            .await
+            {
+                Ok(Ok(response)) => Ok(response.content_text_into_string().unwrap_or_default()),
+                Ok(Err(e)) => Err(e.to_string()),
+                Err(_) => {
+                    logline::warn!("{} timed out after {:?}", model, timeout);
+                    Err(format!("timed out after {:?}", timeout))
+                }
+            };
+            logline::debug!("{} answered in {:?}", model, started.elapsed());
+            (index, Answer::new(model, started.elapsed(), result))
+        });
+    }
+
+    let mut answers = Vec::with_capacity(models.len());
+    while let Some(joined) = tasks.join_next().await {
+        match joined {
+            Ok(answer) => answers.push(answer),
+            Err(e) => logline::error!("Comparison task failed: {}", e),
+        }
+    }
+    answers.sort_by_key(|(index, _)| *index);
+    answers.into_iter().map(|(_, answer)| answer).collect()
+}
+
 fn get_env_var(key: &str) -> Result<String, Box<dyn std::error::Error>> {
     var(key).map_err(|_| format!("{} not set", key).into())
 }
@@ -0,0 +1,46 @@
+use anyhow::{bail, Result};
+use std::env::var;
+
+/// Pairs of (model, api key) environment variables, in order of preference.
+pub const MODELS: &[(&str, &str)] = &[
+    ("MODEL_TAG", "MODEL_KEY"),
+    ("OPENAI_MODEL", "OPENAI_API_KEY"),
+    ("ANTHROPIC_MODEL", "ANTHROPIC_API_KEY"),
+    ("GEMINI_MODEL", "GEMINI_API_KEY"),
+    ("COHERE_MODEL", "COHERE_API_KEY"),
+    ("XAI_MODEL", "XAI_API_KEY"),
+    ("GROQ_MODEL", "GROQ_API_KEY"),
+    ("OLLAMA_MODEL", "OLLAMA_API_KEY"),
+    // (MODEL_OLLAMA, ""),
+];
+
+/// Returns every model whose model and key variables are both set.
+pub fn configured() -> Vec<String> {
+    MODELS
+        .iter()
+        .filter_map(|(model_var, key_var)| {
+            let model = var(model_var).ok()?;
+            var(key_var).ok()?;
+            Some(model)
+        })
+        .collect()
+}
+
+/// Picks the requested model, or the first configured one.
+pub fn resolve(requested: Option<&str>) -> Result<String> {
+    if let Some(model) = requested {
+        return Ok(model.to_string());
+    }
+
+    match configured().into_iter().next() {
+        Some(model) => Ok(model),
+        None => bail!(
+            "No model configured, set one of: {}",
+            MODELS
+                .iter()
+                .map(|(model_var, _)| *model_var)
+                .collect::<Vec<_>>()
+                .join(", ")
+        ),
+    }
+}
@@ -0,0 +1,231 @@
+use erks::{
+    anyhow::{anyhow, Result},
+    Reporter, ThisError,
+};
+use genai::{
+    chat::{ChatRequest, ChatResponse},
+    Client,
+};
+use std::{env::var, time::Duration};
+
+/// Default time allowed for one request.
+pub const TIMEOUT: Duration = Duration::from_secs(60);
+
+/// Default number of retries on the same model before falling back.
+pub const RETRIES: u32 = 2;
+
+/// Why a request to a model failed, which decides whether it is retried.
+#[derive(Debug, Clone, PartialEq, Eq, ThisError)]
+pub enum Failure {
+    #[error("timed out: {0}")]
+    Timeout(String),
+    #[error("rate limited: {0}")]
+    RateLimited(String),
+    #[error("not authorized: {0}")]
+    Auth(String),
+    #[error("network error: {0}")]
+    Network(String),
+    #[error("{0}")]
+    Other(String),
+}
+
+impl Failure {
+    /// Sorts a provider error by its message, since providers report
+    /// status codes in their own wording.
+    pub fn classify(message: impl Into<String>) -> Self {
+        let message = message.into();
+        let lower = message.to_lowercase();
+        let mentions = |needles: &[&str]| needles.iter().any(|n| lower.contains(n));
+
+        if mentions(&[
+            "429",
+            "rate limit",
+            "rate_limit",
+            "too many requests",
+            "overloaded",
+        ]) {
+            Self::RateLimited(message)
+        } else if mentions(&[
+            "401",
+            "403",
+            "unauthorized",
+            "forbidden",
+            "api key",
+            "api_key",
+        ]) {
+            Self::Auth(message)
+        } else if mentions(&["timed out", "timeout"]) {
+            Self::Timeout(message)
+        } else if mentions(&[
+            "connect",
+            "dns",
+            "network",
+            "reset by peer",
+            "502",
+            "503",
+            "504",
+        ]) {
+            Self::Network(message)
+        } else {
+            Self::Other(message)
+        }
+    }
+
+    /// Whether trying the same model again could succeed.
+    pub fn is_transient(&self) -> bool {
+        matches!(
+            self,
+            Self::Timeout(_) | Self::RateLimited(_) | Self::Network(_)
+        )
+    }
+}
+
+/// Retries, timeouts and fallback models applied to every request.
+///
+/// Set through the environment with `GENNA_TIMEOUT` (seconds),
+/// `GENNA_RETRIES` and `GENNA_FALLBACK_MODELS` (comma-separated).
+#[derive(Debug, Clone, PartialEq)]
+pub struct Policy {
+    pub timeout: Duration,
+    pub retries: u32,
+    /// Delay before the first retry, doubled on each one after.
+    pub backoff: Duration,
+    /// Models tried in order once the primary one gives up.
+    pub fallbacks: Vec<String>,
+}
+
+impl Default for Policy {
+    fn default() -> Self {
+        Self {
+            timeout: TIMEOUT,
+            retries: RETRIES,
+            backoff: Duration::from_millis(500),
+            fallbacks: Vec::new(),
+        }
+    }
+}
+
+impl Policy {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn from_env() -> Self {
+        let mut policy = Self::default();
+        if let Some(secs) = var("GENNA_TIMEOUT").ok().and_then(|v| v.parse().ok()) {
+            policy.timeout = Duration::from_secs(secs);
+        }
+        if let Some(retries) = var("GENNA_RETRIES").ok().and_then(|v| v.parse().ok()) {
+            policy.retries = retries;
+        }
+        if let Ok(models) = var("GENNA_FALLBACK_MODELS") {
+            policy.fallbacks = split(&models);
+        }
+        policy
+    }
+
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    pub fn with_retries(mut self, retries: u32) -> Self {
+        self.retries = retries;
+        self
+    }
+
+    pub fn with_fallbacks(mut self, fallbacks: Vec<String>) -> Self {
+        self.fallbacks = fallbacks;
+        self
+    }
+
+    /// `primary` followed by the fallbacks, each listed once.
+    pub fn models(&self, primary: &str) -> Vec<String> {
+        let mut models = vec![primary.to_string()];
+        for model in &self.fallbacks {
+            if !models.contains(model) {
+                models.push(model.clone());
+            }
+        }
+        models
+    }
+
+    /// Sends `request` to `primary`, retrying transient failures and then
+    /// moving down the fallbacks. Returns the model that answered.
+    ///
+    /// Every failed attempt is logged; if all of them fail the error lists
+    /// each one.
+    pub async fn exec(
+        &self,
+        client: &Client,
+        primary: &str,
+        request: ChatRequest,
+    ) -> Result<(String, ChatResponse)> {
+        let mut reporter = Reporter::new();
+
+        for model in self.models(primary) {
+            let mut delay = self.backoff;
+            for attempt in 0..=self.retries {
+                let result = tokio::time::timeout(
+                    self.timeout,
+                    client.exec_chat(&model, request.clone(), None),
+                )
+                .await;
+                let failure = match result {
+                    Ok(Ok(response)) => return Ok((model, response)),
+                    Ok(Err(e)) => Failure::classify(e.to_string()),
+                    Err(_) => Failure::Timeout(format!("no reply within {:?}", self.timeout)),
+                };
+
+                logline::warn!("{} attempt {} failed: {}", model, attempt + 1, failure);
+                reporter.warn(format!("{} attempt {}: {}", model, attempt + 1, failure));
+                if !failure.is_transient() || attempt == self.retries {
+                    break;
+                }
+                tokio::time::sleep(delay).await;
+                delay *= 2;
+            }
+        }
+
+        reporter.error(format!(
+            "No model answered, tried {}",
+            self.models(primary).join(", ")
+        ));
+        Err(anyhow!("{}", reporter.render()))
+    }
+}
+
+/// Splits a comma-separated list, dropping blanks.
+pub fn split(models: &str) -> Vec<String> {
+    models
+        .split(',')
+        .map(str::trim)
+        .filter(|model| !model.is_empty())
+        .map(String::from)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_classify_decides_retries() {
+        let limited = Failure::classify("Response failed with status 429 Too Many Requests");
+        assert!(matches!(limited, Failure::RateLimited(_)));
+        assert!(limited.is_transient());
+
+        let auth = Failure::classify("status 401: invalid api key");
+        assert!(matches!(auth, Failure::Auth(_)));
+        assert!(!auth.is_transient());
+
+        assert!(Failure::classify("error trying to connect: dns error").is_transient());
+        assert!(!Failure::classify("model not found").is_transient());
+    }
+
+    #[test]
+    fn test_models_put_primary_first_once() {
+        let policy = Policy::new().with_fallbacks(split("claude-3-5, gpt-4o,,gemini"));
+        assert_eq!(policy.models("gpt-4o"), ["gpt-4o", "claude-3-5", "gemini"]);
+    }
+}
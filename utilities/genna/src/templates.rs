@@ -0,0 +1,217 @@
+use anyhow::{bail, Context, Result};
+use directories::BaseDirs;
+use std::{
+    collections::HashMap,
+    fs,
+    path::{Path, PathBuf},
+};
+
+/// Extensions tried, in order, when a template is looked up by name.
+const EXTENSIONS: &[&str] = &["", "txt", "md", "prompt"];
+
+/// A prompt stored on disk with `{{var}}` placeholders.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Template {
+    pub name: String,
+    pub body: String,
+}
+
+impl Template {
+    pub fn new(name: impl Into<String>, body: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            body: body.into(),
+        }
+    }
+
+    /// Finds a template by path, or by name in the first matching directory.
+    pub fn load(name: &str, dirs: &[PathBuf]) -> Result<Self> {
+        let direct = Path::new(name);
+        if direct.is_file() {
+            return Self::from_file(direct);
+        }
+
+        for dir in dirs {
+            for ext in EXTENSIONS {
+                let path = match *ext {
+                    "" => dir.join(name),
+                    ext => dir.join(format!("{}.{}", name, ext)),
+                };
+                if path.is_file() {
+                    return Self::from_file(&path);
+                }
+            }
+        }
+
+        bail!(
+            "Template '{}' not found in: {}",
+            name,
+            dirs.iter()
+                .map(|dir| dir.display().to_string())
+                .collect::<Vec<_>>()
+                .join(", ")
+        )
+    }
+
+    pub fn from_file(path: &Path) -> Result<Self> {
+        let body = fs::read_to_string(path)
+            .with_context(|| format!("Failed to read template {}", path.display()))?;
+        let name = path
+            .file_stem()
+            .map(|stem| stem.to_string_lossy().into_owned())
+            .unwrap_or_default();
+
+        Ok(Self::new(name, body))
+    }
+
+    /// Lists placeholder names in order of first appearance.
+    pub fn placeholders(&self) -> Vec<String> {
+        let mut names: Vec<String> = Vec::new();
+        for (_, name, _) in Placeholders::new(&self.body) {
+            if !names.iter().any(|known| known == name) {
+                names.push(name.to_string());
+            }
+        }
+        names
+    }
+
+    /// Substitutes every placeholder, failing if any variable is missing.
+    pub fn render(&self, vars: &HashMap<String, String>) -> Result<String> {
+        let missing: Vec<String> = self
+            .placeholders()
+            .into_iter()
+            .filter(|name| !vars.contains_key(name))
+            .collect();
+        if !missing.is_empty() {
+            bail!(
+                "Template '{}' is missing variables: {}",
+                self.name,
+                missing.join(", ")
+            );
+        }
+
+        let mut rendered = String::with_capacity(self.body.len());
+        let mut cursor = 0;
+        for (start, name, end) in Placeholders::new(&self.body) {
+            rendered.push_str(&self.body[cursor..start]);
+            rendered.push_str(&vars[name]);
+            cursor = end;
+        }
+        rendered.push_str(&self.body[cursor..]);
+
+        Ok(rendered)
+    }
+}
+
+/// Iterates `{{ name }}` occurrences as (start, trimmed name, end) offsets.
+struct Placeholders<'a> {
+    body: &'a str,
+    cursor: usize,
+}
+
+impl<'a> Placeholders<'a> {
+    fn new(body: &'a str) -> Self {
+        Self { body, cursor: 0 }
+    }
+}
+
+impl<'a> Iterator for Placeholders<'a> {
+    type Item = (usize, &'a str, usize);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            let start = self.cursor + self.body[self.cursor..].find("{{")?;
+            let close = start + 2 + self.body[start + 2..].find("}}")?;
+            let end = close + 2;
+            self.cursor = end;
+
+            let name = self.body[start + 2..close].trim();
+            if is_identifier(name) {
+                return Some((start, name, end));
+            }
+        }
+    }
+}
+
+fn is_identifier(name: &str) -> bool {
+    !name.is_empty()
+        && name
+            .chars()
+            .all(|c| c.is_alphanumeric() || c == '_' || c == '-' || c == '.')
+}
+
+/// Parses a `key=value` argument, reading the value from a file for `key=@path`.
+pub fn parse_var(arg: &str) -> Result<(String, String)> {
+    let Some((key, value)) = arg.split_once('=') else {
+        bail!("Invalid variable '{}', expected key=value", arg);
+    };
+
+    let key = key.trim();
+    if !is_identifier(key) {
+        bail!("Invalid variable name '{}'", key);
+    }
+
+    let value = match value.strip_prefix('@') {
+        Some(path) => fs::read_to_string(path)
+            .with_context(|| format!("Failed to read value of '{}' from {}", key, path))?,
+        None => value.to_string(),
+    };
+
+    Ok((key.to_string(), value))
+}
+
+/// Default search path: `GENNA_TEMPLATES`, `./templates`, then the user config dir.
+pub fn directories() -> Vec<PathBuf> {
+    let mut dirs = Vec::new();
+
+    if let Ok(paths) = std::env::var("GENNA_TEMPLATES") {
+        dirs.extend(std::env::split_paths(&paths));
+    }
+
+    dirs.push(PathBuf::from("templates"));
+
+    if let Some(base_dirs) = BaseDirs::new() {
+        dirs.push(base_dirs.config_dir().join("genna").join("templates"));
+    }
+
+    dirs
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn vars(pairs: &[(&str, &str)]) -> HashMap<String, String> {
+        pairs
+            .iter()
+            .map(|(key, value)| (key.to_string(), value.to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn test_render_substitutes_placeholders() {
+        let template = Template::new("greet", "Hello {{name}}, {{ name }} likes {{topic}}.");
+        let rendered = template
+            .render(&vars(&[("name", "Ada"), ("topic", "engines")]))
+            .unwrap();
+
+        assert_eq!(rendered, "Hello Ada, Ada likes engines.");
+        assert_eq!(template.placeholders(), vec!["name", "topic"]);
+    }
+
+    #[test]
+    fn test_render_reports_missing_variables() {
+        let template = Template::new("summarize", "Summarize {{text}} in {{words}} words");
+        let error = template.render(&vars(&[("text", "...")])).unwrap_err();
+
+        assert!(error.to_string().contains("words"));
+    }
+
+    #[test]
+    fn test_non_identifier_braces_are_left_alone() {
+        let template = Template::new("code", "fn main() {{ }} {{x}}");
+        let rendered = template.render(&vars(&[("x", "1")])).unwrap();
+
+        assert_eq!(rendered, "fn main() {{ }} 1");
+    }
+}
@@ -0,0 +1,41 @@
+use clap::Parser;
+use std::path::PathBuf;
+
+#[derive(Parser)]
+#[command(author, version, about, long_about = None)]
+pub struct Cli {
+    /// TOML file declaring the fields to extract
+    pub rules: Option<PathBuf>,
+
+    /// Page to scrape, overriding the url in the rules file
+    #[arg(short, long)]
+    pub url: Option<String>,
+
+    /// How many links deep to follow from the start page
+    #[arg(short, long, default_value_t = 0)]
+    pub depth: usize,
+
+    /// Stop after fetching this many pages
+    #[arg(long, default_value_t = 100)]
+    pub max_pages: usize,
+
+    /// Minimum delay between requests to the same host, in milliseconds
+    #[arg(long, default_value_t = 500)]
+    pub delay: u64,
+
+    /// Follow links to other hosts
+    #[arg(long)]
+    pub external: bool,
+
+    /// Fetch pages even when robots.txt disallows them
+    #[arg(long)]
+    pub ignore_robots: bool,
+
+    /// Always download pages instead of reusing cached responses
+    #[arg(long)]
+    pub no_cache: bool,
+
+    /// Seconds a cached page is reused before asking the server whether it changed
+    #[arg(long, default_value_t = 0)]
+    pub cache_ttl: u64,
+}
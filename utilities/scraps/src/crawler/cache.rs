@@ -0,0 +1,152 @@
+use anyhow::{Context, Result};
+use directories::BaseDirs;
+use reqwest::Url;
+use std::{
+    fs,
+    path::PathBuf,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+/// A stored response and the validators used to revalidate it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Entry {
+    pub body: String,
+    pub etag: Option<String>,
+    pub last_modified: Option<String>,
+    /// When the response was fetched or last confirmed unchanged.
+    pub checked: SystemTime,
+}
+
+impl Entry {
+    pub fn new(body: String, etag: Option<String>, last_modified: Option<String>) -> Self {
+        Self {
+            body,
+            etag,
+            last_modified,
+            checked: SystemTime::now(),
+        }
+    }
+
+    pub fn age(&self) -> Duration {
+        self.checked.elapsed().unwrap_or_default()
+    }
+}
+
+/// Responses kept on disk, keyed by URL, so unchanged pages aren't
+/// downloaded again.
+#[derive(Debug, Clone)]
+pub struct Cache {
+    pub dir: PathBuf,
+    /// How long an entry is used without asking the server; after that it
+    /// is revalidated with `If-None-Match` / `If-Modified-Since`.
+    pub ttl: Duration,
+}
+
+impl Cache {
+    pub fn new(dir: impl Into<PathBuf>) -> Self {
+        Self {
+            dir: dir.into(),
+            ttl: Duration::ZERO,
+        }
+    }
+
+    /// Uses `scraps` under the user's cache directory.
+    pub fn user() -> Result<Self> {
+        let dirs = BaseDirs::new().context("Failed to locate the cache directory")?;
+        Ok(Self::new(dirs.cache_dir().join("scraps")))
+    }
+
+    pub fn with_ttl(mut self, ttl: Duration) -> Self {
+        self.ttl = ttl;
+        self
+    }
+
+    pub fn is_fresh(&self, entry: &Entry) -> bool {
+        entry.age() < self.ttl
+    }
+
+    pub fn get(&self, url: &Url) -> Option<Entry> {
+        let (meta, body) = self.paths(url);
+        let meta = fs::read_to_string(meta).ok()?;
+        let body = fs::read_to_string(body).ok()?;
+
+        let mut lines = meta.lines();
+        if lines.next()? != url.as_str() {
+            return None;
+        }
+        let optional = |line: Option<&str>| line.filter(|l| !l.is_empty()).map(String::from);
+        let etag = optional(lines.next());
+        let last_modified = optional(lines.next());
+        let checked = UNIX_EPOCH + Duration::from_secs(lines.next()?.parse().ok()?);
+
+        Some(Entry {
+            body,
+            etag,
+            last_modified,
+            checked,
+        })
+    }
+
+    pub fn put(&self, url: &Url, entry: &Entry) -> Result<()> {
+        fs::create_dir_all(&self.dir)
+            .with_context(|| format!("Failed to create cache {}", self.dir.display()))?;
+
+        let (meta, body) = self.paths(url);
+        let checked = entry
+            .checked
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_secs();
+        let header = format!(
+            "{}\n{}\n{}\n{}\n",
+            url,
+            entry.etag.as_deref().unwrap_or_default(),
+            entry.last_modified.as_deref().unwrap_or_default(),
+            checked
+        );
+
+        fs::write(&body, &entry.body)
+            .with_context(|| format!("Failed to write {}", body.display()))?;
+        fs::write(&meta, header).with_context(|| format!("Failed to write {}", meta.display()))
+    }
+
+    fn paths(&self, url: &Url) -> (PathBuf, PathBuf) {
+        let key = format!("{:016x}", fnv1a(url.as_str()));
+        (
+            self.dir.join(format!("{}.meta", key)),
+            self.dir.join(format!("{}.html", key)),
+        )
+    }
+}
+
+fn fnv1a(text: &str) -> u64 {
+    text.bytes().fold(0xcbf29ce484222325, |hash, byte| {
+        (hash ^ u64::from(byte)).wrapping_mul(0x100000001b3)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_round_trips_entries() {
+        let dir = std::env::temp_dir().join(format!("scraps-cache-{}", std::process::id()));
+        let cache = Cache::new(&dir).with_ttl(Duration::from_secs(60));
+        let url = Url::parse("https://example.com/shop/").unwrap();
+
+        assert!(cache.get(&url).is_none());
+
+        let entry = Entry::new("<html></html>".into(), Some("\"abc\"".into()), None);
+        cache.put(&url, &entry).unwrap();
+        let stored = cache.get(&url).unwrap();
+
+        assert_eq!(stored.body, entry.body);
+        assert_eq!(stored.etag.as_deref(), Some("\"abc\""));
+        assert_eq!(stored.last_modified, None);
+        assert!(cache.is_fresh(&stored));
+        assert!(!Cache::new(&dir).is_fresh(&stored));
+
+        fs::remove_dir_all(dir).unwrap();
+    }
+}
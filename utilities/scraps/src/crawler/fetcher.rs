@@ -0,0 +1,113 @@
+use super::{Cache, Entry};
+use anyhow::{Context, Result};
+use erks::http::HttpError;
+use logline::{debug, warn};
+use reqwest::{
+    header::{HeaderMap, ETAG, IF_MODIFIED_SINCE, IF_NONE_MATCH, LAST_MODIFIED},
+    Client, StatusCode, Url,
+};
+use std::future::Future;
+
+/// Retrieves the HTML of a page for the crawler.
+pub trait Fetcher {
+    fn fetch(&self, url: &Url) -> impl Future<Output = Result<String>> + Send;
+}
+
+/// Fetches pages with plain HTTP requests, without running scripts.
+#[derive(Debug, Clone)]
+pub struct HttpFetcher {
+    client: Client,
+    cache: Option<Cache>,
+}
+
+impl HttpFetcher {
+    pub fn new(client: Client) -> Self {
+        Self {
+            client,
+            cache: None,
+        }
+    }
+
+    /// Serves fresh pages from `cache` and revalidates stale ones with
+    /// conditional requests.
+    pub fn with_cache(mut self, cache: Cache) -> Self {
+        self.cache = Some(cache);
+        self
+    }
+
+    async fn fetch_cached(&self, cache: &Cache, url: &Url) -> Result<String> {
+        let cached = cache.get(url);
+        if let Some(entry) = cached.as_ref().filter(|entry| cache.is_fresh(entry)) {
+            debug!("Cached: {}", url);
+            return Ok(entry.body.clone());
+        }
+
+        let mut request = self.client.get(url.clone());
+        if let Some(entry) = &cached {
+            if let Some(etag) = &entry.etag {
+                request = request.header(IF_NONE_MATCH, etag);
+            }
+            if let Some(modified) = &entry.last_modified {
+                request = request.header(IF_MODIFIED_SINCE, modified);
+            }
+        }
+
+        let response = request
+            .send()
+            .await
+            .with_context(|| format!("Failed to send URL request for: '{}'", url))?;
+
+        let entry = match (response.status(), cached) {
+            (StatusCode::NOT_MODIFIED, Some(entry)) => {
+                debug!("Not modified: {}", url);
+                Entry::new(entry.body, entry.etag, entry.last_modified)
+            }
+            _ => {
+                let response = HttpError::check(response).await?;
+                let headers = response.headers().clone();
+                let body = response
+                    .text()
+                    .await
+                    .with_context(|| format!("Failed to read response text from: '{}'", url))?;
+                Entry::new(
+                    body,
+                    header(&headers, ETAG),
+                    header(&headers, LAST_MODIFIED),
+                )
+            }
+        };
+
+        if let Err(e) = cache.put(url, &entry) {
+            warn!("{:#}", e);
+        }
+        Ok(entry.body)
+    }
+}
+
+impl Fetcher for HttpFetcher {
+    async fn fetch(&self, url: &Url) -> Result<String> {
+        if let Some(cache) = &self.cache {
+            return self.fetch_cached(cache, url).await;
+        }
+
+        let response = self
+            .client
+            .get(url.clone())
+            .send()
+            .await
+            .with_context(|| format!("Failed to send URL request for: '{}'", url))?;
+        let response = HttpError::check(response).await?;
+
+        response
+            .text()
+            .await
+            .with_context(|| format!("Failed to read response text from: '{}'", url))
+    }
+}
+
+fn header(headers: &HeaderMap, name: reqwest::header::HeaderName) -> Option<String> {
+    headers
+        .get(name)
+        .and_then(|value| value.to_str().ok())
+        .map(String::from)
+}
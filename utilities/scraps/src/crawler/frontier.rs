@@ -0,0 +1,52 @@
+use reqwest::Url;
+use std::collections::{HashSet, VecDeque};
+
+/// Breadth-first queue of URLs that never yields the same page twice.
+#[derive(Debug, Default)]
+pub struct Frontier {
+    queue: VecDeque<(Url, usize)>,
+    seen: HashSet<String>,
+}
+
+impl Frontier {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Queues a URL at the given depth, returning false if it was already seen.
+    pub fn push(&mut self, mut url: Url, depth: usize) -> bool {
+        url.set_fragment(None);
+        if !self.seen.insert(url.as_str().to_string()) {
+            return false;
+        }
+        self.queue.push_back((url, depth));
+        true
+    }
+
+    pub fn pop(&mut self) -> Option<(Url, usize)> {
+        self.queue.pop_front()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.queue.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_deduplicates_ignoring_fragments() {
+        let mut frontier = Frontier::new();
+        let url = Url::parse("https://example.com/shop/").unwrap();
+
+        assert!(frontier.push(url.clone(), 0));
+        assert!(!frontier.push(url.join("#reviews").unwrap(), 1));
+        assert!(frontier.push(url.join("page/2/").unwrap(), 1));
+
+        assert_eq!(frontier.pop().map(|(_, depth)| depth), Some(0));
+        assert_eq!(frontier.pop().map(|(_, depth)| depth), Some(1));
+        assert!(frontier.is_empty());
+    }
+}
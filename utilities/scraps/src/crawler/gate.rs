@@ -0,0 +1,89 @@
+use super::{RateLimiter, Robots};
+use logline::{debug, warn};
+use reqwest::{Client, Url};
+use std::{collections::HashMap, time::Duration};
+
+/// The robots.txt check and per-host rate limit every request goes
+/// through, whether it comes from a crawl or from following detail links.
+#[derive(Debug)]
+pub struct Gate {
+    client: Client,
+    user_agent: String,
+    respect_robots: bool,
+    limiter: RateLimiter,
+    robots: HashMap<String, Robots>,
+}
+
+impl Gate {
+    pub fn new(client: Client, user_agent: impl Into<String>, delay: Duration) -> Self {
+        Self {
+            client,
+            user_agent: user_agent.into(),
+            respect_robots: true,
+            limiter: RateLimiter::new(delay),
+            robots: HashMap::new(),
+        }
+    }
+
+    pub fn ignore_robots(mut self) -> Self {
+        self.respect_robots = false;
+        self
+    }
+
+    /// Uses `robots` for `host` instead of requesting its robots.txt.
+    pub fn with_robots(mut self, host: &str, robots: Robots) -> Self {
+        self.insert_robots(host, robots);
+        self
+    }
+
+    /// Waits until `url` may be requested, or returns `false` straight
+    /// away when robots.txt disallows it.
+    pub async fn admit(&mut self, url: &Url) -> bool {
+        let host = url.host_str().unwrap_or_default().to_string();
+
+        if self.respect_robots {
+            if !self.robots.contains_key(&host) {
+                let robots = self.fetch_robots(url).await;
+                self.insert_robots(&host, robots);
+            }
+            if !self.robots[&host].is_allowed(url.path()) {
+                debug!("Disallowed by robots.txt: {}", url);
+                return false;
+            }
+        }
+
+        self.limiter.wait(&host).await;
+        true
+    }
+
+    fn insert_robots(&mut self, host: &str, robots: Robots) {
+        if let Some(delay) = robots.crawl_delay {
+            self.limiter.set_delay(host, delay);
+        }
+        self.robots.insert(host.to_string(), robots);
+    }
+
+    async fn fetch_robots(&self, url: &Url) -> Robots {
+        let Ok(robots_url) = url.join("/robots.txt") else {
+            return Robots::allow_all();
+        };
+
+        match self.client.get(robots_url.clone()).send().await {
+            Ok(response) if response.status().is_success() => match response.text().await {
+                Ok(content) => Robots::parse(&content, &self.user_agent),
+                Err(e) => {
+                    warn!("Failed to read {}: {}", robots_url, e);
+                    Robots::allow_all()
+                }
+            },
+            Ok(response) => {
+                debug!("No robots.txt at {} ({})", robots_url, response.status());
+                Robots::allow_all()
+            }
+            Err(e) => {
+                warn!("Failed to fetch {}: {}", robots_url, e);
+                Robots::allow_all()
+            }
+        }
+    }
+}
@@ -0,0 +1,46 @@
+use std::{
+    collections::HashMap,
+    time::{Duration, Instant},
+};
+
+/// Spaces out requests to the same host by at least a fixed delay.
+#[derive(Debug, Default)]
+pub struct RateLimiter {
+    delay: Duration,
+    overrides: HashMap<String, Duration>,
+    last: HashMap<String, Instant>,
+}
+
+impl RateLimiter {
+    pub fn new(delay: Duration) -> Self {
+        Self {
+            delay,
+            ..Self::default()
+        }
+    }
+
+    /// Uses a different delay for one host, e.g. from its robots.txt Crawl-delay.
+    pub fn set_delay(&mut self, host: &str, delay: Duration) {
+        self.overrides.insert(host.to_string(), delay);
+    }
+
+    pub fn delay(&self, host: &str) -> Duration {
+        self.overrides
+            .get(host)
+            .copied()
+            .unwrap_or(self.delay)
+            .max(self.delay)
+    }
+
+    /// Sleeps until the host may be contacted again, then records the request.
+    pub async fn wait(&mut self, host: &str) {
+        let delay = self.delay(host);
+        if let Some(last) = self.last.get(host) {
+            let elapsed = last.elapsed();
+            if elapsed < delay {
+                tokio::time::sleep(delay - elapsed).await;
+            }
+        }
+        self.last.insert(host.to_string(), Instant::now());
+    }
+}
@@ -0,0 +1,242 @@
+mod cache;
+mod fetcher;
+mod frontier;
+mod gate;
+mod limiter;
+#[cfg(feature = "render")]
+mod render;
+mod robots;
+
+pub use cache::{Cache, Entry};
+pub use fetcher::{Fetcher, HttpFetcher};
+pub use frontier::Frontier;
+pub use gate::Gate;
+pub use limiter::RateLimiter;
+#[cfg(feature = "render")]
+pub use render::ChromeFetcher;
+pub use robots::Robots;
+
+use anyhow::{anyhow, Context, Result};
+use logline::{debug, info, warn};
+use reqwest::{Client, Url};
+use scraper::{Html, Selector};
+use std::{collections::HashMap, time::Duration};
+
+/// Default identity sent with every request and matched against robots.txt.
+pub const USER_AGENT: &str = concat!("scraps/", env!("CARGO_PKG_VERSION"));
+
+/// A fetched page handed to the crawl callback.
+pub struct Page {
+    pub url: Url,
+    pub depth: usize,
+    pub document: Html,
+}
+
+/// Totals reported once a crawl finishes.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct Stats {
+    pub fetched: usize,
+    pub failed: usize,
+    pub disallowed: usize,
+}
+
+/// A polite breadth-first crawler starting from a single URL.
+#[derive(Debug, Clone)]
+pub struct Crawler {
+    pub start: Url,
+    pub max_depth: usize,
+    pub max_pages: usize,
+    pub delay: Duration,
+    pub same_host: bool,
+    pub respect_robots: bool,
+    pub user_agent: String,
+    /// Where plain HTTP responses are kept between runs.
+    pub cache: Option<Cache>,
+    /// Selector for a "next page" link, followed at the same depth so
+    /// paginated listings don't use up `max_depth`.
+    pub next: Option<String>,
+}
+
+impl Crawler {
+    /// Fetches only the start page until depth or page limits are raised.
+    pub fn new(start: Url) -> Self {
+        Self {
+            start,
+            max_depth: 0,
+            max_pages: 100,
+            delay: Duration::from_millis(500),
+            same_host: true,
+            respect_robots: true,
+            user_agent: USER_AGENT.to_string(),
+            cache: None,
+            next: None,
+        }
+    }
+
+    pub fn with_max_depth(mut self, depth: usize) -> Self {
+        self.max_depth = depth;
+        self
+    }
+
+    pub fn with_max_pages(mut self, pages: usize) -> Self {
+        self.max_pages = pages;
+        self
+    }
+
+    pub fn with_delay(mut self, delay: Duration) -> Self {
+        self.delay = delay;
+        self
+    }
+
+    pub fn with_user_agent(mut self, user_agent: impl Into<String>) -> Self {
+        self.user_agent = user_agent.into();
+        self
+    }
+
+    pub fn with_cache(mut self, cache: Cache) -> Self {
+        self.cache = Some(cache);
+        self
+    }
+
+    pub fn with_next(mut self, selector: impl Into<String>) -> Self {
+        self.next = Some(selector.into());
+        self
+    }
+
+    pub fn allow_external(mut self) -> Self {
+        self.same_host = false;
+        self
+    }
+
+    pub fn ignore_robots(mut self) -> Self {
+        self.respect_robots = false;
+        self
+    }
+
+    /// Crawls breadth-first over plain HTTP, calling `visit` for every page
+    /// fetched.
+    pub async fn crawl<F>(&self, visit: F) -> Result<Stats>
+    where
+        F: FnMut(&Page) -> Result<()>,
+    {
+        self.crawl_with(&self.http_fetcher()?, visit).await
+    }
+
+    /// The plain HTTP fetcher [`crawl`](Self::crawl) uses, sharing its
+    /// user agent and cache.
+    pub fn http_fetcher(&self) -> Result<HttpFetcher> {
+        let mut fetcher = HttpFetcher::new(self.client()?);
+        if let Some(cache) = &self.cache {
+            fetcher = fetcher.with_cache(cache.clone());
+        }
+        Ok(fetcher)
+    }
+
+    /// The robots.txt and rate-limit checks this crawler applies, for
+    /// requests made outside a crawl such as detail pages.
+    pub fn gate(&self) -> Result<Gate> {
+        let gate = Gate::new(self.client()?, &self.user_agent, self.delay);
+        Ok(match self.respect_robots {
+            true => gate,
+            false => gate.ignore_robots(),
+        })
+    }
+
+    /// Like [`crawl`](Self::crawl), but reads pages through `fetcher`.
+    /// robots.txt is still requested over plain HTTP.
+    pub async fn crawl_with<T, F>(&self, fetcher: &T, mut visit: F) -> Result<Stats>
+    where
+        T: Fetcher,
+        F: FnMut(&Page) -> Result<()>,
+    {
+        let mut gate = self.gate()?;
+        let links = Selector::parse("a[href]").expect("static selector is valid");
+        let next = self
+            .next
+            .as_deref()
+            .map(|next| {
+                Selector::parse(next).map_err(|e| anyhow!("Invalid selector '{}': {}", next, e))
+            })
+            .transpose()?;
+
+        let mut frontier = Frontier::new();
+        let mut stats = Stats::default();
+
+        frontier.push(self.start.clone(), 0);
+
+        while let Some((url, depth)) = frontier.pop() {
+            if stats.fetched >= self.max_pages {
+                info!("Reached the limit of {} pages", self.max_pages);
+                break;
+            }
+
+            if !gate.admit(&url).await {
+                stats.disallowed += 1;
+                continue;
+            }
+
+            let content = match fetcher.fetch(&url).await {
+                Ok(content) => content,
+                Err(e) => {
+                    warn!("{:#}", e);
+                    stats.failed += 1;
+                    continue;
+                }
+            };
+            stats.fetched += 1;
+
+            let page = Page {
+                document: Html::parse_document(&content),
+                url,
+                depth,
+            };
+
+            if let Some(next) = &next {
+                let href = page
+                    .document
+                    .select(next)
+                    .find_map(|a| a.value().attr("href"));
+                if let Some(url) = href.and_then(|href| self.follow(&page.url, href)) {
+                    debug!("Next page: {}", url);
+                    frontier.push(url, depth);
+                }
+            }
+
+            if depth < self.max_depth {
+                for href in page
+                    .document
+                    .select(&links)
+                    .filter_map(|a| a.value().attr("href"))
+                {
+                    if let Some(next) = self.follow(&page.url, href) {
+                        frontier.push(next, depth + 1);
+                    }
+                }
+            }
+
+            visit(&page)?;
+        }
+
+        debug!("{:?}", stats);
+        Ok(stats)
+    }
+
+    fn client(&self) -> Result<Client> {
+        Client::builder()
+            .user_agent(&self.user_agent)
+            .build()
+            .context("Failed to build HTTP client")
+    }
+
+    /// Resolves a link against its page, keeping only crawlable targets.
+    fn follow(&self, base: &Url, href: &str) -> Option<Url> {
+        let url = base.join(href).ok()?;
+        if !matches!(url.scheme(), "http" | "https") {
+            return None;
+        }
+        if self.same_host && url.host_str() != self.start.host_str() {
+            return None;
+        }
+        Some(url)
+    }
+}
@@ -0,0 +1,137 @@
+use super::Fetcher;
+use anyhow::{bail, Context, Result};
+use reqwest::Url;
+use std::{env, path::PathBuf, time::Duration};
+use tokio::process::Command;
+
+/// Browsers tried, in order, when `SCRAPS_CHROME` is not set.
+const BROWSERS: &[&str] = &[
+    "chromium",
+    "chromium-browser",
+    "google-chrome",
+    "google-chrome-stable",
+    "chrome",
+    "msedge",
+];
+
+/// Fetches pages through headless Chromium so scripts run before the DOM is
+/// read, for pages that build their content in JavaScript.
+///
+/// Each page is one `--dump-dom` run of the browser rather than a session
+/// driven over WebDriver or CDP through fantoccini or chromiumoxide: the
+/// crawler only needs the final HTML, so the `render` feature stops there
+/// and stays free of driver crates and a long-lived browser. Pages that
+/// need clicks or logins before their content appears are out of scope.
+#[derive(Debug, Clone)]
+pub struct ChromeFetcher {
+    pub binary: PathBuf,
+    pub user_agent: String,
+    /// How long scripts may run before the DOM is captured.
+    pub budget: Duration,
+    pub timeout: Duration,
+}
+
+impl ChromeFetcher {
+    /// Uses `SCRAPS_CHROME`, or the first Chromium-based browser on PATH.
+    pub fn new(user_agent: impl Into<String>) -> Result<Self> {
+        let binary = match env::var_os("SCRAPS_CHROME") {
+            Some(binary) => PathBuf::from(binary),
+            None => find().context(
+                "No Chromium-based browser found on PATH, set SCRAPS_CHROME to its path",
+            )?,
+        };
+
+        Ok(Self {
+            binary,
+            user_agent: user_agent.into(),
+            budget: Duration::from_secs(5),
+            timeout: Duration::from_secs(30),
+        })
+    }
+
+    pub fn with_budget(mut self, budget: Duration) -> Self {
+        self.budget = budget;
+        self
+    }
+
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout;
+        self
+    }
+}
+
+impl Fetcher for ChromeFetcher {
+    async fn fetch(&self, url: &Url) -> Result<String> {
+        let run = Command::new(&self.binary)
+            .args([
+                "--headless=new",
+                "--disable-gpu",
+                "--no-first-run",
+                "--mute-audio",
+                "--dump-dom",
+            ])
+            .arg(format!("--virtual-time-budget={}", self.budget.as_millis()))
+            .arg(format!("--user-agent={}", self.user_agent))
+            .arg(url.as_str())
+            .kill_on_drop(true)
+            .output();
+
+        let output = tokio::time::timeout(self.timeout, run)
+            .await
+            .with_context(|| format!("Rendering timed out for: '{}'", url))?
+            .with_context(|| format!("Failed to start {}", self.binary.display()))?;
+
+        if !output.status.success() {
+            bail!(
+                "Rendering failed for: '{}': {}",
+                url,
+                String::from_utf8_lossy(&output.stderr).trim()
+            );
+        }
+        Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+    }
+}
+
+fn find() -> Option<PathBuf> {
+    let path = env::var_os("PATH")?;
+    env::split_paths(&path).find_map(|dir| {
+        BROWSERS.iter().find_map(|name| {
+            let candidate = dir.join(name);
+            let exe = candidate.with_extension(env::consts::EXE_EXTENSION);
+            [candidate, exe].into_iter().find(|path| path.is_file())
+        })
+    })
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_missing_browser_names_binary() {
+        let fetcher = ChromeFetcher {
+            binary: PathBuf::from("/nonexistent/chromium"),
+            user_agent: "scraps".to_string(),
+            budget: Duration::ZERO,
+            timeout: Duration::from_secs(5),
+        };
+        let url = Url::parse("https://example.com/").unwrap();
+        let err = fetcher.fetch(&url).await.unwrap_err();
+        assert_eq!(err.to_string(), "Failed to start /nonexistent/chromium");
+    }
+
+    #[tokio::test]
+    async fn test_failed_browser_is_an_error() {
+        let fetcher = ChromeFetcher {
+            binary: PathBuf::from("false"),
+            user_agent: "scraps".to_string(),
+            budget: Duration::ZERO,
+            timeout: Duration::from_secs(5),
+        };
+        let url = Url::parse("https://example.com/").unwrap();
+        let err = fetcher.fetch(&url).await.unwrap_err();
+        assert!(err
+            .to_string()
+            .starts_with("Rendering failed for: 'https://example.com/'"));
+    }
+}
@@ -0,0 +1,163 @@
+use std::time::Duration;
+
+/// The subset of robots.txt that applies to one user agent.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Robots {
+    rules: Vec<Rule>,
+    pub crawl_delay: Option<Duration>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+struct Rule {
+    allow: bool,
+    pattern: String,
+}
+
+impl Robots {
+    /// Allows everything, used when a site has no robots.txt.
+    pub fn allow_all() -> Self {
+        Self::default()
+    }
+
+    /// Parses robots.txt, keeping the group for `user_agent` or falling back to `*`.
+    pub fn parse(content: &str, user_agent: &str) -> Self {
+        let user_agent = user_agent.to_lowercase();
+        let mut specific: Option<Self> = None;
+        let mut wildcard: Option<Self> = None;
+
+        let mut agents: Vec<String> = Vec::new();
+        let mut group = Self::default();
+        let mut in_rules = false;
+
+        let mut finish = |agents: &[String], group: &mut Self| {
+            let group = std::mem::take(group);
+            if agents
+                .iter()
+                .any(|agent| user_agent.contains(agent.as_str()))
+            {
+                specific.get_or_insert_with(Self::default).merge(group);
+            } else if agents.iter().any(|agent| agent == "*") {
+                wildcard.get_or_insert_with(Self::default).merge(group);
+            }
+        };
+
+        for line in content.lines() {
+            let line = line.split('#').next().unwrap_or_default().trim();
+            let Some((key, value)) = line.split_once(':') else {
+                continue;
+            };
+            let key = key.trim().to_lowercase();
+            let value = value.trim();
+
+            match key.as_str() {
+                "user-agent" => {
+                    if in_rules {
+                        finish(&agents, &mut group);
+                        agents.clear();
+                        in_rules = false;
+                    }
+                    agents.push(value.to_lowercase());
+                }
+                "allow" | "disallow" => {
+                    in_rules = true;
+                    if !value.is_empty() {
+                        group.rules.push(Rule {
+                            allow: key == "allow",
+                            pattern: value.to_string(),
+                        });
+                    }
+                }
+                "crawl-delay" => {
+                    in_rules = true;
+                    group.crawl_delay = value.parse::<f64>().ok().map(Duration::from_secs_f64);
+                }
+                _ => {}
+            }
+        }
+        finish(&agents, &mut group);
+
+        specific.or(wildcard).unwrap_or_default()
+    }
+
+    /// Applies the most specific (longest) matching rule; ties favour allow.
+    pub fn is_allowed(&self, path: &str) -> bool {
+        self.rules
+            .iter()
+            .filter(|rule| matches(&rule.pattern, path))
+            .max_by_key(|rule| (rule.pattern.len(), rule.allow))
+            .is_none_or(|rule| rule.allow)
+    }
+
+    fn merge(&mut self, other: Self) {
+        self.rules.extend(other.rules);
+        self.crawl_delay = self.crawl_delay.or(other.crawl_delay);
+    }
+}
+
+/// Matches a robots.txt path pattern supporting `*` wildcards and a `$` anchor.
+fn matches(pattern: &str, path: &str) -> bool {
+    let (pattern, anchored) = match pattern.strip_suffix('$') {
+        Some(pattern) => (pattern, true),
+        None => (pattern, false),
+    };
+
+    let mut parts = pattern.split('*');
+    let Some(first) = parts.next() else {
+        return true;
+    };
+    let Some(mut rest) = path.strip_prefix(first) else {
+        return false;
+    };
+
+    let parts: Vec<&str> = parts.collect();
+    for (index, part) in parts.iter().enumerate() {
+        let last = index + 1 == parts.len();
+        if last && anchored {
+            return rest.ends_with(part);
+        }
+        match rest.find(part) {
+            Some(position) => rest = &rest[position + part.len()..],
+            None => return false,
+        }
+    }
+
+    !anchored || rest.is_empty()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ROBOTS: &str = "
+        User-agent: *
+        Disallow: /cart
+        Disallow: /*?orderby=
+        Allow: /cart/public
+        Crawl-delay: 2
+
+        User-agent: scraps
+        Disallow: /private/
+        Disallow: /*.pdf$
+    ";
+
+    #[test]
+    fn test_wildcard_group() {
+        let robots = Robots::parse(ROBOTS, "Mozilla/5.0");
+
+        assert!(!robots.is_allowed("/cart"));
+        assert!(robots.is_allowed("/cart/public/item"));
+        assert!(!robots.is_allowed("/shop/?orderby=price"));
+        assert!(robots.is_allowed("/shop/"));
+        assert_eq!(robots.crawl_delay, Some(Duration::from_secs(2)));
+    }
+
+    #[test]
+    fn test_specific_group_wins() {
+        let robots = Robots::parse(ROBOTS, "scraps/0.1");
+
+        assert!(robots.is_allowed("/cart"));
+        assert!(!robots.is_allowed("/private/notes"));
+        assert!(!robots.is_allowed("/docs/manual.pdf"));
+        assert!(robots.is_allowed("/docs/manual.pdf.html"));
+    }
+}
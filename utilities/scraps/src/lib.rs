@@ -1,4 +1,7 @@
+pub mod cli;
+pub mod crawler;
 mod data;
+pub mod rules;
 mod utils;
 
 pub use utils::*;
@@ -1,18 +1,146 @@
-use scraps::*;
+use clap::Parser;
+use reqwest::Url;
+use scraps::{
+    cli::Cli,
+    crawler::{Cache, Crawler, Page, Stats},
+    rules::{Detail, Rules},
+    *,
+};
+use serde_json::Value;
+use std::time::Duration;
 
 #[tokio::main]
 async fn main() -> anyhow::Result<()> {
     logline::init_with_level(INFO);
 
-    let target_url = "https://scrapeme.live/shop/";
-    let content = get::html_content(target_url).await?;
-    let document = get::html_document(content)?;
+    let cli = Cli::parse();
+    let rules = cli.rules.as_deref().map(Rules::load).transpose()?;
 
-    // let products = data::products(document);
-    // scrape::products(document);
-    // warn!("{:#?}", &document);
-    warn!("{:#?}", "pop");
-    info!("{:#?}", "pop");
+    let target_url = cli
+        .url
+        .as_deref()
+        .or(rules.as_ref().and_then(|rules| rules.url.as_deref()))
+        .unwrap_or("https://scrapeme.live/shop/");
+
+    let mut crawler = Crawler::new(Url::parse(target_url)?)
+        .with_max_depth(cli.depth)
+        .with_max_pages(cli.max_pages)
+        .with_delay(Duration::from_millis(cli.delay));
+    if cli.external {
+        crawler = crawler.allow_external();
+    }
+    if cli.ignore_robots {
+        crawler = crawler.ignore_robots();
+    }
+    if !cli.no_cache {
+        crawler = crawler.with_cache(Cache::user()?.with_ttl(Duration::from_secs(cli.cache_ttl)));
+    }
+    if let Some(next) = rules.as_ref().and_then(|rules| rules.next.clone()) {
+        crawler = crawler.with_next(next);
+    }
+
+    let mut output = Vec::new();
+    let mut links = Vec::new();
+    let visit = |page: &Page| {
+        info!("{} (depth {})", page.url, page.depth);
+        if let Some(rules) = &rules {
+            match rules.extract(&page.document)? {
+                Value::Array(records) => output.extend(records),
+                record => output.push(record),
+            }
+            links.extend(rules.links(&page.document, &page.url)?);
+        }
+        Ok(())
+    };
+    let rendered = rules.as_ref().is_some_and(|rules| rules.render);
+    let stats = match rendered {
+        true => render(&crawler, visit).await?,
+        false => crawler.crawl(visit).await?,
+    };
+
+    info!(
+        "Fetched {} pages ({} failed, {} disallowed)",
+        stats.fetched, stats.failed, stats.disallowed
+    );
+
+    if let Some(detail) = rules.as_ref().and_then(|rules| rules.detail.as_ref()) {
+        let failed = match rendered {
+            true => render_details(&crawler, detail, &mut output, &links).await?,
+            false => {
+                let fetcher = crawler.http_fetcher()?;
+                let mut gate = crawler.gate()?;
+                detail
+                    .follow(&fetcher, &mut gate, &mut output, &links)
+                    .await
+            }
+        };
+        info!(
+            "Followed {} detail pages ({} failed)",
+            links.iter().flatten().count(),
+            failed
+        );
+    }
+
+    if rules.is_some() {
+        println!("{}", serde_json::to_string_pretty(&output)?);
+    }
 
     Ok(())
 }
+
+#[cfg(feature = "render")]
+async fn render<F>(crawler: &Crawler, visit: F) -> anyhow::Result<Stats>
+where
+    F: FnMut(&Page) -> anyhow::Result<()>,
+{
+    let fetcher = scraps::crawler::ChromeFetcher::new(&crawler.user_agent)?;
+    crawler.crawl_with(&fetcher, visit).await
+}
+
+#[cfg(feature = "render")]
+async fn render_details(
+    crawler: &Crawler,
+    detail: &Detail,
+    records: &mut [Value],
+    links: &[Option<Url>],
+) -> anyhow::Result<usize> {
+    let fetcher = scraps::crawler::ChromeFetcher::new(&crawler.user_agent)?;
+    let mut gate = crawler.gate()?;
+    Ok(detail.follow(&fetcher, &mut gate, records, links).await)
+}
+
+#[cfg(not(feature = "render"))]
+async fn render<F>(_crawler: &Crawler, _visit: F) -> anyhow::Result<Stats>
+where
+    F: FnMut(&Page) -> anyhow::Result<()>,
+{
+    anyhow::bail!("These rules need rendering, but scraps was built without the render feature")
+}
+
+#[cfg(not(feature = "render"))]
+async fn render_details(
+    _crawler: &Crawler,
+    _detail: &Detail,
+    _records: &mut [Value],
+    _links: &[Option<Url>],
+) -> anyhow::Result<usize> {
+    anyhow::bail!("These rules need rendering, but scraps was built without the render feature")
+}
+
+#[cfg(all(test, not(feature = "render")))]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_render_needs_feature() {
+        let crawler = Crawler::new(Url::parse("https://example.com/").unwrap());
+        let err = render(&crawler, |_| Ok(())).await.unwrap_err();
+        assert!(err.to_string().contains("without the render feature"));
+
+        let detail: Detail = serde_json::from_str(r#"{"link": "a"}"#).unwrap();
+        let err = render_details(&crawler, &detail, &mut [], &[])
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("without the render feature"));
+    }
+}
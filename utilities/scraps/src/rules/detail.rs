@@ -0,0 +1,176 @@
+use super::{record, validate_fields, Field};
+use crate::crawler::{Fetcher, Gate};
+use anyhow::{bail, Context, Result};
+use logline::{debug, warn};
+use reqwest::Url;
+use scraper::Html;
+use serde::Deserialize;
+use serde_json::{Map, Value};
+use tokio::task::JoinSet;
+
+/// Default number of detail pages fetched at once.
+pub const CONCURRENCY: usize = 4;
+
+/// Fields read from the page each listed item links to, merged into that
+/// item's record.
+///
+/// ```toml
+/// [detail]
+/// link = "a.woocommerce-LoopProduct-link"
+/// concurrency = 2
+///
+/// [[detail.fields]]
+/// name = "description"
+/// selector = "div.woocommerce-product-details__short-description"
+/// ```
+#[derive(Debug, Clone, Deserialize)]
+pub struct Detail {
+    /// Selector, scoped to the item, for the link whose `href` is followed.
+    pub link: String,
+
+    /// Most detail pages requested at the same time.
+    #[serde(default = "concurrency")]
+    pub concurrency: usize,
+
+    /// Values with the same name as a listing field replace it.
+    #[serde(default)]
+    pub fields: Vec<Field>,
+}
+
+fn concurrency() -> usize {
+    CONCURRENCY
+}
+
+impl Detail {
+    pub fn validate(&self) -> Result<()> {
+        if self.concurrency == 0 {
+            bail!("Detail concurrency must be at least 1");
+        }
+        super::selector(&self.link)?;
+        validate_fields(&self.fields)
+    }
+
+    pub fn extract(&self, document: &Html) -> Result<Map<String, Value>> {
+        record(&self.fields, document.root_element())
+    }
+
+    /// Fetches the page behind each link and merges its fields into the
+    /// record at the same index, keeping at most `concurrency` requests in
+    /// flight. Every request passes `gate` first, so robots.txt and the
+    /// per-host delay apply as in a crawl. Pages that fail are logged and
+    /// skipped; returns how many.
+    pub async fn follow<T>(
+        &self,
+        fetcher: &T,
+        gate: &mut Gate,
+        records: &mut [Value],
+        links: &[Option<Url>],
+    ) -> usize
+    where
+        T: Fetcher + Clone + Send + Sync + 'static,
+    {
+        let mut pending = links
+            .iter()
+            .enumerate()
+            .filter_map(|(index, link)| link.clone().map(|url| (index, url)));
+        let mut tasks = JoinSet::new();
+        let mut failed = 0;
+
+        loop {
+            while tasks.len() < self.concurrency {
+                let Some((index, url)) = pending.next() else {
+                    break;
+                };
+                if !gate.admit(&url).await {
+                    continue;
+                }
+                let fetcher = fetcher.clone();
+                let detail = self.clone();
+                tasks.spawn(async move {
+                    debug!("Detail: {}", url);
+                    let result = match fetcher.fetch(&url).await {
+                        Ok(content) => detail.extract(&Html::parse_document(&content)),
+                        Err(e) => Err(e),
+                    };
+                    (
+                        index,
+                        result.with_context(|| format!("Detail page {}", url)),
+                    )
+                });
+            }
+
+            let Some(joined) = tasks.join_next().await else {
+                break;
+            };
+            match joined {
+                Ok((index, Ok(fields))) => {
+                    if let Some(Value::Object(record)) = records.get_mut(index) {
+                        record.extend(fields);
+                    }
+                }
+                Ok((_, Err(e))) => {
+                    warn!("{:#}", e);
+                    failed += 1;
+                }
+                Err(e) => {
+                    warn!("Detail task failed: {}", e);
+                    failed += 1;
+                }
+            }
+        }
+
+        failed
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::crawler::Robots;
+    use reqwest::Client;
+    use std::{
+        sync::{Arc, Mutex},
+        time::Duration,
+    };
+
+    /// Serves the same page for every URL and remembers what was asked for.
+    #[derive(Clone, Default)]
+    struct Recorder(Arc<Mutex<Vec<String>>>);
+
+    impl Fetcher for Recorder {
+        async fn fetch(&self, url: &Url) -> Result<String> {
+            self.0.lock().unwrap().push(url.path().to_string());
+            Ok("<p class=\"price\">5</p>".to_string())
+        }
+    }
+
+    #[tokio::test]
+    async fn test_follow_skips_disallowed_links() {
+        let detail: Detail = toml::from_str(
+            r#"
+            link = "a"
+
+            [[fields]]
+            name = "price"
+            selector = "p.price"
+            "#,
+        )
+        .unwrap();
+        let robots = Robots::parse("User-agent: *\nDisallow: /private", "scraps");
+        let mut gate =
+            Gate::new(Client::new(), "scraps", Duration::ZERO).with_robots("shop.test", robots);
+
+        let url = |path: &str| Url::parse("https://shop.test").unwrap().join(path).ok();
+        let links = [url("/public"), url("/private/item"), None];
+        let mut records = vec![serde_json::json!({}); 3];
+        let fetcher = Recorder::default();
+
+        let failed = detail
+            .follow(&fetcher, &mut gate, &mut records, &links)
+            .await;
+        assert_eq!(failed, 0);
+        assert_eq!(*fetcher.0.lock().unwrap(), ["/public"]);
+        assert_eq!(records[0], serde_json::json!({ "price": "5" }));
+        assert_eq!(records[1], serde_json::json!({}));
+    }
+}
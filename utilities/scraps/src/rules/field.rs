@@ -0,0 +1,42 @@
+use serde::Deserialize;
+
+/// A single value extracted from each matched element.
+#[derive(Debug, Clone, Deserialize)]
+pub struct Field {
+    pub name: String,
+    pub selector: String,
+
+    /// What to read from the matched element, defaults to its text.
+    #[serde(default)]
+    pub attr: Attr,
+
+    /// Collect every match into an array instead of the first one.
+    #[serde(default)]
+    pub multiple: bool,
+
+    /// Fail the extraction when nothing matches.
+    #[serde(default)]
+    pub required: bool,
+}
+
+/// The part of an element a field reads.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize)]
+#[serde(from = "String")]
+pub enum Attr {
+    #[default]
+    Text,
+    Html,
+    InnerHtml,
+    Attribute(String),
+}
+
+impl From<String> for Attr {
+    fn from(value: String) -> Self {
+        match value.as_str() {
+            "text" => Self::Text,
+            "html" => Self::Html,
+            "inner_html" => Self::InnerHtml,
+            _ => Self::Attribute(value),
+        }
+    }
+}
@@ -0,0 +1,287 @@
+mod detail;
+mod field;
+
+pub use detail::{Detail, CONCURRENCY};
+pub use field::{Attr, Field};
+
+use anyhow::{anyhow, bail, Context, Result};
+use logline::debug;
+use reqwest::Url;
+use scraper::{ElementRef, Html, Selector};
+use serde::Deserialize;
+use serde_json::{Map, Value};
+use std::{fs, path::Path};
+
+/// A declarative set of extraction rules, usually loaded from TOML.
+///
+/// ```toml
+/// url = "https://scrapeme.live/shop/"
+/// item = "li.product"
+///
+/// [[fields]]
+/// name = "title"
+/// selector = "h2.woocommerce-loop-product__title"
+///
+/// [[fields]]
+/// name = "link"
+/// selector = "a"
+/// attr = "href"
+/// ```
+///
+/// Listings spread over several pages set `next`, and `[detail]` pulls
+/// more fields from the page each item links to.
+#[derive(Debug, Clone, Deserialize)]
+pub struct Rules {
+    pub name: Option<String>,
+
+    /// Page to fetch when no URL is given on the command line.
+    pub url: Option<String>,
+
+    /// Selector scoping each record; without it the whole page is one record.
+    pub item: Option<String>,
+
+    /// Load pages in headless Chromium so scripts run first. Needs the
+    /// `render` feature.
+    #[serde(default)]
+    pub render: bool,
+
+    /// Selector for the link to the next page of a listing, followed
+    /// whatever the crawl depth.
+    pub next: Option<String>,
+
+    #[serde(default)]
+    pub fields: Vec<Field>,
+
+    pub detail: Option<Detail>,
+}
+
+impl Rules {
+    pub fn from_toml(content: &str) -> Result<Self> {
+        let rules: Self = toml::from_str(content).context("Failed to parse scraping rules")?;
+        rules.validate()?;
+        Ok(rules)
+    }
+
+    pub fn load(path: &Path) -> Result<Self> {
+        let content = fs::read_to_string(path)
+            .with_context(|| format!("Failed to read rules from {}", path.display()))?;
+        Self::from_toml(&content).with_context(|| format!("Invalid rules in {}", path.display()))
+    }
+
+    /// Checks that every selector parses and field names are unique.
+    pub fn validate(&self) -> Result<()> {
+        let detail_fields = self.detail.as_ref().map_or(0, |d| d.fields.len());
+        if self.fields.is_empty() && detail_fields == 0 {
+            bail!("No fields declared");
+        }
+
+        for selector_text in [&self.item, &self.next].into_iter().flatten() {
+            selector(selector_text)?;
+        }
+        if let Some(detail) = &self.detail {
+            detail.validate()?;
+        }
+
+        validate_fields(&self.fields)
+    }
+
+    /// Runs the rules against a document, returning an array of records when
+    /// `item` is set and a single record otherwise.
+    pub fn extract(&self, document: &Html) -> Result<Value> {
+        let root = document.root_element();
+
+        match &self.item {
+            Some(item) => {
+                let records = root
+                    .select(&selector(item)?)
+                    .map(|element| record(&self.fields, element).map(Value::Object))
+                    .collect::<Result<Vec<_>>>()?;
+                debug!("Extracted {} records", records.len());
+                Ok(Value::Array(records))
+            }
+            None => record(&self.fields, root).map(Value::Object),
+        }
+    }
+
+    /// The detail link of each record [`extract`](Self::extract) returns, in
+    /// the same order, resolved against `base`. Empty without `[detail]`.
+    pub fn links(&self, document: &Html, base: &Url) -> Result<Vec<Option<Url>>> {
+        let Some(detail) = &self.detail else {
+            return Ok(Vec::new());
+        };
+        let link = selector(&detail.link)?;
+        let resolve = |scope: ElementRef| {
+            scope
+                .select(&link)
+                .find_map(|a| a.value().attr("href"))
+                .and_then(|href| base.join(href).ok())
+        };
+
+        let root = document.root_element();
+        match &self.item {
+            Some(item) => Ok(root.select(&selector(item)?).map(resolve).collect()),
+            None => Ok(vec![resolve(root)]),
+        }
+    }
+}
+
+fn validate_fields(fields: &[Field]) -> Result<()> {
+    for (index, field) in fields.iter().enumerate() {
+        selector(&field.selector)?;
+        if fields[..index].iter().any(|f| f.name == field.name) {
+            bail!("Duplicate field '{}'", field.name);
+        }
+    }
+    Ok(())
+}
+
+fn record(fields: &[Field], scope: ElementRef) -> Result<Map<String, Value>> {
+    let mut record = Map::new();
+
+    for field in fields {
+        let selector = selector(&field.selector)?;
+        let mut values = scope
+            .select(&selector)
+            .filter_map(|element| read(element, &field.attr));
+
+        let value = if field.multiple {
+            Value::Array(values.map(Value::String).collect())
+        } else {
+            values.next().map_or(Value::Null, Value::String)
+        };
+
+        let missing = match &value {
+            Value::Null => true,
+            Value::Array(items) => items.is_empty(),
+            _ => false,
+        };
+        if field.required && missing {
+            bail!("Required field '{}' matched nothing", field.name);
+        }
+
+        record.insert(field.name.clone(), value);
+    }
+
+    Ok(record)
+}
+
+fn selector(selector: &str) -> Result<Selector> {
+    Selector::parse(selector).map_err(|e| anyhow!("Invalid selector '{}': {}", selector, e))
+}
+
+fn read(element: ElementRef, attr: &Attr) -> Option<String> {
+    match attr {
+        Attr::Text => Some(
+            element
+                .text()
+                .collect::<String>()
+                .split_whitespace()
+                .collect::<Vec<_>>()
+                .join(" "),
+        ),
+        Attr::Html => Some(element.html()),
+        Attr::InnerHtml => Some(element.inner_html()),
+        Attr::Attribute(name) => element.value().attr(name).map(str::to_string),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PAGE: &str = r#"
+        <ul>
+            <li class="product"><h2>Bulbasaur</h2><a href="/bulbasaur">view</a></li>
+            <li class="product"><h2> Ivysaur </h2><a href="/ivysaur">view</a></li>
+        </ul>
+    "#;
+
+    #[test]
+    fn test_extract_items() {
+        let rules = Rules::from_toml(
+            r#"
+            item = "li.product"
+
+            [[fields]]
+            name = "title"
+            selector = "h2"
+
+            [[fields]]
+            name = "link"
+            selector = "a"
+            attr = "href"
+            "#,
+        )
+        .unwrap();
+
+        let output = rules.extract(&Html::parse_document(PAGE)).unwrap();
+        assert_eq!(
+            output,
+            serde_json::json!([
+                { "title": "Bulbasaur", "link": "/bulbasaur" },
+                { "title": "Ivysaur", "link": "/ivysaur" },
+            ])
+        );
+    }
+
+    #[test]
+    fn test_detail_links_follow_items() {
+        let rules = Rules::from_toml(
+            r#"
+            item = "li.product"
+            next = "a.next"
+
+            [detail]
+            link = "a"
+
+            [[detail.fields]]
+            name = "price"
+            selector = "span.price"
+            "#,
+        )
+        .unwrap();
+        let base = Url::parse("https://example.com/shop/").unwrap();
+
+        let links = rules.links(&Html::parse_document(PAGE), &base).unwrap();
+        assert_eq!(
+            links,
+            [
+                Some(base.join("/bulbasaur").unwrap()),
+                Some(base.join("/ivysaur").unwrap()),
+            ]
+        );
+
+        let detail = rules.detail.as_ref().unwrap();
+        let page = Html::parse_document(r#"<span class="price">£63.00</span>"#);
+        assert_eq!(detail.extract(&page).unwrap()["price"], "£63.00");
+        assert_eq!(detail.concurrency, CONCURRENCY);
+    }
+
+    #[test]
+    fn test_required_field_fails_when_missing() {
+        let rules = Rules::from_toml(
+            r#"
+            [[fields]]
+            name = "price"
+            selector = "span.price"
+            required = true
+            "#,
+        )
+        .unwrap();
+
+        assert!(rules.extract(&Html::parse_document(PAGE)).is_err());
+    }
+
+    #[test]
+    fn test_invalid_selector_is_rejected() {
+        let result = Rules::from_toml(
+            r#"
+            [[fields]]
+            name = "broken"
+            selector = "h2[["
+            "#,
+        );
+
+        assert!(result.is_err());
+    }
+}
@@ -0,0 +1,174 @@
+use crate::{
+    symlink::{
+        backup_existing_path, create_link, ensure_parent_directory_exists, is_current,
+        remove_existing_path,
+    },
+    Config, SymlinkError,
+};
+use std::{
+    fmt, fs,
+    path::{Path, PathBuf},
+};
+use tracing::{debug, info};
+
+/// A problem found at or under the link base.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Issue {
+    /// A symlink whose target no longer exists.
+    Broken { link: PathBuf, target: PathBuf },
+    /// A symlink pointing somewhere other than the expected sources. Only
+    /// reported when sources are given, and never fixed since it may belong
+    /// to another tool.
+    Outside { link: PathBuf, target: PathBuf },
+    /// A source's link path holds something other than its link.
+    Drifted {
+        link: PathBuf,
+        source: PathBuf,
+        actual: Option<PathBuf>,
+    },
+    /// A source has no link yet.
+    Missing { link: PathBuf, source: PathBuf },
+}
+
+impl Issue {
+    pub fn link(&self) -> &Path {
+        match self {
+            Self::Broken { link, .. }
+            | Self::Outside { link, .. }
+            | Self::Drifted { link, .. }
+            | Self::Missing { link, .. } => link,
+        }
+    }
+
+    pub fn is_fixable(&self) -> bool {
+        !matches!(self, Self::Outside { .. })
+    }
+}
+
+impl fmt::Display for Issue {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::Broken { link, target } => {
+                write!(f, "broken: {} -> {}", link.display(), target.display())
+            }
+            Self::Outside { link, target } => write!(
+                f,
+                "outside sources: {} -> {}",
+                link.display(),
+                target.display()
+            ),
+            Self::Drifted {
+                link,
+                source,
+                actual: Some(actual),
+            } => write!(
+                f,
+                "drifted: {} -> {} (expected {})",
+                link.display(),
+                actual.display(),
+                source.display()
+            ),
+            Self::Drifted { link, source, .. } => write!(
+                f,
+                "drifted: {} is not linked to {}",
+                link.display(),
+                source.display()
+            ),
+            Self::Missing { link, source } => {
+                write!(f, "missing: {} -> {}", link.display(), source.display())
+            }
+        }
+    }
+}
+
+/// Checks each source's link, then scans the top level of the link base for
+/// broken symlinks and, when sources are given, symlinks pointing elsewhere.
+pub fn check_links(config: &Config) -> Result<Vec<Issue>, SymlinkError> {
+    let mut issues = Vec::new();
+    let mut expected = Vec::new();
+
+    for source in &config.sources {
+        let link = config.resolve_link_path(source)?;
+        expected.push(link.clone());
+
+        if fs::symlink_metadata(&link).is_err() {
+            issues.push(Issue::Missing {
+                link,
+                source: source.clone(),
+            });
+        } else if !is_current(&link, source, config.mode)? {
+            issues.push(Issue::Drifted {
+                actual: fs::read_link(&link).ok(),
+                link,
+                source: source.clone(),
+            });
+        }
+    }
+
+    if !config.link_base.is_dir() {
+        return Ok(issues);
+    }
+
+    let mut entries = fs::read_dir(&config.link_base)?
+        .map(|entry| entry.map(|entry| entry.path()))
+        .collect::<Result<Vec<_>, _>>()?;
+    entries.sort();
+
+    for link in entries {
+        if expected.contains(&link) {
+            continue;
+        }
+        let Ok(target) = fs::read_link(&link) else {
+            continue;
+        };
+        let target = link
+            .parent()
+            .map_or_else(|| target.clone(), |parent| parent.join(&target));
+        debug!("Checking {} -> {}", link.display(), target.display());
+
+        if fs::metadata(&link).is_err() {
+            issues.push(Issue::Broken { link, target });
+        } else if !config.sources.is_empty()
+            && !config
+                .sources
+                .iter()
+                .any(|source| target.starts_with(source))
+        {
+            issues.push(Issue::Outside { link, target });
+        }
+    }
+
+    Ok(issues)
+}
+
+/// Repairs an issue, returning `false` for issues that are only reported.
+/// Drifted paths that aren't symlinks are backed up rather than deleted.
+pub fn fix_issue(issue: &Issue, config: &Config) -> Result<bool, SymlinkError> {
+    match issue {
+        Issue::Outside { .. } => return Ok(false),
+        Issue::Broken { link, .. } => remove_existing_path(link, config)?,
+        Issue::Drifted { link, source, .. } => {
+            if link.is_symlink() {
+                remove_existing_path(link, config)?;
+            } else {
+                backup_existing_path(link, config)?;
+            }
+            relink(source, link, config)?;
+        }
+        Issue::Missing { link, source } => relink(source, link, config)?,
+    }
+    Ok(true)
+}
+
+fn relink(source: &Path, link: &Path, config: &Config) -> Result<(), SymlinkError> {
+    if !source.exists() {
+        return Err(SymlinkError::SourceNotFound(source.to_path_buf()));
+    }
+
+    ensure_parent_directory_exists(link, config.debug)?;
+    info!("Relinking '{}' to '{}'", link.display(), source.display());
+    if !config.debug {
+        create_link(source, link, config.mode)?;
+    }
+    Ok(())
+}
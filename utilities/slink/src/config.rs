@@ -1,23 +1,126 @@
-use std::path::PathBuf;
+use std::{fmt, path::PathBuf, str::FromStr};
+
+/// How each source is placed at its link path.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum LinkMode {
+    /// Falls back to `Copy` where symlinks need privileges the user lacks,
+    /// such as Windows without developer mode.
+    #[default]
+    Symlink,
+    /// Directories are recreated and their files hard linked one by one.
+    Hardlink,
+    Copy,
+}
+
+impl FromStr for LinkMode {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "symlink" => Ok(Self::Symlink),
+            "hardlink" => Ok(Self::Hardlink),
+            "copy" => Ok(Self::Copy),
+            _ => Err(format!("Unknown link mode: {}", s)),
+        }
+    }
+}
+
+impl fmt::Display for LinkMode {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let mode = match self {
+            Self::Symlink => "symlink",
+            Self::Hardlink => "hardlink",
+            Self::Copy => "copy",
+        };
+        write!(f, "{}", mode)
+    }
+}
+
+/// What to do when something already exists at a link path.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum ConflictPolicy {
+    /// Leave the existing path alone.
+    Skip,
+    /// Delete the existing path.
+    Overwrite,
+    /// Move the existing path aside with a timestamp suffix.
+    Backup,
+    /// Ask per conflict on a terminal; fails like `FailFast` otherwise.
+    #[default]
+    Prompt,
+    /// Stop at the first conflict with [`SymlinkError::LinkExists`].
+    ///
+    /// [`SymlinkError::LinkExists`]: crate::SymlinkError::LinkExists
+    FailFast,
+}
+
+impl FromStr for ConflictPolicy {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().replace(['-', '_'], "").as_str() {
+            "skip" => Ok(Self::Skip),
+            "overwrite" => Ok(Self::Overwrite),
+            "backup" => Ok(Self::Backup),
+            "prompt" => Ok(Self::Prompt),
+            "failfast" | "fail" => Ok(Self::FailFast),
+            _ => Err(format!("Unknown conflict policy: {}", s)),
+        }
+    }
+}
+
+impl fmt::Display for ConflictPolicy {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let policy = match self {
+            Self::Skip => "skip",
+            Self::Overwrite => "overwrite",
+            Self::Backup => "backup",
+            Self::Prompt => "prompt",
+            Self::FailFast => "fail-fast",
+        };
+        write!(f, "{}", policy)
+    }
+}
 
 #[derive(Debug)]
 pub struct Config {
-    pub force: bool,
+    pub conflict: ConflictPolicy,
     pub debug: bool,
     pub sources: Vec<PathBuf>,
     pub link_base: PathBuf,
+    pub mode: LinkMode,
+    /// Retry operations refused for lack of permission with sudo, or a UAC
+    /// prompt on Windows.
+    pub elevate: bool,
 }
 
 impl Config {
-    pub fn new(force: bool, debug: bool, sources: Vec<PathBuf>, link_base: PathBuf) -> Self {
+    pub fn new(debug: bool, sources: Vec<PathBuf>, link_base: PathBuf) -> Self {
         Self {
-            force,
+            conflict: ConflictPolicy::default(),
             debug,
             sources,
             link_base,
+            mode: LinkMode::default(),
+            elevate: false,
         }
     }
 
+    pub fn with_conflict(mut self, conflict: ConflictPolicy) -> Self {
+        self.conflict = conflict;
+        self
+    }
+
+    pub fn with_mode(mut self, mode: LinkMode) -> Self {
+        self.mode = mode;
+        self
+    }
+
+    pub fn with_elevate(mut self, elevate: bool) -> Self {
+        self.elevate = elevate;
+        self
+    }
+
     pub fn resolve_link_path(
         &self,
         src: &std::path::Path,
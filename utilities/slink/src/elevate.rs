@@ -0,0 +1,78 @@
+use crate::{Config, ConflictPolicy, SymlinkError};
+use std::ffi::OsString;
+use std::io;
+use std::path::Path;
+use std::process::Command;
+
+/// Whether `error` means the current user may not write where the link goes.
+pub(crate) fn is_permission_denied(error: &SymlinkError) -> bool {
+    matches!(error, SymlinkError::Io(e) if e.kind() == io::ErrorKind::PermissionDenied)
+}
+
+/// Runs slink again with elevated rights to place just `src` at
+/// `link_path`, waiting for it to finish.
+pub(crate) fn relaunch(
+    src: &Path,
+    link_path: &Path,
+    config: &Config,
+    conflict: ConflictPolicy,
+) -> Result<(), SymlinkError> {
+    let link_base = link_path.parent().unwrap_or(Path::new("."));
+
+    // `--src` is a glob, so escape anything in the path that would expand
+    let args: Vec<OsString> = vec![
+        "--src".into(),
+        glob::Pattern::escape(&src.to_string_lossy()).into(),
+        "--link".into(),
+        link_base.into(),
+        "--mode".into(),
+        config.mode.to_string().into(),
+        "--conflict".into(),
+        conflict.to_string().into(),
+    ];
+
+    let exe = std::env::current_exe()?;
+    let mut command = elevated(&exe, &args);
+    let status = command.status().map_err(|e| {
+        SymlinkError::Elevation(format!(
+            "could not run {}: {}",
+            command.get_program().to_string_lossy(),
+            e
+        ))
+    })?;
+    if status.success() {
+        Ok(())
+    } else {
+        Err(SymlinkError::Elevation(format!(
+            "'{}' exited with {}",
+            link_path.display(),
+            status
+        )))
+    }
+}
+
+#[cfg(not(windows))]
+fn elevated(exe: &Path, args: &[OsString]) -> Command {
+    let mut command = Command::new("sudo");
+    command.arg(exe).args(args);
+    command
+}
+
+#[cfg(windows)]
+fn elevated(exe: &Path, args: &[OsString]) -> Command {
+    // Start-Process joins its arguments with spaces, so each one is quoted
+    let quote = |arg: &str| format!("'\"{}\"'", arg.replace('\'', "''"));
+    let args: Vec<String> = args
+        .iter()
+        .map(|arg| quote(&arg.to_string_lossy()))
+        .collect();
+    let script = format!(
+        "exit (Start-Process -FilePath {} -ArgumentList {} -Verb RunAs -Wait -PassThru).ExitCode",
+        quote(&exe.to_string_lossy()),
+        args.join(",")
+    );
+
+    let mut command = Command::new("powershell");
+    command.args(["-NoProfile", "-NonInteractive", "-Command", &script]);
+    command
+}
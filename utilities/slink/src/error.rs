@@ -15,6 +15,8 @@ pub enum SymlinkError {
     NoSourceFileName,
     #[error("Glob pattern error: {0}")]
     GlobPattern(#[from] glob::PatternError),
+    #[error("Invalid manifest: {0}")]
+    Manifest(String),
     #[error("No files matched the given pattern")]
     NoMatchingFiles,
     #[error("User aborted the operation")]
@@ -23,6 +25,8 @@ pub enum SymlinkError {
     IoError(String),
     #[error("Path error: {0}")]
     PathError(String),
+    #[error("Elevated retry failed: {0}")]
+    Elevation(String),
     #[error(
         "Insufficient privileges to create symlink. Try running the program as administrator."
     )]
@@ -1,9 +1,16 @@
+pub mod check;
 pub mod config;
+mod elevate;
 pub mod error;
+pub mod manifest;
+pub mod report;
 pub mod symlink;
 
-pub use config::Config;
+pub use check::{check_links, fix_issue, Issue};
+pub use config::{Config, ConflictPolicy, LinkMode};
 pub use error::SymlinkError;
+pub use manifest::{Guard, Host, Manifest};
+pub use report::LinkReport;
 pub use symlink::process_links;
 
 #[cfg(test)]
@@ -11,6 +18,7 @@ mod tests {
     use super::*;
     use std::fs::{self, File};
     use std::io::Write;
+    use std::path::PathBuf;
     use tempfile::tempdir;
 
     fn setup_test_env() -> (tempfile::TempDir, Config) {
@@ -20,7 +28,7 @@ mod tests {
         fs::create_dir_all(&src_dir).unwrap();
         fs::create_dir_all(&link_dir).unwrap();
 
-        let config = Config::new(false, false, vec![], link_dir);
+        let config = Config::new(false, vec![], link_dir);
 
         (temp_dir, config)
     }
@@ -31,8 +39,11 @@ mod tests {
         let src_file = temp_dir.path().join("src/test_file");
         File::create(&src_file)?.write_all(b"test content")?;
 
-        config.sources = vec![src_file.clone()];
-        process_links(&config)?;
+        // The root has no file name to link under, so it fails on its own
+        config.sources = vec![PathBuf::from("/"), src_file.clone()];
+        let report = process_links(&config)?;
+        assert!(!report.is_success());
+        assert_eq!(report.created[0].src, src_file);
 
         let link_path = config.link_base.join("test_file");
         assert!(link_path.exists());
@@ -42,5 +53,216 @@ mod tests {
         Ok(())
     }
 
-    // ... [Include all other tests from the previous version]
+    #[test]
+    fn test_copy_and_hardlink_modes() -> Result<(), Box<dyn std::error::Error>> {
+        let (temp_dir, config) = setup_test_env();
+        let src_dir = temp_dir.path().join("src/app");
+        fs::create_dir_all(src_dir.join("nested"))?;
+        fs::write(src_dir.join("nested/settings"), "theme = dark")?;
+
+        let link_base = config.link_base.clone();
+        for mode in [LinkMode::Copy, LinkMode::Hardlink] {
+            let base = link_base.join(mode.to_string());
+            let config = Config::new(false, vec![src_dir.clone()], base.clone()).with_mode(mode);
+            process_links(&config)?;
+
+            let linked = base.join("app/nested/settings");
+            assert!(!base.join("app").is_symlink());
+            assert_eq!(fs::read_to_string(&linked)?, "theme = dark");
+
+            // Running again leaves the up-to-date copy alone
+            process_links(&config)?;
+        }
+
+        fs::write(src_dir.join("nested/settings"), "theme = light")?;
+        let hardlinked = link_base.join("hardlink/app/nested/settings");
+        assert_eq!(fs::read_to_string(hardlinked)?, "theme = light");
+
+        Ok(())
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_copy_keeps_mode_bits() -> Result<(), Box<dyn std::error::Error>> {
+        use std::os::unix::fs::PermissionsExt;
+
+        let (temp_dir, config) = setup_test_env();
+        let src_dir = temp_dir.path().join("src/bin");
+        fs::create_dir_all(&src_dir)?;
+        fs::write(src_dir.join("tool"), "#!/bin/sh")?;
+        fs::set_permissions(src_dir.join("tool"), fs::Permissions::from_mode(0o750))?;
+        fs::set_permissions(&src_dir, fs::Permissions::from_mode(0o700))?;
+
+        let config = Config::new(false, vec![src_dir], config.link_base).with_mode(LinkMode::Copy);
+        process_links(&config)?;
+
+        let mode = |path: PathBuf| fs::metadata(path).map(|m| m.permissions().mode() & 0o777);
+        assert_eq!(mode(config.link_base.join("bin"))?, 0o700);
+        assert_eq!(mode(config.link_base.join("bin/tool"))?, 0o750);
+
+        Ok(())
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_copy_keeps_nested_symlinks() -> Result<(), Box<dyn std::error::Error>> {
+        let (temp_dir, config) = setup_test_env();
+        let src_dir = temp_dir.path().join("src/app");
+        fs::create_dir_all(&src_dir)?;
+        fs::write(src_dir.join("settings"), "theme = dark")?;
+        // Following this would recurse forever
+        std::os::unix::fs::symlink("..", src_dir.join("parent"))?;
+
+        for mode in [LinkMode::Copy, LinkMode::Hardlink] {
+            let base = config.link_base.join(mode.to_string());
+            let config = Config::new(false, vec![src_dir.clone()], base.clone()).with_mode(mode);
+            let report = process_links(&config)?;
+            assert_eq!(report.created.len(), 1);
+
+            let parent = base.join("app/parent");
+            assert!(parent.is_symlink());
+            assert_eq!(fs::read_link(parent)?, PathBuf::from(".."));
+
+            let report = process_links(&config)?;
+            assert_eq!(report.skipped.len(), 1);
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_conflict_policies() -> Result<(), Box<dyn std::error::Error>> {
+        let (temp_dir, mut config) = setup_test_env();
+        let src_file = temp_dir.path().join("src/profile");
+        fs::write(&src_file, "new")?;
+        let link_path = config.link_base.join("profile");
+        config.sources = vec![src_file.clone()];
+
+        fs::write(&link_path, "old")?;
+        config.conflict = ConflictPolicy::FailFast;
+        assert!(matches!(
+            process_links(&config),
+            Err(SymlinkError::LinkExists(_))
+        ));
+
+        config.conflict = ConflictPolicy::Skip;
+        let report = process_links(&config)?;
+        assert!(!link_path.is_symlink());
+        assert_eq!(
+            report.skipped[0].reason.as_deref(),
+            Some("kept the existing path")
+        );
+
+        config.conflict = ConflictPolicy::Backup;
+        let report = process_links(&config)?;
+        assert!(link_path.is_symlink());
+        assert_eq!(report.replaced[0].link, link_path);
+        let backups = fs::read_dir(&config.link_base)?
+            .filter_map(Result::ok)
+            .filter(|entry| {
+                entry
+                    .file_name()
+                    .to_string_lossy()
+                    .starts_with("profile_backup_")
+            })
+            .count();
+        assert_eq!(backups, 1);
+
+        fs::remove_file(&link_path)?;
+        fs::create_dir(&link_path)?;
+        config.conflict = ConflictPolicy::Overwrite;
+        process_links(&config)?;
+        assert_eq!(fs::read_to_string(&link_path)?, "new");
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_manifest_guards_skip_other_machines() -> Result<(), Box<dyn std::error::Error>> {
+        let (temp_dir, config) = setup_test_env();
+        let src = temp_dir.path().join("src");
+        fs::write(src.join("bashrc"), "")?;
+        fs::write(src.join("hypr.conf"), "")?;
+
+        let manifest: Manifest = toml::from_str(
+            r#"
+            [[links]]
+            src = "src/bashrc"
+            when = { os = "unix" }
+
+            [[links]]
+            src = "src/*.conf"
+            link = "elsewhere"
+            when = { os = "linux", hostname = "desktop" }
+            "#,
+        )?;
+        let host = |hostname: &str| Host {
+            os: "linux".to_string(),
+            hostname: hostname.to_string(),
+            user: "me".to_string(),
+        };
+
+        let (planned, skipped) =
+            manifest.plan(&host("laptop"), temp_dir.path(), &config.link_base)?;
+        assert_eq!(planned.len(), 1);
+        assert_eq!(planned[0].sources, [src.join("bashrc")]);
+        assert_eq!(planned[0].link_base, config.link_base);
+        assert_eq!(skipped[0].reason, "hostname is laptop, not desktop");
+
+        let (planned, skipped) =
+            manifest.plan(&host("Desktop"), temp_dir.path(), &config.link_base)?;
+        assert_eq!(planned[1].sources, [src.join("hypr.conf")]);
+        assert_eq!(planned[1].link_base, temp_dir.path().join("elsewhere"));
+        assert!(skipped.is_empty());
+
+        let windows = Host {
+            os: "windows".to_string(),
+            ..host("desktop")
+        };
+        assert_eq!(
+            Guard {
+                os: Some("unix".to_string()),
+                ..Guard::default()
+            }
+            .check(&windows)
+            .as_deref(),
+            Some("os is windows, not unix")
+        );
+
+        Ok(())
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_check_and_fix() -> Result<(), Box<dyn std::error::Error>> {
+        let (temp_dir, mut config) = setup_test_env();
+        let src = temp_dir.path().join("src");
+        for name in ["kept", "moved", "unlinked"] {
+            fs::write(src.join(name), name)?;
+        }
+        config.sources = vec![src.join("kept"), src.join("moved")];
+        process_links(&config)?;
+
+        fs::remove_file(src.join("moved"))?;
+        fs::write(src.join("renamed"), "moved")?;
+        std::os::unix::fs::symlink(src.join("unlinked"), config.link_base.join("other"))?;
+        std::os::unix::fs::symlink(src.join("gone"), config.link_base.join("dangling"))?;
+
+        config.sources = vec![src.join("kept"), src.join("renamed")];
+        let issues = check_links(&config)?;
+        let kinds: Vec<_> = issues
+            .iter()
+            .map(|issue| issue.to_string().split(':').next().unwrap().to_string())
+            .collect();
+        assert_eq!(kinds, ["missing", "broken", "broken", "outside sources"]);
+
+        for issue in &issues {
+            fix_issue(issue, &config)?;
+        }
+        let remaining = check_links(&config)?;
+        assert_eq!(remaining.len(), 1);
+        assert!(!remaining[0].is_fixable());
+
+        Ok(())
+    }
 }
@@ -1,39 +1,95 @@
-use clap::{arg, command, value_parser, ArgAction};
+use clap::{arg, command, value_parser, ArgAction, ArgMatches, Command};
 use directories::BaseDirs;
 use glob::glob;
-use slink::{process_links, Config, SymlinkError};
+use slink::{
+    check_links, fix_issue, process_links, report, Config, ConflictPolicy, Host, LinkMode,
+    LinkReport, Manifest, SymlinkError,
+};
+use std::path::{Path, PathBuf};
 use tracing_subscriber::{EnvFilter, FmtSubscriber};
 
 fn main() -> Result<(), Box<dyn std::error::Error>> {
     let matches = command!()
         .about("Creates symbolic links for configuration files")
+        .subcommand_negates_reqs(true)
         .arg(
             arg!(-s --src <PATTERN> "Source file(s) or directory to link (supports glob patterns)")
                 .value_parser(value_parser!(String))
-                .required(true)
+                .required_unless_present("manifest")
                 .action(ArgAction::Append),
         )
+        .arg(
+            arg!(-M --manifest <FILE> "TOML file listing the links, with optional per-machine guards")
+                .value_parser(value_parser!(PathBuf))
+                .conflicts_with("src"),
+        )
         .arg(
             arg!(-l --link <PATH> "Base directory for links (defaults to XDG config home)")
-                .value_parser(value_parser!(std::path::PathBuf)),
+                .value_parser(value_parser!(PathBuf)),
         )
         .arg(
-            arg!(-f --force "Overwrite destination if it exists without prompting")
-                .action(ArgAction::SetTrue),
+            arg!(-c --conflict <POLICY> "What to do when a link path exists: skip, overwrite, backup, prompt or fail-fast")
+                .value_parser(["skip", "overwrite", "backup", "prompt", "fail-fast"])
+                .default_value("prompt"),
+        )
+        .arg(
+            arg!(-f --force "Back up existing paths without prompting (same as --conflict backup)")
+                .action(ArgAction::SetTrue)
+                .conflicts_with("conflict"),
         )
         .arg(
             arg!(-v --verbose "Increase verbosity (can be used multiple times)")
-                .action(ArgAction::Count),
+                .action(ArgAction::Count)
+                .global(true),
         )
         .arg(
             arg!(-q --quiet "Suppress all output except errors")
                 .action(ArgAction::SetTrue)
-                .conflicts_with("verbose"),
+                .conflicts_with("verbose")
+                .global(true),
         )
         .arg(
-            arg!(-d --debug "Debug mode: simulate operations and show additional info")
+            arg!(-m --mode <MODE> "How to place sources: symlink, hardlink or copy")
+                .value_parser(["symlink", "hardlink", "copy"])
+                .default_value("symlink"),
+        )
+        .arg(
+            arg!(-e --elevate "Retry links refused for lack of permission with sudo (a UAC prompt on Windows)")
                 .action(ArgAction::SetTrue),
         )
+        .arg(
+            arg!(-r --report <FORMAT> "Print what was created, replaced, skipped and failed: text or json")
+                .value_parser(["text", "json"]),
+        )
+        .arg(
+            arg!(-d --debug "Debug mode: simulate operations and show additional info")
+                .action(ArgAction::SetTrue)
+                .global(true),
+        )
+        .subcommand(
+            Command::new("check")
+                .about("Report broken, drifted and stray links under the link base")
+                .arg(
+                    arg!(-s --src <PATTERN> "Sources the links should point to (supports glob patterns)")
+                        .value_parser(value_parser!(String))
+                        .action(ArgAction::Append),
+                )
+                .arg(
+                    arg!(-M --manifest <FILE> "Check the links a manifest declares for this machine")
+                        .value_parser(value_parser!(PathBuf))
+                        .conflicts_with("src"),
+                )
+                .arg(
+                    arg!(-l --link <PATH> "Base directory for links (defaults to XDG config home)")
+                        .value_parser(value_parser!(PathBuf)),
+                )
+                .arg(
+                    arg!(-m --mode <MODE> "How sources were placed: symlink, hardlink or copy")
+                        .value_parser(["symlink", "hardlink", "copy"])
+                        .default_value("symlink"),
+                )
+                .arg(arg!(--fix "Repair or remove the links that can be fixed")),
+        )
         .get_matches();
 
     // Set up tracing
@@ -55,8 +111,134 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
 
     tracing::subscriber::set_global_default(subscriber).expect("Failed to set tracing subscriber");
 
+    if let Some(check) = matches.subcommand_matches("check") {
+        return run_check(check);
+    }
+
+    let conflict = if matches.get_flag("force") {
+        ConflictPolicy::Backup
+    } else {
+        matches
+            .get_one::<String>("conflict")
+            .map(|policy| policy.parse::<ConflictPolicy>())
+            .transpose()?
+            .unwrap_or_default()
+    };
+
+    if let Some(path) = matches.get_one::<PathBuf>("manifest") {
+        return run_manifest(&matches, path, conflict);
+    }
+
+    let sources = expand_sources(&matches)?;
+    if sources.is_empty() {
+        return Err(SymlinkError::NoMatchingFiles.into());
+    }
+
+    let config = Config::new(matches.get_flag("debug"), sources, link_base(&matches)?)
+        .with_conflict(conflict)
+        .with_mode(link_mode(&matches)?)
+        .with_elevate(matches.get_flag("elevate"));
+
+    // debug!(?config, "Configuration");
+    // debug!("{}", config);
+
+    let report = process_links(&config)?;
+    finish(&matches, &report)
+}
+
+fn run_manifest(
+    matches: &ArgMatches,
+    path: &Path,
+    conflict: ConflictPolicy,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let manifest = Manifest::load(path)?;
+    let dir = path.parent().unwrap_or(Path::new("."));
+    let (planned, skipped) = manifest.plan(&Host::current(), dir, &link_base(matches)?)?;
+    let mut report = LinkReport::default();
+
+    for entry in skipped {
+        tracing::info!("Skipping {}: {}", entry.src, entry.reason);
+        let skipped = report::Entry::new(entry.src, entry.link_base).with_reason(entry.reason);
+        report.skipped.push(skipped);
+    }
+    for entry in planned {
+        if entry.sources.is_empty() {
+            tracing::warn!("No sources matched {}", entry.src);
+            let skipped =
+                report::Entry::new(entry.src, entry.link_base).with_reason("no sources matched");
+            report.skipped.push(skipped);
+            continue;
+        }
+        let config = Config::new(matches.get_flag("debug"), entry.sources, entry.link_base)
+            .with_conflict(conflict)
+            .with_mode(link_mode(matches)?)
+            .with_elevate(matches.get_flag("elevate"));
+        report.merge(process_links(&config)?);
+    }
+    finish(matches, &report)
+}
+
+/// Prints the report if one was asked for, exiting non-zero when any
+/// entry failed so provisioning scripts can stop.
+fn finish(matches: &ArgMatches, report: &LinkReport) -> Result<(), Box<dyn std::error::Error>> {
+    match matches.get_one::<String>("report").map(String::as_str) {
+        Some("json") => println!("{}", serde_json::to_string_pretty(report)?),
+        Some(_) => println!("{}", report),
+        None => {}
+    }
+
+    if !report.is_success() {
+        std::process::exit(1);
+    }
+    Ok(())
+}
+
+fn run_check(matches: &ArgMatches) -> Result<(), Box<dyn std::error::Error>> {
+    let debug = matches.get_flag("debug");
+    let mode = link_mode(matches)?;
+    let configs = match matches.get_one::<PathBuf>("manifest") {
+        Some(path) => {
+            let manifest = Manifest::load(path)?;
+            let dir = path.parent().unwrap_or(Path::new("."));
+            let (planned, skipped) = manifest.plan(&Host::current(), dir, &link_base(matches)?)?;
+            for entry in skipped {
+                tracing::info!("Skipping {}: {}", entry.src, entry.reason);
+            }
+            planned
+                .into_iter()
+                .map(|entry| Config::new(debug, entry.sources, entry.link_base).with_mode(mode))
+                .collect()
+        }
+        None => {
+            vec![Config::new(debug, expand_sources(matches)?, link_base(matches)?).with_mode(mode)]
+        }
+    };
+
+    let mut unresolved = 0;
+    for config in &configs {
+        let issues = check_links(config)?;
+        for issue in &issues {
+            if matches.get_flag("fix") && fix_issue(issue, config)? {
+                println!("fixed {}", issue);
+            } else {
+                println!("{}", issue);
+                unresolved += 1;
+            }
+        }
+
+        if issues.is_empty() {
+            tracing::info!("All links in {} are healthy", config.link_base.display());
+        }
+    }
+    if unresolved > 0 {
+        std::process::exit(1);
+    }
+    Ok(())
+}
+
+fn expand_sources(matches: &ArgMatches) -> Result<Vec<PathBuf>, glob::PatternError> {
     let mut sources = Vec::new();
-    for pattern in matches.get_many::<String>("src").unwrap() {
+    for pattern in matches.get_many::<String>("src").into_iter().flatten() {
         for entry in glob(pattern)? {
             match entry {
                 Ok(path) => sources.push(path),
@@ -66,27 +248,22 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
             }
         }
     }
+    Ok(sources)
+}
 
-    if sources.is_empty() {
-        return Err(SymlinkError::NoMatchingFiles.into());
+fn link_base(matches: &ArgMatches) -> Result<PathBuf, SymlinkError> {
+    match matches.get_one::<PathBuf>("link") {
+        Some(link) => Ok(link.clone()),
+        None => BaseDirs::new()
+            .map(|dirs| dirs.config_dir().to_path_buf())
+            .ok_or(SymlinkError::NoConfigDir),
     }
+}
 
-    let base_dirs = BaseDirs::new().ok_or(SymlinkError::NoConfigDir)?;
-    let default_link_base = base_dirs.config_dir().to_path_buf();
-
-    let config = Config::new(
-        matches.get_flag("force"),
-        matches.get_flag("debug"),
-        sources,
-        matches
-            .get_one::<std::path::PathBuf>("link")
-            .cloned()
-            .unwrap_or(default_link_base),
-    );
-
-    // debug!(?config, "Configuration");
-    // debug!("{}", config);
-
-    process_links(&config)?;
-    Ok(())
+fn link_mode(matches: &ArgMatches) -> Result<LinkMode, String> {
+    matches
+        .get_one::<String>("mode")
+        .map(|mode| mode.parse())
+        .transpose()
+        .map(Option::unwrap_or_default)
 }
@@ -0,0 +1,187 @@
+use crate::SymlinkError;
+use directories::BaseDirs;
+use serde::Deserialize;
+use std::{
+    env, fs,
+    path::{Path, PathBuf},
+    process::Command,
+};
+
+/// Links declared in a TOML file, so one file can serve several machines.
+///
+/// ```toml
+/// link_base = "~/.config"
+///
+/// [[links]]
+/// src = "nvim"
+///
+/// [[links]]
+/// src = "hypr/*.conf"
+/// link = "~/.config/hypr"
+/// when = { os = "linux", hostname = "desktop" }
+/// ```
+///
+/// Sources are glob patterns and, like relative link directories, are
+/// resolved against the manifest's directory.
+#[derive(Debug, Clone, Default, PartialEq, Deserialize)]
+pub struct Manifest {
+    /// Where links go unless an entry says otherwise.
+    pub link_base: Option<PathBuf>,
+
+    #[serde(default)]
+    pub links: Vec<Entry>,
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct Entry {
+    pub src: String,
+
+    /// Directory the links are created in, overriding `link_base`.
+    pub link: Option<PathBuf>,
+
+    #[serde(default)]
+    pub when: Guard,
+}
+
+/// Conditions an entry needs to be linked; every one given must hold.
+/// Values are compared case-insensitively.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Guard {
+    /// `linux`, `macos`, `windows`, or `unix` for any Unix.
+    pub os: Option<String>,
+    pub hostname: Option<String>,
+    pub user: Option<String>,
+}
+
+/// The machine a manifest is evaluated on.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Host {
+    pub os: String,
+    pub hostname: String,
+    pub user: String,
+}
+
+impl Host {
+    pub fn current() -> Self {
+        Self {
+            os: env::consts::OS.to_string(),
+            hostname: hostname(),
+            user: env::var("USER")
+                .or_else(|_| env::var("USERNAME"))
+                .unwrap_or_default(),
+        }
+    }
+}
+
+impl Guard {
+    /// Why the guard fails on `host`, or `None` if it holds.
+    pub fn check(&self, host: &Host) -> Option<String> {
+        if let Some(os) = &self.os {
+            let unix = os.eq_ignore_ascii_case("unix") && host.os != "windows";
+            if !unix && !os.eq_ignore_ascii_case(&host.os) {
+                return Some(format!("os is {}, not {}", host.os, os));
+            }
+        }
+
+        [
+            ("hostname", &self.hostname, &host.hostname),
+            ("user", &self.user, &host.user),
+        ]
+        .into_iter()
+        .find_map(|(name, wanted, actual)| {
+            let wanted = wanted.as_ref()?;
+            (!wanted.eq_ignore_ascii_case(actual))
+                .then(|| format!("{} is {}, not {}", name, actual, wanted))
+        })
+    }
+}
+
+/// An entry left out because its guard failed.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Skipped {
+    pub src: String,
+    pub link_base: PathBuf,
+    pub reason: String,
+}
+
+/// The sources of one entry, resolved to paths, and where to link them.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Planned {
+    pub src: String,
+    pub sources: Vec<PathBuf>,
+    pub link_base: PathBuf,
+}
+
+impl Manifest {
+    pub fn load(path: &Path) -> Result<Self, SymlinkError> {
+        let content = fs::read_to_string(path)?;
+        toml::from_str(&content)
+            .map_err(|e| SymlinkError::Manifest(format!("{}: {}", path.display(), e)))
+    }
+
+    /// Splits the entries into those to link on `host` and those whose
+    /// guard fails. Sources and link directories are resolved against
+    /// `dir`; entries without a link directory use `default_base`.
+    pub fn plan(
+        &self,
+        host: &Host,
+        dir: &Path,
+        default_base: &Path,
+    ) -> Result<(Vec<Planned>, Vec<Skipped>), SymlinkError> {
+        let base = self.link_base.as_deref().map(|base| dir.join(expand(base)));
+        let mut planned = Vec::new();
+        let mut skipped = Vec::new();
+
+        for entry in &self.links {
+            let link_base = match (&entry.link, &base) {
+                (Some(link), _) => dir.join(expand(link)),
+                (None, Some(base)) => base.clone(),
+                (None, None) => default_base.to_path_buf(),
+            };
+            if let Some(reason) = entry.when.check(host) {
+                skipped.push(Skipped {
+                    src: entry.src.clone(),
+                    link_base,
+                    reason,
+                });
+                continue;
+            }
+
+            let pattern = dir.join(expand(Path::new(&entry.src)));
+            let sources = glob::glob(&pattern.to_string_lossy())?
+                .filter_map(Result::ok)
+                .collect();
+            planned.push(Planned {
+                src: entry.src.clone(),
+                sources,
+                link_base,
+            });
+        }
+
+        Ok((planned, skipped))
+    }
+}
+
+/// Replaces a leading `~` with the home directory.
+fn expand(path: &Path) -> PathBuf {
+    match (path.strip_prefix("~"), BaseDirs::new()) {
+        (Ok(rest), Some(dirs)) => dirs.home_dir().join(rest),
+        _ => path.to_path_buf(),
+    }
+}
+
+fn hostname() -> String {
+    env::var("HOSTNAME")
+        .or_else(|_| env::var("COMPUTERNAME"))
+        .ok()
+        .or_else(|| fs::read_to_string("/etc/hostname").ok())
+        .or_else(|| {
+            Command::new("hostname")
+                .output()
+                .ok()
+                .map(|output| String::from_utf8_lossy(&output.stdout).into_owned())
+        })
+        .map(|name| name.trim().to_string())
+        .unwrap_or_default()
+}
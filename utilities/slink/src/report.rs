@@ -0,0 +1,88 @@
+use serde::Serialize;
+use std::fmt;
+use std::path::PathBuf;
+
+/// What happened to each source in a run of [`process_links`].
+///
+/// [`process_links`]: crate::process_links
+#[derive(Debug, Default, Clone, PartialEq, Eq, Serialize)]
+pub struct LinkReport {
+    pub created: Vec<Entry>,
+    /// Links placed after clearing something already at the link path.
+    pub replaced: Vec<Entry>,
+    pub skipped: Vec<Entry>,
+    pub failed: Vec<Entry>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Entry {
+    pub src: PathBuf,
+    pub link: PathBuf,
+    /// Why the entry was skipped or failed.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub reason: Option<String>,
+}
+
+impl Entry {
+    pub fn new(src: impl Into<PathBuf>, link: impl Into<PathBuf>) -> Self {
+        Self {
+            src: src.into(),
+            link: link.into(),
+            reason: None,
+        }
+    }
+
+    pub fn with_reason(mut self, reason: impl fmt::Display) -> Self {
+        self.reason = Some(reason.to_string());
+        self
+    }
+}
+
+impl LinkReport {
+    pub fn is_success(&self) -> bool {
+        self.failed.is_empty()
+    }
+
+    /// Appends the entries of `other`, as when one manifest runs several
+    /// configs.
+    pub fn merge(&mut self, other: LinkReport) {
+        self.created.extend(other.created);
+        self.replaced.extend(other.replaced);
+        self.skipped.extend(other.skipped);
+        self.failed.extend(other.failed);
+    }
+}
+
+impl fmt::Display for LinkReport {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let groups = [
+            ("created", &self.created),
+            ("replaced", &self.replaced),
+            ("skipped", &self.skipped),
+            ("failed", &self.failed),
+        ];
+        for (label, entries) in groups {
+            for entry in entries {
+                write!(
+                    f,
+                    "{:<8} {} -> {}",
+                    label,
+                    entry.src.display(),
+                    entry.link.display()
+                )?;
+                match &entry.reason {
+                    Some(reason) => writeln!(f, " ({})", reason)?,
+                    None => writeln!(f)?,
+                }
+            }
+        }
+        write!(
+            f,
+            "{} created, {} replaced, {} skipped, {} failed",
+            self.created.len(),
+            self.replaced.len(),
+            self.skipped.len(),
+            self.failed.len()
+        )
+    }
+}
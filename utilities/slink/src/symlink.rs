@@ -1,52 +1,180 @@
-use crate::{Config, SymlinkError};
+use crate::elevate::{self, is_permission_denied};
+use crate::report::{Entry, LinkReport};
+use crate::{Config, ConflictPolicy, LinkMode, SymlinkError};
 use chrono::Local;
 use std::fs;
-use std::io::{self, Write};
+use std::io::{self, IsTerminal, Write};
 use std::path::{Path, PathBuf};
-use tracing::{debug, info, warn};
+use tracing::{debug, error, info, warn};
 
-pub fn process_links(config: &Config) -> Result<(), SymlinkError> {
+/// Places every source in `config`, carrying on past sources that fail.
+/// Only a fail-fast conflict or quitting at the prompt stops the run early,
+/// with an error instead of a report.
+pub fn process_links(config: &Config) -> Result<LinkReport, SymlinkError> {
     debug!("Configuration:\n{:#?}", config);
+    let mut policy = config.conflict;
+    let mut report = LinkReport::default();
 
     for src in &config.sources {
+        let link_path = match config.resolve_link_path(src) {
+            Ok(link_path) => link_path,
+            Err(e) => {
+                error!("Failed to place '{}': {}", src.display(), e);
+                let entry = Entry::new(src, &config.link_base).with_reason(e);
+                report.failed.push(entry);
+                continue;
+            }
+        };
+        let entry = Entry::new(src, &link_path);
+
         if !src.exists() {
             warn!("Skipping non-existent source: {}", src.display());
+            report
+                .skipped
+                .push(entry.with_reason("source does not exist"));
             continue;
         }
 
-        let link_path = config.resolve_link_path(src)?;
-
-        debug!(
-            "Processing: Source: {}, Link: {}",
-            src.display(),
-            link_path.display()
-        );
-
-        if link_path.exists() {
-            if is_correct_symlink(&link_path, src)? {
-                info!(
-                    "Symlink already exists and is correct: {}",
+        let existed = fs::symlink_metadata(&link_path).is_ok();
+        let placed = match place(src, &link_path, config, &mut policy) {
+            Err(e) if config.elevate && is_permission_denied(&e) => {
+                warn!(
+                    "Permission denied for '{}', retrying with elevated rights",
                     link_path.display()
                 );
-                continue;
+                elevate::relaunch(src, &link_path, config, policy).map(|()| match existed {
+                    true => Placed::Replaced,
+                    false => Placed::Created,
+                })
+            }
+            result => result,
+        };
+
+        match placed {
+            Ok(Placed::Created) => report.created.push(entry),
+            Ok(Placed::Replaced) => report.replaced.push(entry),
+            Ok(Placed::Skipped(reason)) => report.skipped.push(entry.with_reason(reason)),
+            Err(e @ (SymlinkError::LinkExists(_) | SymlinkError::UserAbort)) => return Err(e),
+            Err(e) => {
+                error!("Failed to place '{}': {}", link_path.display(), e);
+                report.failed.push(entry.with_reason(e));
             }
-            handle_existing_link(&link_path, config)?;
         }
+    }
 
-        ensure_parent_directory_exists(&link_path, config.debug)?;
+    Ok(report)
+}
 
-        info!(
-            "Creating symbolic link from '{}' to '{}'",
-            src.display(),
-            link_path.display()
-        );
+/// How [`place`] left one link path.
+enum Placed {
+    Created,
+    Replaced,
+    Skipped(&'static str),
+}
 
-        if !config.debug {
-            create_symlink(src, &link_path)?;
+/// Resolves any conflict at `link_path` and places `src` there.
+fn place(
+    src: &Path,
+    link_path: &Path,
+    config: &Config,
+    policy: &mut ConflictPolicy,
+) -> Result<Placed, SymlinkError> {
+    debug!(
+        "Processing: Source: {}, Link: {}",
+        src.display(),
+        link_path.display()
+    );
+
+    // symlink_metadata also catches dangling symlinks
+    let existed = fs::symlink_metadata(link_path).is_ok();
+    if existed {
+        if is_current(link_path, src, config.mode)? {
+            info!(
+                "Link already exists and is correct: {}",
+                link_path.display()
+            );
+            return Ok(Placed::Skipped("already up to date"));
+        }
+        if !resolve_conflict(link_path, config, policy)? {
+            info!("Skipping existing path: {}", link_path.display());
+            return Ok(Placed::Skipped("kept the existing path"));
         }
     }
 
-    Ok(())
+    ensure_parent_directory_exists(link_path, config.debug)?;
+
+    info!(
+        "Creating {} from '{}' to '{}'",
+        config.mode,
+        src.display(),
+        link_path.display()
+    );
+
+    if !config.debug {
+        create_link(src, link_path, config.mode)?;
+    }
+    Ok(match existed {
+        true => Placed::Replaced,
+        false => Placed::Created,
+    })
+}
+
+/// Whether `link_path` already holds what `mode` would create for `src`.
+pub(crate) fn is_current(
+    link_path: &Path,
+    src: &Path,
+    mode: LinkMode,
+) -> Result<bool, SymlinkError> {
+    match mode {
+        LinkMode::Symlink => is_correct_symlink(link_path, src),
+        LinkMode::Hardlink | LinkMode::Copy if link_path.is_symlink() => Ok(false),
+        LinkMode::Hardlink => is_same_tree(link_path, src, &is_same_file),
+        LinkMode::Copy => is_same_tree(link_path, src, &|a, b| Ok(fs::read(a)? == fs::read(b)?)),
+    }
+}
+
+/// Compares two files, or two directories file by file, with `same`.
+fn is_same_tree(
+    a: &Path,
+    b: &Path,
+    same: &dyn Fn(&Path, &Path) -> io::Result<bool>,
+) -> Result<bool, SymlinkError> {
+    if a.is_dir() != b.is_dir() {
+        return Ok(false);
+    }
+    if !b.is_dir() {
+        return Ok(same(a, b)?);
+    }
+
+    let mut a_entries = fs::read_dir(a)?.count();
+    for entry in fs::read_dir(b)? {
+        let entry = entry?;
+        let a_entry = a.join(entry.file_name());
+        // Links inside a tree are placed as links, so only their targets
+        // are compared, which also keeps a link to a parent from looping
+        let same_entry = if entry.file_type()?.is_symlink() {
+            fs::read_link(&a_entry).ok() == Some(fs::read_link(entry.path())?)
+        } else {
+            is_same_tree(&a_entry, &entry.path(), same)?
+        };
+        if !same_entry {
+            return Ok(false);
+        }
+        a_entries = a_entries.saturating_sub(1);
+    }
+    Ok(a_entries == 0)
+}
+
+#[cfg(unix)]
+fn is_same_file(a: &Path, b: &Path) -> io::Result<bool> {
+    use std::os::unix::fs::MetadataExt;
+    let (a, b) = (fs::metadata(a)?, fs::metadata(b)?);
+    Ok(a.dev() == b.dev() && a.ino() == b.ino())
+}
+
+#[cfg(not(unix))]
+fn is_same_file(_a: &Path, _b: &Path) -> io::Result<bool> {
+    Ok(false)
 }
 
 fn is_correct_symlink(link_path: &Path, src: &Path) -> Result<bool, SymlinkError> {
@@ -63,40 +191,99 @@ fn is_correct_symlink(link_path: &Path, src: &Path) -> Result<bool, SymlinkError
     }
 }
 
-fn handle_existing_link(link_path: &Path, config: &Config) -> Result<(), SymlinkError> {
-    if config.force {
-        backup_existing_path(link_path, config, None)?;
-    } else {
-        if !prompt_user_for_overwrite()? {
-            info!("Skipping existing link: {}", link_path.display());
-            return Ok(());
+/// Clears `link_path` according to `policy`, returning whether to go on and
+/// create the link. A prompt answered "for all" replaces `policy`.
+fn resolve_conflict(
+    link_path: &Path,
+    config: &Config,
+    policy: &mut ConflictPolicy,
+) -> Result<bool, SymlinkError> {
+    let action = match *policy {
+        ConflictPolicy::Prompt if io::stdin().is_terminal() => {
+            let (action, for_all) = prompt_for_conflict(link_path)?;
+            if for_all {
+                *policy = action;
+            }
+            action
         }
+        ConflictPolicy::Prompt => ConflictPolicy::FailFast,
+        action => action,
+    };
+
+    match action {
+        ConflictPolicy::Skip => Ok(false),
+        ConflictPolicy::Overwrite => {
+            remove_existing_path(link_path, config)?;
+            Ok(true)
+        }
+        ConflictPolicy::Backup => {
+            backup_existing_path(link_path, config)?;
+            Ok(true)
+        }
+        ConflictPolicy::Prompt | ConflictPolicy::FailFast => {
+            Err(SymlinkError::LinkExists(link_path.to_path_buf()))
+        }
+    }
+}
 
-        let mut backup_name = String::new();
-        print!("Enter backup name (press Enter for timestamp): ");
+/// Asks how to resolve one conflict. Answering in uppercase applies the
+/// choice to every remaining conflict.
+fn prompt_for_conflict(link_path: &Path) -> Result<(ConflictPolicy, bool), SymlinkError> {
+    loop {
+        print!(
+            "'{}' already exists. [s]kip (default), [o]verwrite, [b]ackup or [q]uit? \
+             (uppercase applies to all) ",
+            link_path.display()
+        );
         io::stdout().flush()?;
-        io::stdin().read_line(&mut backup_name)?;
-        let backup_name = if backup_name.trim().is_empty() {
-            None
-        } else {
-            Some(backup_name.trim().to_string())
-        };
 
-        backup_existing_path(link_path, config, backup_name)?;
+        let mut input = String::new();
+        io::stdin().read_line(&mut input)?;
+        if let Some(answer) = parse_conflict_answer(input.trim())? {
+            return Ok(answer);
+        }
+    }
+}
+
+/// Reads one prompt answer, or `None` to ask again. Just pressing enter
+/// skips, as the prompt shows.
+fn parse_conflict_answer(answer: &str) -> Result<Option<(ConflictPolicy, bool)>, SymlinkError> {
+    let for_all = answer.chars().any(|c| c.is_uppercase());
+    let action = match answer.to_lowercase().as_str() {
+        "s" | "skip" | "" => ConflictPolicy::Skip,
+        "o" | "overwrite" => ConflictPolicy::Overwrite,
+        "b" | "backup" => ConflictPolicy::Backup,
+        "q" | "quit" => return Err(SymlinkError::UserAbort),
+        _ => return Ok(None),
+    };
+    Ok(Some((action, for_all)))
+}
+
+pub(crate) fn remove_existing_path(link_path: &Path, config: &Config) -> Result<(), SymlinkError> {
+    info!("Removing existing path '{}'", link_path.display());
+    if config.debug {
+        return Ok(());
+    }
+
+    let metadata = fs::symlink_metadata(link_path)?;
+    if metadata.is_dir() {
+        fs::remove_dir_all(link_path)?;
+    } else if let Err(e) = fs::remove_file(link_path) {
+        // Windows directory symlinks must be removed as directories
+        if cfg!(unix) {
+            return Err(e.into());
+        }
+        fs::remove_dir(link_path)?;
     }
     Ok(())
 }
 
-fn backup_existing_path(
-    link_path: &Path,
-    config: &Config,
-    backup_name: Option<String>,
-) -> Result<(), SymlinkError> {
+pub(crate) fn backup_existing_path(link_path: &Path, config: &Config) -> Result<(), SymlinkError> {
     if config.debug {
         return Ok(());
     }
 
-    let backup_path = generate_backup_path(link_path, backup_name)?;
+    let backup_path = generate_backup_path(link_path)?;
     info!(
         "Moving existing path '{}' to backup location '{}'",
         link_path.display(),
@@ -104,6 +291,9 @@ fn backup_existing_path(
     );
 
     fs::rename(link_path, &backup_path).map_err(|e| {
+        if e.kind() == io::ErrorKind::PermissionDenied {
+            return SymlinkError::Io(e);
+        }
         SymlinkError::IoError(format!(
             "Failed to move '{}' to backup location '{}': {}",
             link_path.display(),
@@ -115,10 +305,7 @@ fn backup_existing_path(
     Ok(())
 }
 
-fn generate_backup_path(
-    original_path: &Path,
-    backup_name: Option<String>,
-) -> Result<PathBuf, SymlinkError> {
+fn generate_backup_path(original_path: &Path) -> Result<PathBuf, SymlinkError> {
     let parent = original_path.parent().ok_or_else(|| {
         SymlinkError::PathError("Cannot determine parent directory for backup".to_string())
     })?;
@@ -127,28 +314,18 @@ fn generate_backup_path(
         SymlinkError::PathError("Cannot determine file name for backup".to_string())
     })?;
 
-    let backup_name = match backup_name {
-        Some(name) => name,
-        None => {
-            let timestamp = Local::now().format("%Y%m%d_%H%M%S").to_string();
-            format!("{}_backup_{}", original_name.to_string_lossy(), timestamp)
-        }
-    };
-
-    Ok(parent.join(backup_name))
+    let timestamp = Local::now().format("%Y%m%d_%H%M%S");
+    Ok(parent.join(format!(
+        "{}_backup_{}",
+        original_name.to_string_lossy(),
+        timestamp
+    )))
 }
 
-fn prompt_user_for_overwrite() -> Result<bool, SymlinkError> {
-    print!("Do you want to overwrite it? [y/N] ");
-    io::stdout().flush()?;
-
-    let mut input = String::new();
-    io::stdin().read_line(&mut input)?;
-
-    Ok(input.trim().eq_ignore_ascii_case("y"))
-}
-
-fn ensure_parent_directory_exists(path: &Path, debug_mode: bool) -> Result<(), SymlinkError> {
+pub(crate) fn ensure_parent_directory_exists(
+    path: &Path,
+    debug_mode: bool,
+) -> Result<(), SymlinkError> {
     if let Some(parent) = path.parent() {
         if !parent.exists() {
             debug!("Creating parent directory: {}", parent.display());
@@ -160,9 +337,114 @@ fn ensure_parent_directory_exists(path: &Path, debug_mode: bool) -> Result<(), S
     Ok(())
 }
 
+pub(crate) fn create_link(src: &Path, dst: &Path, mode: LinkMode) -> Result<(), SymlinkError> {
+    match mode {
+        LinkMode::Symlink => match create_symlink(src, dst) {
+            Err(e) if symlinks_unavailable(&e) => {
+                warn!(
+                    "Symlinks are not available here ({}), copying '{}' instead",
+                    e,
+                    src.display()
+                );
+                copy_tree(src, dst)
+            }
+            result => result,
+        },
+        LinkMode::Hardlink => hardlink_tree(src, dst),
+        LinkMode::Copy => copy_tree(src, dst),
+    }
+}
+
+/// Whether symlinks cannot be made here at all. Permission errors are left
+/// to the elevated retry instead, since copying would hide them.
+fn symlinks_unavailable(error: &SymlinkError) -> bool {
+    match error {
+        SymlinkError::InsufficientPrivileges => true,
+        SymlinkError::Io(e) => e.kind() == io::ErrorKind::Unsupported,
+        _ => false,
+    }
+}
+
+fn hardlink_tree(src: &Path, dst: &Path) -> Result<(), SymlinkError> {
+    if !src.is_dir() {
+        return Ok(fs::hard_link(src, dst)?);
+    }
+
+    fs::create_dir_all(dst)?;
+    for entry in fs::read_dir(src)? {
+        let entry = entry?;
+        let dst = dst.join(entry.file_name());
+        if entry.file_type()?.is_symlink() {
+            // hard_link does not follow, so this links the link itself
+            fs::hard_link(entry.path(), dst)?;
+        } else {
+            hardlink_tree(&entry.path(), &dst)?;
+        }
+    }
+    Ok(())
+}
+
+/// Copies files and directories along with their mode bits, and their
+/// owner where the current user is allowed to set it. Symlinks inside a
+/// directory are recreated as links rather than followed.
+fn copy_tree(src: &Path, dst: &Path) -> Result<(), SymlinkError> {
+    let metadata = fs::metadata(src)?;
+    if !metadata.is_dir() {
+        // fs::copy already carries the mode bits over
+        fs::copy(src, dst)?;
+        return keep_owner(&metadata, dst);
+    }
+
+    fs::create_dir_all(dst)?;
+    for entry in fs::read_dir(src)? {
+        let entry = entry?;
+        let dst = dst.join(entry.file_name());
+        if entry.file_type()?.is_symlink() {
+            copy_symlink(&entry.path(), &dst)?;
+        } else {
+            copy_tree(&entry.path(), &dst)?;
+        }
+    }
+    // Set last so a read-only directory can still be filled
+    fs::set_permissions(dst, metadata.permissions())?;
+    keep_owner(&metadata, dst)
+}
+
+#[cfg(unix)]
+fn copy_symlink(link: &Path, dst: &Path) -> Result<(), SymlinkError> {
+    Ok(std::os::unix::fs::symlink(fs::read_link(link)?, dst)?)
+}
+
+#[cfg(windows)]
+fn copy_symlink(link: &Path, dst: &Path) -> Result<(), SymlinkError> {
+    let target = fs::read_link(link)?;
+    // Windows keeps file and directory links apart, so follow it to pick
+    if link.is_dir() {
+        std::os::windows::fs::symlink_dir(target, dst)?;
+    } else {
+        std::os::windows::fs::symlink_file(target, dst)?;
+    }
+    Ok(())
+}
+
+#[cfg(unix)]
+fn keep_owner(metadata: &fs::Metadata, dst: &Path) -> Result<(), SymlinkError> {
+    use std::os::unix::fs::MetadataExt;
+    match std::os::unix::fs::chown(dst, Some(metadata.uid()), Some(metadata.gid())) {
+        // Only root may give files away; the copy stays with the current user
+        Err(e) if e.kind() == io::ErrorKind::PermissionDenied => Ok(()),
+        result => Ok(result?),
+    }
+}
+
+#[cfg(not(unix))]
+fn keep_owner(_metadata: &fs::Metadata, _dst: &Path) -> Result<(), SymlinkError> {
+    Ok(())
+}
+
 #[cfg(unix)]
-fn create_symlink(src: &Path, dst: &Path) -> Result<(), std::io::Error> {
-    std::os::unix::fs::symlink(src, dst)
+fn create_symlink(src: &Path, dst: &Path) -> Result<(), SymlinkError> {
+    Ok(std::os::unix::fs::symlink(src, dst)?)
 }
 
 #[cfg(windows)]
@@ -179,3 +461,36 @@ fn create_symlink(src: &Path, dst: &Path) -> Result<(), SymlinkError> {
         Err(e) => Err(SymlinkError::Io(e)),
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_empty_conflict_answer_skips() {
+        let answer = |input| parse_conflict_answer(input).map_err(|e| e.to_string());
+        assert_eq!(answer(""), Ok(Some((ConflictPolicy::Skip, false))));
+        assert_eq!(answer("O"), Ok(Some((ConflictPolicy::Overwrite, true))));
+        assert_eq!(answer("maybe"), Ok(None));
+        assert!(matches!(
+            parse_conflict_answer("quit"),
+            Err(SymlinkError::UserAbort)
+        ));
+    }
+
+    #[test]
+    fn test_only_missing_support_falls_back_to_copy() {
+        let io_error = |error: io::Error| SymlinkError::Io(error);
+        assert!(symlinks_unavailable(&SymlinkError::InsufficientPrivileges));
+        assert!(symlinks_unavailable(&io_error(
+            io::ErrorKind::Unsupported.into()
+        )));
+        // EPERM and EACCES both go to the elevated retry instead
+        assert!(!symlinks_unavailable(&io_error(
+            io::Error::from_raw_os_error(1)
+        )));
+        assert!(!symlinks_unavailable(&io_error(
+            io::ErrorKind::PermissionDenied.into()
+        )));
+    }
+}
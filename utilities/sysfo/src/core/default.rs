@@ -1,33 +1,58 @@
-use super::{process, terminal, time, traits::FormatHelpers, Error};
-use sysinfo::System;
-
-#[derive(Debug)]
+use super::{power, process, terminal, thermal, time, traits::FormatHelpers, Error};
+use sysinfo::{ProcessRefreshKind, RefreshKind, System};
+
+/// Gathers system information, probing only what was asked for.
+///
+/// Time is always read since it costs nothing; everything else is
+/// opt-in so callers don't pay for a full `System` refresh.
+///
+/// ```no_run
+/// use sysfo::prelude::*;
+///
+/// let info = Fetcher::new().with_hardware().with_battery();
+/// println!("{}", info.fetch());
+/// ```
+#[derive(Debug, Default)]
 pub struct Fetcher {
     pub time: time::Info,
-    pub process: process::Info,
+    pub process: Option<process::Info>,
+    pub power: Option<power::Info>,
+    pub thermal: Option<thermal::Info>,
 }
 
-impl Default for Fetcher {
-    fn default() -> Self {
-        Self::new().unwrap_or_else(|e| {
-            logline::error!("Failed to create Fetcher: {}", e);
-            Self {
-                time: time::Info::default(),
-                process: process::Info::default(),
-            }
-        })
+impl Fetcher {
+    pub fn new() -> Self {
+        Self::default()
     }
-}
 
-impl Fetcher {
-    pub fn new() -> Result<Self, Error> {
-        let mut system = System::new_all();
-        system.refresh_all();
-
-        Ok(Self {
-            time: time::Info::default(),
-            process: process::Info::new(&system)?,
-        })
+    /// Runs every probe.
+    pub fn all() -> Self {
+        Self::new().with_process().with_hardware().with_battery()
+    }
+
+    /// Reads the current process and the shell it was started from.
+    pub fn with_process(mut self) -> Self {
+        let system = System::new_with_specifics(
+            RefreshKind::nothing().with_processes(ProcessRefreshKind::everything()),
+        );
+        self.process = process::Info::new(&system)
+            .inspect_err(|e| logline::debug!("Skipping process info: {}", e))
+            .ok();
+        self
+    }
+
+    /// Reads temperature sensors and fans.
+    pub fn with_hardware(mut self) -> Self {
+        self.thermal = Some(thermal::Info::new());
+        self
+    }
+
+    /// Reads the first battery, if there is one.
+    pub fn with_battery(mut self) -> Self {
+        self.power = power::Info::new()
+            .inspect_err(|e| logline::debug!("Skipping power info: {}", e))
+            .ok();
+        self
     }
 
     pub fn fetcher(&self) -> String {
@@ -43,33 +68,37 @@ impl Fetcher {
         output.push_str(&format!("System Uptime: {}\n", self.time.uptime_fmt()));
         output.push_str(&format!("Time Zone    : {}\n", self.time.timezone));
 
+        let Some(process) = &self.process else {
+            return output;
+        };
+
         // Process section
         output.push_str("\nActive Process\n");
         output.push_str(&"-".repeat(40));
         output.push('\n');
-        output.push_str(&format!("ID           : {}\n", self.process.id));
-        output.push_str(&format!("Name         : {}\n", self.process.name));
-        output.push_str(&format!("User         : {}\n", self.process.user));
-        output.push_str(&format!("Path         : {}\n", self.process.path.display()));
-        output.push_str(&format!("Working Dir  : {}\n", self.process.cwd.display()));
+        output.push_str(&format!("ID           : {}\n", process.id));
+        output.push_str(&format!("Name         : {}\n", process.name));
+        output.push_str(&format!("User         : {}\n", process.user));
+        output.push_str(&format!("Path         : {}\n", process.path.display()));
+        output.push_str(&format!("Working Dir  : {}\n", process.cwd.display()));
 
         // Shell section
         output.push_str("\nShell Environment\n");
         output.push_str(&"-".repeat(40));
         output.push('\n');
-        output.push_str(&format!("Shell        : {}\n", self.process.shell.name));
+        output.push_str(&format!("Shell        : {}\n", process.shell.name));
         output.push_str(&format!(
             "Version      : {}\n",
-            self.process.shell.version.as_deref().unwrap_or("Unknown")
+            process.shell.version.as_deref().unwrap_or("Unknown")
         ));
         output.push_str(&format!(
             "Shell Path   : {}\n",
-            self.process.shell.path.display()
+            process.shell.path.display()
         ));
 
         // Shell configurations
         output.push_str("\nConfiguration Files:\n");
-        for path in &self.process.shell.conf {
+        for path in &process.shell.conf {
             output.push_str(&format!("  - {}\n", path.display()));
         }
 
@@ -89,25 +118,50 @@ impl Fetcher {
         output.push_str(&term.format_field("Time Zone", &self.time.timezone));
 
         // Process Section
-        output.push_str(&term.format_section("Process"));
-        output.push_str(&term.format_field("ID", &self.process.id.to_string()));
-        output.push_str(&term.format_field("Name", &self.process.name));
-        output.push_str(&term.format_field("User", &self.process.user));
-        output.push_str(&term.format_field("Path", &self.process.path.display().to_string()));
-        output.push_str(&term.format_field("Working Dir", &self.process.cwd.display().to_string()));
-
-        output.push_str(&term.format_section("Shell"));
-        output.push_str(&term.format_field("ID", &self.process.shell.id.to_string()));
-        output.push_str(&term.format_field("Name", &self.process.shell.name));
-        output.push_str(&term.format_field("Path", &self.process.shell.path.display().to_string()));
-        output.push_str(&term.format_field(
-            "Version",
-            self.process.shell.version.as_deref().unwrap_or("Unknown"),
-        ));
+        if let Some(process) = &self.process {
+            output.push_str(&term.format_section("Process"));
+            output.push_str(&term.format_field("ID", &process.id.to_string()));
+            output.push_str(&term.format_field("Name", &process.name));
+            output.push_str(&term.format_field("User", &process.user));
+            output.push_str(&term.format_field("Path", &process.path.display().to_string()));
+            output.push_str(&term.format_field("Working Dir", &process.cwd.display().to_string()));
+
+            output.push_str(&term.format_section("Shell"));
+            output.push_str(&term.format_field("ID", &process.shell.id.to_string()));
+            output.push_str(&term.format_field("Name", &process.shell.name));
+            output.push_str(&term.format_field("Path", &process.shell.path.display().to_string()));
+            output.push_str(&term.format_field(
+                "Version",
+                process.shell.version.as_deref().unwrap_or("Unknown"),
+            ));
+        }
 
-        output.push_str(&term.format_section("Shell Configurations"));
-        for path in &self.process.shell.conf {
-            output.push_str(&format!("  - {}\n", path.display()));
+        if let Some(power) = &self.power {
+            output.push_str(&term.format_section("Power"));
+            output.push_str(&term.format_field("Level", &power.level_fmt()));
+            output.push_str(&term.format_field("Status", &format!("{:?}", power.state)));
+            output.push_str(&term.format_field("Rate", &power.rate_fmt()));
+            output.push_str(&term.format_field("Remaining", &power.remaining_fmt()));
+        }
+
+        if let Some(thermal) = self.thermal.as_ref().filter(|t| !t.is_empty()) {
+            output.push_str(&term.format_section("Hardware"));
+            output.push_str(&term.format_field("Thermal", &thermal.status().to_string()));
+            for (label, kind) in [("CPU", thermal::Kind::Cpu), ("GPU", thermal::Kind::Gpu)] {
+                if let Some(sensor) = thermal.hottest(kind) {
+                    output.push_str(&term.format_field(label, &sensor.temperature_fmt()));
+                }
+            }
+            for fan in &thermal.fans {
+                output.push_str(&term.format_field(&fan.label, &format!("{} RPM", fan.rpm)));
+            }
+        }
+
+        if let Some(process) = &self.process {
+            output.push_str(&term.format_section("Shell Configurations"));
+            for path in &process.shell.conf {
+                output.push_str(&format!("  - {}\n", path.display()));
+            }
         }
 
         output
@@ -115,7 +169,7 @@ impl Fetcher {
 }
 
 pub fn init() -> Result<Fetcher, Error> {
-    Fetcher::new()
+    Ok(Fetcher::all())
 }
 
 // Update test function to handle potential errors
@@ -1,8 +1,11 @@
 use thiserror::Error;
-use super::process;
+use super::{power, process};
 
 #[derive(Debug, Error)]
 pub enum Error {
     #[error("Process error: {0}")]
     Process(#[from] process::Error),
+
+    #[error("Power error: {0}")]
+    Power(#[from] power::Error),
 }
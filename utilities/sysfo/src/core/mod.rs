@@ -1,10 +1,13 @@
 // pub mod battery;
 mod default;
 mod error;
-mod process;
-mod time;
-mod terminal;
+pub mod power;
+pub mod process;
+pub mod time;
+pub mod terminal;
+pub mod thermal;
 mod traits;
 
 pub use default::*;
 pub use error::Error;
+pub use traits::FormatHelpers;
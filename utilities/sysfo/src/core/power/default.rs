@@ -0,0 +1,79 @@
+use super::{Error, History, Sample};
+use battery::{Manager, State};
+use chrono::Local;
+use std::time::Duration;
+
+#[derive(Debug, Clone)]
+pub struct Info {
+    pub level: f32,
+    pub state: State,
+    /// Change in charge per hour from recorded history; negative while draining.
+    pub rate: Option<f32>,
+    pub time_to_empty: Option<Duration>,
+    pub time_to_full: Option<Duration>,
+}
+
+impl Info {
+    /// Reads the first battery and records the reading in the history, so
+    /// estimates improve the more often sysfo runs.
+    pub fn new() -> Result<Self, Error> {
+        let battery = Manager::new()?
+            .batteries()?
+            .next()
+            .ok_or(Error::NoBattery)??;
+        let level = battery.state_of_charge().value;
+        let state = battery.state();
+
+        let mut history = History::load()?;
+        history.record(Sample::new(Local::now().timestamp(), level, state));
+        if let Err(e) = history.save() {
+            logline::warn!("Failed to save battery history: {}", e);
+        }
+
+        // Prefer the observed trend and fall back to the driver's estimate
+        let instant = |time: Option<battery::units::Time>| {
+            time.map(|time| Duration::from_secs_f32(time.value.max(0.0)))
+        };
+        let time_to_empty = match state {
+            State::Discharging => history
+                .time_to_empty()
+                .or_else(|| instant(battery.time_to_empty())),
+            _ => None,
+        };
+        let time_to_full = match state {
+            State::Charging => history
+                .time_to_full()
+                .or_else(|| instant(battery.time_to_full())),
+            _ => None,
+        };
+
+        Ok(Self {
+            level,
+            state,
+            rate: history.rate(),
+            time_to_empty,
+            time_to_full,
+        })
+    }
+
+    pub fn level_fmt(&self) -> String {
+        format!("{}%", (self.level * 100.0).round() as i32)
+    }
+
+    pub fn rate_fmt(&self) -> String {
+        match self.rate {
+            Some(rate) => format!("{:+.1}%/h", rate * 100.0),
+            None => "Unknown".to_string(),
+        }
+    }
+
+    pub fn remaining_fmt(&self) -> String {
+        let (time, label) = match (self.time_to_empty, self.time_to_full) {
+            (Some(time), _) => (time, "until empty"),
+            (_, Some(time)) => (time, "until full"),
+            _ => return "Unknown".to_string(),
+        };
+        let minutes = time.as_secs() / 60;
+        format!("{}h {:02}m {}", minutes / 60, minutes % 60, label)
+    }
+}
@@ -0,0 +1,17 @@
+use thiserror::Error;
+
+/// Custom error type for battery and power history operations.
+#[derive(Debug, Error)]
+pub enum Error {
+    #[error("Battery error: {0}")]
+    Battery(#[from] battery::Error),
+
+    #[error("No battery was found")]
+    NoBattery,
+
+    #[error("Cache directory could not be determined")]
+    CacheDir,
+
+    #[error("Failed to access battery history: {0}")]
+    History(#[from] std::io::Error),
+}
@@ -0,0 +1,225 @@
+use super::Error;
+use battery::State;
+use directories::BaseDirs;
+use std::{fs, path::PathBuf, time::Duration};
+
+/// Samples older than this are dropped when the history is saved.
+const WINDOW: i64 = 24 * 60 * 60;
+
+/// Samples closer together than this replace each other.
+const MIN_INTERVAL: i64 = 30;
+
+/// Samples must span at least this long before a rate is trusted.
+const MIN_SPAN: i64 = 5 * 60;
+
+/// A battery reading at a point in time.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Sample {
+    /// Unix timestamp in seconds.
+    pub timestamp: i64,
+    /// State of charge between 0 and 1.
+    pub level: f32,
+    pub charging: bool,
+}
+
+impl Sample {
+    pub fn new(timestamp: i64, level: f32, state: State) -> Self {
+        Self {
+            timestamp,
+            level,
+            charging: matches!(state, State::Charging),
+        }
+    }
+
+    fn parse(line: &str) -> Option<Self> {
+        let mut fields = line.split_whitespace();
+        Some(Self {
+            timestamp: fields.next()?.parse().ok()?,
+            level: fields.next()?.parse().ok()?,
+            charging: fields.next()? == "charging",
+        })
+    }
+
+    fn line(&self) -> String {
+        let state = if self.charging {
+            "charging"
+        } else {
+            "discharging"
+        };
+        format!("{} {:.4} {}\n", self.timestamp, self.level, state)
+    }
+}
+
+/// Recent battery samples, persisted to `$XDG_CACHE_HOME/sysfo/battery.log`.
+#[derive(Debug, Default, Clone)]
+pub struct History {
+    pub path: PathBuf,
+    pub samples: Vec<Sample>,
+}
+
+impl History {
+    pub fn load() -> Result<Self, Error> {
+        let path = BaseDirs::new()
+            .map(|dirs| dirs.cache_dir().join("sysfo").join("battery.log"))
+            .ok_or(Error::CacheDir)?;
+
+        let samples = match fs::read_to_string(&path) {
+            Ok(content) => content.lines().filter_map(Sample::parse).collect(),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Vec::new(),
+            Err(e) => return Err(e.into()),
+        };
+
+        Ok(Self { path, samples })
+    }
+
+    pub fn save(&self) -> Result<(), Error> {
+        if let Some(parent) = self.path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        let content: String = self.samples.iter().map(Sample::line).collect();
+        fs::write(&self.path, content)?;
+        Ok(())
+    }
+
+    /// Appends a sample, replacing the last one if it was taken moments ago,
+    /// and drops samples that fell out of the window.
+    pub fn record(&mut self, sample: Sample) {
+        if let Some(last) = self.samples.last() {
+            if sample.timestamp - last.timestamp < MIN_INTERVAL {
+                self.samples.pop();
+            }
+        }
+        self.samples.push(sample);
+        self.samples
+            .retain(|old| sample.timestamp - old.timestamp <= WINDOW);
+    }
+
+    /// Samples since the battery last switched between charging and draining.
+    fn current_run(&self) -> &[Sample] {
+        let Some(last) = self.samples.last() else {
+            return &[];
+        };
+        let start = self
+            .samples
+            .iter()
+            .rposition(|sample| sample.charging != last.charging)
+            .map_or(0, |index| index + 1);
+        &self.samples[start..]
+    }
+
+    /// Change in charge per hour over the current run, as a fraction of a full
+    /// battery; negative while draining. Uses a least-squares fit so a single
+    /// noisy reading doesn't swing the estimate.
+    pub fn rate(&self) -> Option<f32> {
+        let run = self.current_run();
+        let (first, last) = (run.first()?, run.last()?);
+        if last.timestamp - first.timestamp < MIN_SPAN {
+            return None;
+        }
+
+        let n = run.len() as f64;
+        let hours = |sample: &Sample| (sample.timestamp - first.timestamp) as f64 / 3600.0;
+        let mean_x = run.iter().map(hours).sum::<f64>() / n;
+        let mean_y = run.iter().map(|sample| sample.level as f64).sum::<f64>() / n;
+
+        let (covariance, variance) = run.iter().fold((0.0, 0.0), |(cov, var), sample| {
+            let dx = hours(sample) - mean_x;
+            (cov + dx * (sample.level as f64 - mean_y), var + dx * dx)
+        });
+
+        (variance > 0.0).then(|| (covariance / variance) as f32)
+    }
+
+    /// Time until the battery is empty at the observed drain rate.
+    pub fn time_to_empty(&self) -> Option<Duration> {
+        let last = self.samples.last().filter(|sample| !sample.charging)?;
+        let rate = self.rate().filter(|rate| *rate < 0.0)?;
+        hours(last.level / -rate)
+    }
+
+    /// Time until the battery is full at the observed charge rate.
+    pub fn time_to_full(&self) -> Option<Duration> {
+        let last = self.samples.last().filter(|sample| sample.charging)?;
+        let rate = self.rate().filter(|rate| *rate > 0.0)?;
+        hours((1.0 - last.level) / rate)
+    }
+}
+
+/// `None` when a near-flat rate puts the estimate beyond any `Duration`.
+fn hours(value: f32) -> Option<Duration> {
+    Duration::try_from_secs_f32(value.max(0.0) * 3600.0).ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn history(samples: &[(i64, f32, bool)]) -> History {
+        History {
+            path: PathBuf::new(),
+            samples: samples
+                .iter()
+                .map(|&(timestamp, level, charging)| Sample {
+                    timestamp,
+                    level,
+                    charging,
+                })
+                .collect(),
+        }
+    }
+
+    #[test]
+    fn test_sample_parse_round_trips() {
+        let sample = Sample {
+            timestamp: 1700000000,
+            level: 0.5,
+            charging: true,
+        };
+        assert_eq!(Sample::parse(sample.line().trim()), Some(sample));
+        assert_eq!(
+            Sample::parse("1700000000 0.2500 discharging").map(|s| s.charging),
+            Some(false)
+        );
+        assert_eq!(Sample::parse("1700000000 full charging"), None);
+        assert_eq!(Sample::parse("1700000000 0.5"), None);
+    }
+
+    #[test]
+    fn test_draining_rate_and_time_to_empty() {
+        // 10% per hour, sampled every 30 minutes
+        let history = history(&[(0, 0.8, false), (1800, 0.75, false), (3600, 0.7, false)]);
+        let rate = history.rate().unwrap();
+        assert!((rate + 0.1).abs() < 1e-4);
+
+        let left = history.time_to_empty().unwrap().as_secs_f32() / 3600.0;
+        assert!((left - 7.0).abs() < 0.01);
+        assert_eq!(history.time_to_full(), None);
+    }
+
+    #[test]
+    fn test_charging_time_to_full() {
+        let history = history(&[(0, 0.5, true), (3600, 0.75, true)]);
+        let left = history.time_to_full().unwrap().as_secs_f32() / 3600.0;
+        assert!((left - 1.0).abs() < 0.01);
+        assert_eq!(history.time_to_empty(), None);
+    }
+
+    #[test]
+    fn test_flat_and_sparse_histories_have_no_estimate() {
+        let flat = history(&[(0, 0.6, false), (3600, 0.6, false)]);
+        assert_eq!(flat.rate(), Some(0.0));
+        assert_eq!(flat.time_to_empty(), None);
+
+        let single = history(&[(0, 0.6, false)]);
+        assert_eq!(single.rate(), None);
+        assert_eq!(single.time_to_empty(), None);
+        assert_eq!(history(&[]).rate(), None);
+    }
+
+    #[test]
+    fn test_near_flat_rate_does_not_overflow() {
+        assert_eq!(hours(f32::MAX), None);
+        assert_eq!(hours(f32::INFINITY), None);
+        assert_eq!(hours(-1.0), Some(Duration::ZERO));
+    }
+}
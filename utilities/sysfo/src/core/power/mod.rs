@@ -0,0 +1,7 @@
+mod default;
+pub mod error;
+mod history;
+
+pub use default::Info;
+pub use error::Error;
+pub use history::{History, Sample};
@@ -0,0 +1,180 @@
+use super::{fans, Fan};
+use std::fmt::{self, Display, Formatter};
+use sysinfo::Components;
+
+/// Temperature classification for a sensor.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Status {
+    Normal,
+    Warm,
+    Critical,
+}
+
+impl Display for Status {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        let status = match self {
+            Self::Normal => "Normal",
+            Self::Warm => "Warm",
+            Self::Critical => "Critical",
+        };
+        write!(f, "{}", status)
+    }
+}
+
+/// Temperatures in °C at which sensors are classed as warm or critical.
+/// A sensor's own critical point takes precedence when it reports one.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Thresholds {
+    pub warm: f32,
+    pub critical: f32,
+}
+
+impl Default for Thresholds {
+    fn default() -> Self {
+        Self {
+            warm: 70.0,
+            critical: 90.0,
+        }
+    }
+}
+
+impl Thresholds {
+    pub fn classify(&self, temperature: f32, critical: Option<f32>) -> Status {
+        let critical = critical.filter(|c| *c > 0.0).unwrap_or(self.critical);
+        if temperature >= critical {
+            Status::Critical
+        } else if temperature >= self.warm.min(critical) {
+            Status::Warm
+        } else {
+            Status::Normal
+        }
+    }
+}
+
+/// What a sensor measures, guessed from its label.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Kind {
+    Cpu,
+    Gpu,
+    Other,
+}
+
+impl Kind {
+    fn from_label(label: &str) -> Self {
+        let label = label.to_lowercase();
+        if ["gpu", "amdgpu", "nouveau", "radeon", "nvidia"]
+            .iter()
+            .any(|name| label.contains(name))
+        {
+            Self::Gpu
+        } else if [
+            "cpu", "core", "package", "k10temp", "coretemp", "tctl", "tdie",
+        ]
+        .iter()
+        .any(|name| label.contains(name))
+        {
+            Self::Cpu
+        } else {
+            Self::Other
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Sensor {
+    pub label: String,
+    pub kind: Kind,
+    /// Current temperature in °C.
+    pub temperature: f32,
+    pub critical: Option<f32>,
+    pub status: Status,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct Info {
+    pub sensors: Vec<Sensor>,
+    pub fans: Vec<Fan>,
+}
+
+impl Info {
+    pub fn new() -> Self {
+        Self::with_thresholds(Thresholds::default())
+    }
+
+    pub fn with_thresholds(thresholds: Thresholds) -> Self {
+        let components = Components::new_with_refreshed_list();
+        let sensors = components
+            .list()
+            .iter()
+            .filter_map(|component| {
+                let temperature = component.temperature().filter(|t| t.is_finite())?;
+                let critical = component.critical();
+                Some(Sensor {
+                    label: component.label().to_string(),
+                    kind: Kind::from_label(component.label()),
+                    temperature,
+                    critical,
+                    status: thresholds.classify(temperature, critical),
+                })
+            })
+            .collect();
+
+        Self {
+            sensors,
+            fans: fans::read(),
+        }
+    }
+
+    /// The hottest classification across all sensors.
+    pub fn status(&self) -> Status {
+        self.sensors
+            .iter()
+            .map(|sensor| sensor.status)
+            .max()
+            .unwrap_or(Status::Normal)
+    }
+
+    /// The hottest sensor of the given kind.
+    pub fn hottest(&self, kind: Kind) -> Option<&Sensor> {
+        self.sensors
+            .iter()
+            .filter(|sensor| sensor.kind == kind)
+            .max_by(|a, b| a.temperature.total_cmp(&b.temperature))
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.sensors.is_empty() && self.fans.is_empty()
+    }
+}
+
+impl Sensor {
+    pub fn temperature_fmt(&self) -> String {
+        format!("{:.1}°C ({})", self.temperature, self.status)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_classify_boundaries() {
+        let thresholds = Thresholds::default();
+        assert_eq!(thresholds.classify(69.9, None), Status::Normal);
+        assert_eq!(thresholds.classify(70.0, None), Status::Warm);
+        assert_eq!(thresholds.classify(89.9, None), Status::Warm);
+        assert_eq!(thresholds.classify(90.0, None), Status::Critical);
+        // An unset critical point falls back to the threshold
+        assert_eq!(thresholds.classify(90.0, Some(0.0)), Status::Critical);
+    }
+
+    #[test]
+    fn test_classify_prefers_sensor_critical_point() {
+        let thresholds = Thresholds::default();
+        assert_eq!(thresholds.classify(79.9, Some(80.0)), Status::Warm);
+        assert_eq!(thresholds.classify(80.0, Some(80.0)), Status::Critical);
+        // A critical point below the warm threshold has no warm band
+        assert_eq!(thresholds.classify(59.9, Some(60.0)), Status::Normal);
+        assert_eq!(thresholds.classify(60.0, Some(60.0)), Status::Critical);
+    }
+}
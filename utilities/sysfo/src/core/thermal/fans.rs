@@ -0,0 +1,51 @@
+/// A fan and its current speed.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Fan {
+    pub label: String,
+    pub rpm: u32,
+}
+
+/// sysinfo doesn't expose fans, so they are read from hwmon on Linux.
+#[cfg(target_os = "linux")]
+pub fn read() -> Vec<Fan> {
+    use std::fs;
+
+    let read = |path: std::path::PathBuf| fs::read_to_string(path).ok();
+    let Ok(devices) = fs::read_dir("/sys/class/hwmon") else {
+        return Vec::new();
+    };
+
+    let mut fans = Vec::new();
+    for device in devices.flatten() {
+        let dir = device.path();
+        let chip = read(dir.join("name")).unwrap_or_default();
+        let Ok(entries) = fs::read_dir(&dir) else {
+            continue;
+        };
+
+        for entry in entries.flatten() {
+            let name = entry.file_name().to_string_lossy().to_string();
+            let Some(index) = name
+                .strip_prefix("fan")
+                .and_then(|rest| rest.strip_suffix("_input"))
+            else {
+                continue;
+            };
+            let Some(rpm) = read(entry.path()).and_then(|value| value.trim().parse().ok()) else {
+                continue;
+            };
+            let label = read(dir.join(format!("fan{}_label", index)))
+                .map(|label| label.trim().to_string())
+                .unwrap_or_else(|| format!("{} fan{}", chip.trim(), index));
+            fans.push(Fan { label, rpm });
+        }
+    }
+
+    fans.sort_by(|a, b| a.label.cmp(&b.label));
+    fans
+}
+
+#[cfg(not(target_os = "linux"))]
+pub fn read() -> Vec<Fan> {
+    Vec::new()
+}
@@ -0,0 +1,5 @@
+mod default;
+mod fans;
+
+pub use default::{Info, Kind, Sensor, Status, Thresholds};
+pub use fans::Fan;
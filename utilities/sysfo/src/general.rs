@@ -1,11 +1,16 @@
+use chrono::{DateTime, Local, TimeZone};
+use serde::Serialize;
 use std::{
     env::var,
-    fmt::{Display, Formatter, Result},
+    fmt::{self, Display, Formatter, Result},
+    process::Command,
 };
+use sysinfo::System;
 
-#[derive(Debug)]
+#[derive(Debug, Serialize)]
 pub struct General {
-    username: String,
+    pub username: String,
+    pub session: Session,
 }
 
 impl Default for General {
@@ -18,7 +23,10 @@ impl Default for General {
         }
 
         let username = get_current_user();
-        Self { username }
+        Self {
+            session: Session::new(&username),
+            username,
+        }
     }
 }
 
@@ -32,8 +40,133 @@ impl Display for General {
     fn fmt(&self, f: &mut Formatter<'_>) -> Result {
         let header = "General {";
         let username = format!("{:>16}: {}", "Username", self.username);
+        let session = format!("{:>16}: {}", "Session", self.session.kind);
+        let boot = format!("{:>16}: {}", "Booted", self.session.boot_fmt());
+        let uptime = format!("{:>16}: {}", "Uptime", self.session.uptime_fmt());
+        let users = format!("{:>16}: {}", "Users", self.session.users.join(", "));
         let footer = "}";
 
-        write!(f, "{}\n{}\n{}", header, username, footer)
+        write!(
+            f,
+            "{}\n{}\n{}\n{}\n{}\n{}\n{}",
+            header, username, session, boot, uptime, users, footer
+        )
+    }
+}
+
+/// How the current user is connected to the machine.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SessionKind {
+    X11,
+    Wayland,
+    Rdp,
+    Ssh,
+    Tty,
+    Unknown,
+}
+
+impl SessionKind {
+    /// Reads the session type from the environment the process inherited.
+    pub fn detect() -> Self {
+        let set = |name: &str| var(name).is_ok_and(|value| !value.is_empty());
+
+        if var("SESSIONNAME").is_ok_and(|name| name.starts_with("RDP-")) {
+            return Self::Rdp;
+        }
+        match var("XDG_SESSION_TYPE").as_deref() {
+            Ok("wayland") => return Self::Wayland,
+            Ok("x11") => return Self::X11,
+            Ok("tty") => return Self::Tty,
+            _ => {}
+        }
+        if set("WAYLAND_DISPLAY") {
+            Self::Wayland
+        } else if set("DISPLAY") {
+            Self::X11
+        } else if set("SSH_CONNECTION") || set("SSH_TTY") {
+            Self::Ssh
+        } else if cfg!(windows) && var("SESSIONNAME").is_ok_and(|name| name == "Console") {
+            Self::Tty
+        } else {
+            Self::Unknown
+        }
+    }
+}
+
+impl Display for SessionKind {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        let kind = match self {
+            Self::X11 => "X11",
+            Self::Wayland => "Wayland",
+            Self::Rdp => "RDP",
+            Self::Ssh => "SSH",
+            Self::Tty => "TTY",
+            Self::Unknown => "Unknown",
+        };
+        write!(f, "{}", kind)
+    }
+}
+
+/// When the machine booted and who is using it.
+#[derive(Debug, Clone, Serialize)]
+pub struct Session {
+    pub kind: SessionKind,
+    pub boot: DateTime<Local>,
+    /// Seconds since boot.
+    pub uptime: u64,
+    /// Users with an open login session, each listed once.
+    pub users: Vec<String>,
+}
+
+impl Session {
+    /// `current` stands in for the user list when it can't be read.
+    pub fn new(current: &str) -> Self {
+        let boot = Local
+            .timestamp_opt(System::boot_time() as i64, 0)
+            .single()
+            .unwrap_or_else(Local::now);
+        let mut users = logged_in_users();
+        if users.is_empty() {
+            users.push(current.to_string());
+        }
+
+        Self {
+            kind: SessionKind::detect(),
+            boot,
+            uptime: System::uptime(),
+            users,
+        }
+    }
+
+    pub fn boot_fmt(&self) -> String {
+        self.boot.format("%Y-%m-%d %H:%M").to_string()
+    }
+
+    pub fn uptime_fmt(&self) -> String {
+        let mut uptime = intime::Info::until_now(self.boot);
+        uptime.above_seconds().to_string()
     }
 }
+
+/// Lists users from `who`, or `query user` on Windows.
+fn logged_in_users() -> Vec<String> {
+    let output = if cfg!(windows) {
+        Command::new("query").arg("user").output()
+    } else {
+        Command::new("who").output()
+    };
+    let Ok(output) = output else {
+        return Vec::new();
+    };
+
+    let mut users: Vec<String> = String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .skip(usize::from(cfg!(windows)))
+        .filter_map(|line| line.split_whitespace().next())
+        .map(|user| user.trim_start_matches('>').to_string())
+        .collect();
+    users.sort();
+    users.dedup();
+    users
+}
@@ -1,31 +1,42 @@
+use crate::thermal;
 use std::fmt::{Display, Formatter, Result};
-use sysinfo::System;
+use sysinfo::{CpuRefreshKind, MemoryRefreshKind, RefreshKind, System};
 
 #[derive(Debug)]
 pub struct Hardware {
-    hostname: String,
-    arch: String,
-    cores: usize,
-    threads: usize,
-    memory: f64,
-    // battery: BatteryInfo,
+    pub hostname: String,
+    pub arch: String,
+    pub cpu: String,
+    pub cores: usize,
+    pub threads: usize,
+    /// Total memory in GiB.
+    pub memory: f64,
+    /// Temperature sensors and fans, classified against the default
+    /// thresholds.
+    pub thermal: thermal::Info,
 }
 
 impl Default for Hardware {
     fn default() -> Self {
-        let mut system = System::new_all();
-        system.refresh_all();
-        // TODO: System needs to be made static
-
-        // let battery =
+        // Only the CPU list and RAM are needed, not a full refresh
+        let system = System::new_with_specifics(
+            RefreshKind::nothing()
+                .with_cpu(CpuRefreshKind::nothing())
+                .with_memory(MemoryRefreshKind::nothing().with_ram()),
+        );
 
         Self {
             hostname: System::host_name().unwrap_or_default(),
-            arch: System::cpu_arch().unwrap_or_default(),
+            arch: System::cpu_arch(),
+            cpu: system
+                .cpus()
+                .first()
+                .map(|cpu| cpu.brand().trim().to_string())
+                .unwrap_or_default(),
             cores: system.physical_core_count().unwrap_or(0),
             threads: system.cpus().len(),
             memory: system.total_memory() as f64 / 2_f64.powi(30),
-            // battery,
+            thermal: thermal::Info::new(),
         }
     }
 }
@@ -45,16 +56,81 @@ impl Display for Hardware {
         let header = "Hardware {";
         let hostname = format!("{:>16}: {}", "Hostname", self.hostname);
         let arch = format!("{:>16}: {}", "Architecture", self.arch);
+        let cpu = format!("{:>16}: {}", "CPU", self.cpu);
         let cores = format!("{:>16}: {} [{} Threads]", "Cores", self.cores, self.threads);
         let memory = format!("{:>16}: {:.2} GB", "Memory", self.memory);
-        // let battery = format!("{:>16}: {}", "Battery", self.battery);
-        let battery = format!("{:>16}: {}", "Battery", "BatteryInfo::default().model");
         let footer = "}";
 
         write!(
             f,
-            "{}\n{}\n{}\n{}\n{}\n{}\n{}",
-            header, hostname, arch, cores, memory, battery, footer
-        )
+            "{}\n{}\n{}\n{}\n{}\n{}",
+            header, hostname, arch, cpu, cores, memory
+        )?;
+        self.thermal_fmt(f)?;
+        write!(f, "\n{}", footer)
+    }
+}
+
+impl Hardware {
+    /// The overall thermal status, then the hottest CPU and GPU sensors
+    /// and each fan.
+    fn thermal_fmt(&self, f: &mut Formatter<'_>) -> Result {
+        if self.thermal.is_empty() {
+            return write!(f, "\n{:>16}: Unknown", "Thermal");
+        }
+        write!(f, "\n{:>16}: {}", "Thermal", self.thermal.status())?;
+        for (label, kind) in [
+            ("CPU Temp", thermal::Kind::Cpu),
+            ("GPU Temp", thermal::Kind::Gpu),
+        ] {
+            if let Some(sensor) = self.thermal.hottest(kind) {
+                write!(f, "\n{:>16}: {}", label, sensor.temperature_fmt())?;
+            }
+        }
+        for fan in &self.thermal.fans {
+            write!(f, "\n{:>16}: {} RPM", fan.label, fan.rpm)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn hardware(thermal: thermal::Info) -> Hardware {
+        Hardware {
+            hostname: "lab".to_string(),
+            arch: "x86_64".to_string(),
+            cpu: "Test CPU".to_string(),
+            cores: 4,
+            threads: 8,
+            memory: 16.0,
+            thermal,
+        }
+    }
+
+    #[test]
+    fn test_display_includes_thermal_status() {
+        let thermal = thermal::Info {
+            sensors: vec![thermal::Sensor {
+                label: "coretemp Package id 0".to_string(),
+                kind: thermal::Kind::Cpu,
+                temperature: 75.0,
+                critical: None,
+                status: thermal::Status::Warm,
+            }],
+            fans: vec![thermal::Fan {
+                label: "cpu_fan".to_string(),
+                rpm: 1200,
+            }],
+        };
+        let output = hardware(thermal).to_string();
+        assert!(output.contains("         Thermal: Warm\n"));
+        assert!(output.contains("        CPU Temp: 75.0°C (Warm)\n"));
+        assert!(output.ends_with("         cpu_fan: 1200 RPM\n}"));
+
+        let output = hardware(thermal::Info::default()).to_string();
+        assert!(output.ends_with("         Thermal: Unknown\n}"));
     }
 }
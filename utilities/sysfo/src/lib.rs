@@ -1,3 +1,12 @@
 mod core;
+mod general;
+mod hardware;
+pub mod metrics;
+pub mod prelude;
+pub mod report;
+mod software;
 
 pub use core::*;
+pub use general::{General, Session, SessionKind};
+pub use hardware::Hardware;
+pub use software::{PackageCount, Software};
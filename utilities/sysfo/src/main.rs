@@ -1,4 +1,105 @@
+use anyhow::Context;
+use clap::{Parser, Subcommand};
+use std::{fs, path::PathBuf};
+use sysfo::report::{Format, Report, Section};
+
+#[derive(Parser)]
+#[command(version, about = "Fetch information about the system")]
+struct Cli {
+    #[command(subcommand)]
+    command: Option<Commands>,
+}
+
+#[derive(Subcommand)]
+enum Commands {
+    /// Print a sectioned summary of the system
+    Report {
+        /// Sections to show, all by default
+        #[arg(short, long, value_enum, value_delimiter = ',')]
+        sections: Vec<Section>,
+
+        /// Print without colors
+        #[arg(long)]
+        no_color: bool,
+    },
+
+    /// Write a shareable report file, e.g. to attach to a bug report
+    Export {
+        /// Document format
+        #[arg(short, long, value_enum, default_value = "markdown")]
+        format: Format,
+
+        /// File to write, sysfo-report.md or .html by default
+        #[arg(short, long)]
+        output: Option<PathBuf>,
+
+        /// Sections to include, all by default
+        #[arg(short, long, value_enum, value_delimiter = ',')]
+        sections: Vec<Section>,
+
+        /// Replace the hostname wherever it appears
+        #[arg(long)]
+        redact: bool,
+    },
+
+    /// Show the current user, session and uptime
+    General {
+        /// Print as JSON
+        #[arg(long)]
+        json: bool,
+    },
+
+    /// Expose metrics for Prometheus to scrape
+    Serve {
+        /// Address to listen on
+        #[arg(short, long, default_value = "127.0.0.1:9101")]
+        listen: String,
+    },
+}
+
 fn main() -> anyhow::Result<()> {
+    let cli = Cli::parse();
+
+    match cli.command {
+        Some(Commands::Report { sections, no_color }) => {
+            let mut report = Report::new().with_sections(sections);
+            if no_color {
+                report = report.with_color(false);
+            }
+            print!("{}", report.render());
+            return Ok(());
+        }
+        Some(Commands::Export {
+            format,
+            output,
+            sections,
+            redact,
+        }) => {
+            let output = output
+                .unwrap_or_else(|| PathBuf::from(format!("sysfo-report.{}", format.extension())));
+            let report = Report::new().with_sections(sections).with_redact(redact);
+            fs::write(&output, report.export(format))
+                .with_context(|| format!("Failed to write {}", output.display()))?;
+            println!("Wrote {}", output.display());
+            return Ok(());
+        }
+        Some(Commands::General { json }) => {
+            let general = sysfo::General::default();
+            match json {
+                true => println!("{}", serde_json::to_string_pretty(&general)?),
+                false => println!("{}", general),
+            }
+            return Ok(());
+        }
+        Some(Commands::Serve { listen }) => {
+            logline::init();
+            sysfo::metrics::serve(&listen)
+                .with_context(|| format!("Failed to serve metrics on {}", listen))?;
+            return Ok(());
+        }
+        None => {}
+    }
+
     println!("\nWelcome to 🦀 sysfo!");
     logline::init();
     sysfo::test();
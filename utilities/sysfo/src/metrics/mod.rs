@@ -0,0 +1,298 @@
+mod server;
+
+pub use server::serve;
+
+use std::fmt::Write;
+use sysinfo::{
+    CpuRefreshKind, DiskRefreshKind, Disks, MemoryRefreshKind, Networks, RefreshKind, System,
+    MINIMUM_CPU_UPDATE_INTERVAL,
+};
+
+/// Collects machine metrics in the Prometheus text exposition format.
+///
+/// The probes are kept between scrapes so CPU usage covers the time
+/// since the previous scrape.
+///
+/// ```no_run
+/// let mut exporter = sysfo::metrics::Exporter::new();
+/// print!("{}", exporter.collect());
+/// ```
+#[derive(Debug)]
+pub struct Exporter {
+    system: System,
+    disks: Disks,
+    networks: Networks,
+}
+
+impl Default for Exporter {
+    fn default() -> Self {
+        let system = System::new_with_specifics(
+            RefreshKind::nothing()
+                .with_cpu(CpuRefreshKind::nothing().with_cpu_usage())
+                .with_memory(MemoryRefreshKind::everything()),
+        );
+        // CPU usage needs two readings some time apart
+        std::thread::sleep(MINIMUM_CPU_UPDATE_INTERVAL);
+
+        Self {
+            system,
+            disks: Disks::new_with_refreshed_list(),
+            networks: Networks::new_with_refreshed_list(),
+        }
+    }
+}
+
+impl Exporter {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Refreshes every probe and renders the current readings.
+    pub fn collect(&mut self) -> String {
+        self.system.refresh_cpu_usage();
+        self.system.refresh_memory();
+        self.disks
+            .refresh_specifics(true, DiskRefreshKind::nothing().with_storage());
+        self.networks.refresh(true);
+
+        let mut out = String::new();
+        self.cpu(&mut out);
+        self.memory(&mut out);
+        self.disk(&mut out);
+        self.network(&mut out);
+        battery(&mut out);
+        family(
+            &mut out,
+            "sysfo_uptime_seconds",
+            "gauge",
+            "Time since the system booted.",
+            &[(vec![], System::uptime() as f64)],
+        );
+        out
+    }
+
+    fn cpu(&self, out: &mut String) {
+        family(
+            out,
+            "sysfo_cpu_usage_percent",
+            "gauge",
+            "CPU usage since the previous scrape, across all cores.",
+            &[(vec![], self.system.global_cpu_usage() as f64)],
+        );
+        let cores: Vec<_> = self
+            .system
+            .cpus()
+            .iter()
+            .enumerate()
+            .map(|(index, cpu)| (vec![("core", index.to_string())], cpu.cpu_usage() as f64))
+            .collect();
+        family(
+            out,
+            "sysfo_cpu_core_usage_percent",
+            "gauge",
+            "CPU usage since the previous scrape, per core.",
+            &cores,
+        );
+        let load = System::load_average();
+        family(
+            out,
+            "sysfo_load_average",
+            "gauge",
+            "System load average.",
+            &[
+                (vec![("period", "1m".to_string())], load.one),
+                (vec![("period", "5m".to_string())], load.five),
+                (vec![("period", "15m".to_string())], load.fifteen),
+            ],
+        );
+    }
+
+    fn memory(&self, out: &mut String) {
+        let system = &self.system;
+        for (name, help, value) in [
+            (
+                "sysfo_memory_total_bytes",
+                "Total RAM.",
+                system.total_memory(),
+            ),
+            (
+                "sysfo_memory_used_bytes",
+                "RAM in use.",
+                system.used_memory(),
+            ),
+            ("sysfo_swap_total_bytes", "Total swap.", system.total_swap()),
+            ("sysfo_swap_used_bytes", "Swap in use.", system.used_swap()),
+        ] {
+            family(out, name, "gauge", help, &[(vec![], value as f64)]);
+        }
+    }
+
+    fn disk(&self, out: &mut String) {
+        let labels = |disk: &sysinfo::Disk| {
+            vec![
+                ("device", disk.name().to_string_lossy().to_string()),
+                ("mountpoint", disk.mount_point().display().to_string()),
+                ("fstype", disk.file_system().to_string_lossy().to_string()),
+            ]
+        };
+        let list = self.disks.list();
+        family(
+            out,
+            "sysfo_disk_total_bytes",
+            "gauge",
+            "Size of each mounted filesystem.",
+            &list
+                .iter()
+                .map(|disk| (labels(disk), disk.total_space() as f64))
+                .collect::<Vec<_>>(),
+        );
+        family(
+            out,
+            "sysfo_disk_available_bytes",
+            "gauge",
+            "Space left on each mounted filesystem.",
+            &list
+                .iter()
+                .map(|disk| (labels(disk), disk.available_space() as f64))
+                .collect::<Vec<_>>(),
+        );
+    }
+
+    fn network(&self, out: &mut String) {
+        let mut interfaces: Vec<_> = self.networks.list().iter().collect();
+        interfaces.sort_by(|a, b| a.0.cmp(b.0));
+        let samples = |value: fn(&sysinfo::NetworkData) -> u64| {
+            interfaces
+                .iter()
+                .map(|(name, data)| (vec![("interface", name.to_string())], value(data) as f64))
+                .collect::<Vec<_>>()
+        };
+
+        family(
+            out,
+            "sysfo_network_received_bytes_total",
+            "counter",
+            "Bytes received per interface.",
+            &samples(|data| data.total_received()),
+        );
+        family(
+            out,
+            "sysfo_network_transmitted_bytes_total",
+            "counter",
+            "Bytes sent per interface.",
+            &samples(|data| data.total_transmitted()),
+        );
+    }
+}
+
+/// Battery gauges are left out on machines without one.
+fn battery(out: &mut String) {
+    let Some(Ok(battery)) = battery::Manager::new()
+        .and_then(|manager| manager.batteries())
+        .ok()
+        .and_then(|mut batteries| batteries.next())
+    else {
+        return;
+    };
+
+    family(
+        out,
+        "sysfo_battery_level_ratio",
+        "gauge",
+        "Battery charge, from 0 to 1.",
+        &[(vec![], battery.state_of_charge().value as f64)],
+    );
+    let state = battery.state();
+    let states: Vec<_> = ["charging", "discharging", "full", "empty", "unknown"]
+        .into_iter()
+        .map(|name| {
+            let active = state.to_string() == name;
+            (vec![("state", name.to_string())], active as u8 as f64)
+        })
+        .collect();
+    family(
+        out,
+        "sysfo_battery_state",
+        "gauge",
+        "Whether the battery is in each state.",
+        &states,
+    );
+}
+
+type Sample = (Vec<(&'static str, String)>, f64);
+
+/// Writes one metric family: its HELP and TYPE lines, then a line per
+/// sample.
+fn family(out: &mut String, name: &str, kind: &str, help: &str, samples: &[Sample]) {
+    if samples.is_empty() {
+        return;
+    }
+
+    let _ = writeln!(out, "# HELP {} {}", name, help);
+    let _ = writeln!(out, "# TYPE {} {}", name, kind);
+    for (labels, value) in samples {
+        let labels = labels
+            .iter()
+            .map(|(key, value)| format!("{}=\"{}\"", key, escape(value)))
+            .collect::<Vec<_>>();
+        match labels.is_empty() {
+            true => {
+                let _ = writeln!(out, "{} {}", name, value);
+            }
+            false => {
+                let _ = writeln!(out, "{}{{{}}} {}", name, labels.join(","), value);
+            }
+        }
+    }
+}
+
+fn escape(value: &str) -> String {
+    value
+        .replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace('\n', "\\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_family_writes_help_type_and_samples() {
+        let mut out = String::new();
+        family(
+            &mut out,
+            "sysfo_load",
+            "gauge",
+            "Load average",
+            &[(vec![], 0.5), (vec![("period", "1m".to_string())], 1.25)],
+        );
+        assert_eq!(
+            out,
+            "# HELP sysfo_load Load average\n\
+             # TYPE sysfo_load gauge\n\
+             sysfo_load 0.5\n\
+             sysfo_load{period=\"1m\"} 1.25\n"
+        );
+
+        let mut out = String::new();
+        family(&mut out, "sysfo_none", "gauge", "Nothing", &[]);
+        assert!(out.is_empty());
+    }
+
+    #[test]
+    fn test_label_values_are_escaped() {
+        assert_eq!(
+            escape(
+                r#"C:\Disk "one"
+two"#
+            ),
+            r#"C:\\Disk \"one\"\ntwo"#
+        );
+
+        let mut out = String::new();
+        let labels = vec![("mount", "/mnt/\"x\"".to_string())];
+        family(&mut out, "sysfo_disk", "gauge", "Disk", &[(labels, 1.0)]);
+        assert!(out.ends_with("sysfo_disk{mount=\"/mnt/\\\"x\\\"\"} 1\n"));
+    }
+}
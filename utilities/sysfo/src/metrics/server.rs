@@ -0,0 +1,111 @@
+use super::Exporter;
+use std::{
+    io::{self, BufRead, BufReader, Write},
+    net::{TcpListener, TcpStream, ToSocketAddrs},
+    time::Duration,
+};
+
+const CONTENT_TYPE: &str = "text/plain; version=0.0.4; charset=utf-8";
+
+/// How long a client may take to send its request or read the reply,
+/// so one stalled connection can't hold up later scrapes.
+const TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Serves `/metrics` on `addr` until the process is stopped, collecting
+/// fresh readings for every scrape.
+///
+/// Requests are handled one at a time, which is plenty for a scraper;
+/// each connection gets [`TIMEOUT`] to send and receive.
+pub fn serve<A: ToSocketAddrs>(addr: A) -> io::Result<()> {
+    let listener = TcpListener::bind(addr)?;
+    let mut exporter = Exporter::new();
+    logline::info!(
+        "Serving metrics on http://{}/metrics",
+        listener.local_addr()?
+    );
+
+    for stream in listener.incoming() {
+        let result = stream.and_then(|stream| respond(stream, &mut exporter));
+        if let Err(e) = result {
+            logline::warn!("Failed to answer scrape: {}", e);
+        }
+    }
+    Ok(())
+}
+
+fn respond(mut stream: TcpStream, exporter: &mut Exporter) -> io::Result<()> {
+    stream.set_read_timeout(Some(TIMEOUT))?;
+    stream.set_write_timeout(Some(TIMEOUT))?;
+
+    let mut reader = BufReader::new(&stream);
+    let mut request = String::new();
+    reader.read_line(&mut request)?;
+    // Read the headers too: closing with unread input can reset the
+    // connection before the client sees the body
+    let mut header = String::new();
+    while reader.read_line(&mut header)? > 0 && !header.trim().is_empty() {
+        header.clear();
+    }
+
+    let mut parts = request.split_whitespace();
+    let (status, content_type, body) = match (parts.next(), parts.next()) {
+        (Some("GET"), Some("/metrics")) => ("200 OK", CONTENT_TYPE, exporter.collect()),
+        (Some("GET"), Some("/")) => (
+            "200 OK",
+            "text/html; charset=utf-8",
+            "<a href=\"/metrics\">metrics</a>\n".to_string(),
+        ),
+        (Some("GET"), _) => ("404 Not Found", CONTENT_TYPE, "not found\n".to_string()),
+        _ => (
+            "405 Method Not Allowed",
+            CONTENT_TYPE,
+            "method not allowed\n".to_string(),
+        ),
+    };
+
+    write!(
+        stream,
+        "HTTP/1.1 {}\r\nContent-Type: {}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+        status,
+        content_type,
+        body.len(),
+        body
+    )?;
+    stream.flush()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::{io::Read, thread};
+
+    /// Sends `request` to `respond` over a local socket and returns the reply.
+    fn exchange(request: &'static str) -> String {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let addr = listener.local_addr().unwrap();
+        let client = thread::spawn(move || {
+            let mut stream = TcpStream::connect(addr).unwrap();
+            stream.write_all(request.as_bytes()).unwrap();
+            let mut reply = String::new();
+            stream.read_to_string(&mut reply).unwrap();
+            reply
+        });
+
+        let (stream, _) = listener.accept().unwrap();
+        respond(stream, &mut Exporter::new()).unwrap();
+        client.join().unwrap()
+    }
+
+    #[test]
+    fn test_respond_reads_headers_and_routes() {
+        let reply = exchange("GET / HTTP/1.1\r\nHost: localhost\r\nAccept: */*\r\n\r\n");
+        assert!(reply.starts_with("HTTP/1.1 200 OK\r\n"));
+        assert!(reply.ends_with("<a href=\"/metrics\">metrics</a>\n"));
+
+        let reply = exchange("GET /other HTTP/1.1\r\nHost: localhost\r\n\r\n");
+        assert!(reply.starts_with("HTTP/1.1 404 Not Found\r\n"));
+
+        let reply = exchange("POST /metrics HTTP/1.1\r\n\r\n");
+        assert!(reply.starts_with("HTTP/1.1 405 Method Not Allowed\r\n"));
+    }
+}
@@ -0,0 +1,17 @@
+//! Everything needed to fetch and print system information.
+//!
+//! ```no_run
+//! use sysfo::prelude::*;
+//!
+//! let info = Fetcher::new().with_battery();
+//! if let Some(power) = &info.power {
+//!     println!("{}", power.level_fmt());
+//! }
+//! ```
+
+pub use crate::{
+    init, power, process,
+    report::{Report, Section},
+    terminal, thermal, time, Error, Fetcher, FormatHelpers, General, Hardware, Session,
+    SessionKind, Software,
+};
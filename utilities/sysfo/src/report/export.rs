@@ -0,0 +1,95 @@
+use super::Section;
+use std::fmt::Write;
+
+/// File format of an exported [`Report`](super::Report).
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+pub enum Format {
+    #[default]
+    Markdown,
+    Html,
+}
+
+impl Format {
+    pub fn extension(&self) -> &'static str {
+        match self {
+            Self::Markdown => "md",
+            Self::Html => "html",
+        }
+    }
+}
+
+pub(super) type Fields = Vec<(&'static str, String)>;
+
+pub(super) fn markdown(title: &str, sections: &[(Section, Fields)]) -> String {
+    let cell = |text: &str| text.replace('|', "\\|");
+    let mut output = format!("# {}\n", cell(title));
+    for (section, fields) in sections {
+        let _ = write!(
+            output,
+            "\n## {}\n\n| Field | Value |\n| --- | --- |\n",
+            section
+        );
+        for (label, value) in fields {
+            let _ = writeln!(output, "| {} | {} |", label, cell(value));
+        }
+    }
+    output
+}
+
+pub(super) fn html(title: &str, sections: &[(Section, Fields)]) -> String {
+    let title = escape(title);
+    let mut output = format!(
+        "<!DOCTYPE html>\n<html>\n<head>\n<meta charset=\"utf-8\">\n\
+         <title>{0}</title>\n</head>\n<body>\n<h1>{0}</h1>\n",
+        title
+    );
+    for (section, fields) in sections {
+        let _ = writeln!(output, "<h2>{}</h2>\n<table>", section);
+        for (label, value) in fields {
+            let _ = writeln!(
+                output,
+                "<tr><th>{}</th><td>{}</td></tr>",
+                label,
+                escape(value)
+            );
+        }
+        output.push_str("</table>\n");
+    }
+    output.push_str("</body>\n</html>\n");
+    output
+}
+
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sections() -> Vec<(Section, Fields)> {
+        vec![(
+            Section::Software,
+            vec![("Shell", "<fish> | \"zsh\" & bash".to_string())],
+        )]
+    }
+
+    #[test]
+    fn test_markdown_escapes_pipes() {
+        let output = markdown("ada|lab", &sections());
+        assert!(output.starts_with("# ada\\|lab\n"));
+        assert!(output.contains("| Shell | <fish> \\| \"zsh\" & bash |\n"));
+    }
+
+    #[test]
+    fn test_html_escapes_markup() {
+        let output = html("<ada>", &sections());
+        assert!(output.contains("<title>&lt;ada&gt;</title>"));
+        assert!(output
+            .contains("<tr><th>Shell</th><td>&lt;fish&gt; | &quot;zsh&quot; &amp; bash</td></tr>"));
+        assert!(!output.contains("<fish>"));
+    }
+}
@@ -0,0 +1,285 @@
+mod export;
+mod section;
+
+pub use export::Format;
+pub use section::Section;
+
+use export::Fields;
+
+use crate::{power, thermal, time, General, Hardware, Software};
+use crossterm::style::{Color, Stylize};
+use std::{
+    env,
+    io::{stdout, IsTerminal},
+};
+use sysinfo::System;
+
+const REDACTED: &str = "[redacted]";
+
+/// A neofetch-style summary of the machine, one titled block per
+/// section.
+///
+/// Only the probes behind the chosen sections are run.
+///
+/// ```no_run
+/// use sysfo::report::{Report, Section};
+///
+/// let report = Report::new().with_sections([Section::Hardware, Section::Time]);
+/// println!("{}", report.render());
+/// ```
+#[derive(Debug, Clone)]
+pub struct Report {
+    pub sections: Vec<Section>,
+    pub color: bool,
+    /// Replaces the hostname wherever it appears, for reports shared
+    /// outside the machine.
+    pub redact: bool,
+}
+
+impl Default for Report {
+    fn default() -> Self {
+        Self {
+            sections: Section::ALL.to_vec(),
+            color: env::var_os("NO_COLOR").is_none() && stdout().is_terminal(),
+            redact: false,
+        }
+    }
+}
+
+impl Report {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Limits the report to `sections`, shown in their usual order.
+    /// An empty selection keeps every section.
+    pub fn with_sections<I: IntoIterator<Item = Section>>(mut self, sections: I) -> Self {
+        let mut sections: Vec<_> = sections.into_iter().collect();
+        sections.sort();
+        sections.dedup();
+        if !sections.is_empty() {
+            self.sections = sections;
+        }
+        self
+    }
+
+    pub fn with_color(mut self, color: bool) -> Self {
+        self.color = color;
+        self
+    }
+
+    pub fn with_redact(mut self, redact: bool) -> Self {
+        self.redact = redact;
+        self
+    }
+
+    pub fn render(&self) -> String {
+        let (title, sections) = self.collect();
+        let mut output = format!(
+            "{}\n{}\n",
+            self.paint(&title, Color::Cyan, true),
+            "-".repeat(title.chars().count())
+        );
+
+        for (section, fields) in &sections {
+            output.push_str(&self.section(section, fields));
+        }
+
+        output
+    }
+
+    /// The report as a standalone document, for attaching to bug
+    /// reports.
+    ///
+    /// ```no_run
+    /// use sysfo::report::{Format, Report};
+    ///
+    /// let html = Report::new().with_redact(true).export(Format::Html);
+    /// std::fs::write("sysfo-report.html", html).unwrap();
+    /// ```
+    pub fn export(&self, format: Format) -> String {
+        let (title, sections) = self.collect();
+        match format {
+            Format::Markdown => export::markdown(&title, &sections),
+            Format::Html => export::html(&title, &sections),
+        }
+    }
+
+    /// The `user@host` title and the fields of each chosen section.
+    fn collect(&self) -> (String, Vec<(Section, Fields)>) {
+        let general = General::default();
+        let host = System::host_name().unwrap_or_else(|| "localhost".to_string());
+        let mut title = format!("{}@{}", general.username, host);
+
+        let mut sections: Vec<(Section, Fields)> = self
+            .sections
+            .iter()
+            .map(|section| {
+                let fields = match section {
+                    Section::General => vec![
+                        ("User", general.username.clone()),
+                        ("Host", host.clone()),
+                        ("Session", general.session.kind.to_string()),
+                        ("Uptime", general.session.uptime_fmt()),
+                        ("Users", general.session.users.join(", ")),
+                    ],
+                    Section::Hardware => hardware(),
+                    Section::Software => software(),
+                    Section::Power => power(),
+                    Section::Time => time(),
+                };
+                (*section, fields)
+            })
+            .collect();
+
+        if self.redact {
+            redact(&host, &mut title, &mut sections);
+        }
+        (title, sections)
+    }
+
+    fn section(&self, section: &Section, fields: &[(&str, String)]) -> String {
+        let width = fields
+            .iter()
+            .map(|(label, _)| label.len())
+            .max()
+            .unwrap_or(0);
+        let mut output = format!(
+            "\n{}\n",
+            self.paint(&section.to_string(), Color::Blue, true)
+        );
+        for (label, value) in fields {
+            let label = format!("{:>width$}", label);
+            output.push_str(&format!(
+                "  {}: {}\n",
+                self.paint(&label, Color::Yellow, false),
+                value
+            ));
+        }
+        output
+    }
+
+    fn paint(&self, text: &str, color: Color, bold: bool) -> String {
+        match (self.color, bold) {
+            (false, _) => text.to_string(),
+            (true, true) => text.with(color).bold().to_string(),
+            (true, false) => text.with(color).to_string(),
+        }
+    }
+}
+
+/// Masks every occurrence of `host`, including inside longer values such
+/// as paths or fully qualified names.
+fn redact(host: &str, title: &mut String, sections: &mut [(Section, Fields)]) {
+    if host.is_empty() {
+        return;
+    }
+    let fields = sections.iter_mut().flat_map(|(_, fields)| fields);
+    for text in std::iter::once(title).chain(fields.map(|(_, value)| value)) {
+        *text = text.replace(host, REDACTED);
+    }
+}
+
+fn hardware() -> Fields {
+    let hardware = Hardware::default();
+    let mut fields = vec![
+        ("CPU", hardware.cpu),
+        (
+            "Cores",
+            format!("{} ({} threads)", hardware.cores, hardware.threads),
+        ),
+        ("Arch", hardware.arch),
+        ("Memory", format!("{:.2} GiB", hardware.memory)),
+    ];
+
+    let thermal = hardware.thermal;
+    for (label, kind) in [
+        ("CPU Temp", thermal::Kind::Cpu),
+        ("GPU Temp", thermal::Kind::Gpu),
+    ] {
+        if let Some(sensor) = thermal.hottest(kind) {
+            fields.push((label, sensor.temperature_fmt()));
+        }
+    }
+    fields
+}
+
+fn software() -> Fields {
+    let software = Software::all();
+    let packages = software.packages_fmt();
+    let mut fields = vec![
+        ("OS", software.name),
+        ("Kernel", software.kernel),
+        ("Distro", software.distro),
+    ];
+    for (label, value) in [
+        ("Init", software.init),
+        ("Desktop", software.desktop),
+        ("Display", software.display_server),
+        ("Shell", software.shell),
+        ("Packages", Some(packages).filter(|p| !p.is_empty())),
+    ] {
+        if let Some(value) = value {
+            fields.push((label, value));
+        }
+    }
+    fields
+}
+
+fn power() -> Fields {
+    match power::Info::new() {
+        Ok(power) => vec![
+            ("Level", power.level_fmt()),
+            ("Status", format!("{:?}", power.state)),
+            ("Remaining", power.remaining_fmt()),
+        ],
+        Err(e) => vec![("Battery", e.to_string())],
+    }
+}
+
+fn time() -> Fields {
+    let time = time::Info::new();
+    vec![
+        ("Current", time.current_fmt()),
+        ("Uptime", time.uptime_fmt()),
+        ("Time Zone", time.timezone),
+    ]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_redact_masks_hostname_inside_values() {
+        let mut title = "ada@lovelace".to_string();
+        let mut sections = vec![(
+            Section::General,
+            vec![
+                ("Host", "lovelace".to_string()),
+                ("Session", "ssh from lovelace.local".to_string()),
+                ("Shell", "zsh".to_string()),
+            ],
+        )];
+        redact("lovelace", &mut title, &mut sections);
+
+        assert_eq!(title, "ada@[redacted]");
+        assert_eq!(
+            sections[0].1,
+            [
+                ("Host", "[redacted]".to_string()),
+                ("Session", "ssh from [redacted].local".to_string()),
+                ("Shell", "zsh".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_redact_ignores_empty_hostname() {
+        let mut title = "ada@".to_string();
+        let mut sections = vec![(Section::Time, vec![("Current", "12:00".to_string())])];
+        redact("", &mut title, &mut sections);
+        assert_eq!(title, "ada@");
+        assert_eq!(sections[0].1[0].1, "12:00");
+    }
+}
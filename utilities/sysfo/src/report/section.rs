@@ -0,0 +1,34 @@
+use std::fmt::{self, Display, Formatter};
+
+/// A part of the [`Report`](super::Report), in display order.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, clap::ValueEnum)]
+pub enum Section {
+    General,
+    Hardware,
+    Software,
+    Power,
+    Time,
+}
+
+impl Section {
+    pub const ALL: [Self; 5] = [
+        Self::General,
+        Self::Hardware,
+        Self::Software,
+        Self::Power,
+        Self::Time,
+    ];
+}
+
+impl Display for Section {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        let title = match self {
+            Self::General => "General",
+            Self::Hardware => "Hardware",
+            Self::Software => "Software",
+            Self::Power => "Power",
+            Self::Time => "Time",
+        };
+        write!(f, "{}", title)
+    }
+}
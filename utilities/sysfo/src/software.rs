@@ -1,13 +1,37 @@
-use std::fmt::{Display, Formatter, Result};
+use crate::SessionKind;
+use serde::Serialize;
+use std::{
+    env,
+    fmt::{Display, Formatter, Result},
+    fs,
+    path::Path,
+};
 use sysinfo::System;
 
-#[derive(Debug)]
+#[derive(Debug, Serialize)]
 pub struct Software {
-    label: String,
-    name: String,
-    kernel: String,
-    version: String,
-    distro: String,
+    pub label: String,
+    pub name: String,
+    pub kernel: String,
+    pub version: String,
+    pub distro: String,
+    /// The first process started, such as systemd or launchd.
+    pub init: Option<String>,
+    pub desktop: Option<String>,
+    /// Wayland, X11, or the platform's own compositor.
+    pub display_server: Option<String>,
+    /// The user's login shell.
+    pub shell: Option<String>,
+    /// Installed packages per package manager; only counted by
+    /// [`Software::all`] since listing them can take a while.
+    pub packages: Vec<PackageCount>,
+}
+
+/// How many packages one package manager reports as installed.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct PackageCount {
+    pub manager: &'static str,
+    pub count: usize,
 }
 
 impl Default for Software {
@@ -18,6 +42,11 @@ impl Default for Software {
             kernel: System::kernel_version().unwrap_or_default(),
             version: System::os_version().unwrap_or_default(),
             distro: System::distribution_id(),
+            init: init_system(),
+            desktop: desktop(),
+            display_server: display_server(),
+            shell: shell(),
+            packages: Vec::new(),
         }
     }
 }
@@ -27,8 +56,12 @@ impl Software {
         Self::default()
     }
 
+    /// Everything, including package counts.
     pub fn all() -> Self {
-        Self::default()
+        Self {
+            packages: Self::packages(),
+            ..Self::default()
+        }
     }
 
     pub fn label() -> String {
@@ -50,22 +83,171 @@ impl Software {
     pub fn distro() -> String {
         Self::default().distro
     }
+
+    /// Counts installed packages with every package manager the `app`
+    /// crate can drive here. Managers that fail to list are left out.
+    pub fn packages() -> Vec<PackageCount> {
+        app::backends::detect()
+            .into_iter()
+            .filter_map(|backend| match backend.list() {
+                Ok(packages) => Some(PackageCount {
+                    manager: backend.name(),
+                    count: packages.len(),
+                }),
+                Err(e) => {
+                    logline::debug!("Skipping {} packages: {:#}", backend.name(), e);
+                    None
+                }
+            })
+            .collect()
+    }
+
+    /// Package counts as `1204 (pacman), 37 (cargo)`.
+    pub fn packages_fmt(&self) -> String {
+        self.packages
+            .iter()
+            .map(|packages| format!("{} ({})", packages.count, packages.manager))
+            .collect::<Vec<_>>()
+            .join(", ")
+    }
+}
+
+fn init_system() -> Option<String> {
+    match env::consts::OS {
+        "linux" => fs::read_to_string("/proc/1/comm")
+            .ok()
+            .map(|comm| comm.trim().to_string())
+            .filter(|comm| !comm.is_empty()),
+        "macos" => Some("launchd".to_string()),
+        "windows" => Some("Service Control Manager".to_string()),
+        _ => None,
+    }
+}
+
+fn desktop() -> Option<String> {
+    match env::consts::OS {
+        "macos" => Some("Aqua".to_string()),
+        "windows" => Some("Explorer".to_string()),
+        // Several desktops list themselves, as in `ubuntu:GNOME`
+        _ => ["XDG_CURRENT_DESKTOP", "DESKTOP_SESSION"]
+            .into_iter()
+            .find_map(|var| env::var(var).ok().filter(|value| !value.is_empty()))
+            .map(|value| value.rsplit(':').next().unwrap_or(&value).to_string()),
+    }
+}
+
+fn display_server() -> Option<String> {
+    match env::consts::OS {
+        "macos" => Some("Quartz".to_string()),
+        "windows" => Some("DWM".to_string()),
+        _ => graphical(SessionKind::detect()),
+    }
+}
+
+/// The display server behind a session, if it is a graphical one.
+fn graphical(kind: SessionKind) -> Option<String> {
+    matches!(kind, SessionKind::Wayland | SessionKind::X11).then(|| kind.to_string())
+}
+
+fn shell() -> Option<String> {
+    let var = match env::consts::OS {
+        "windows" => "COMSPEC",
+        _ => "SHELL",
+    };
+    let shell = env::var(var).ok()?;
+    Path::new(&shell)
+        .file_stem()
+        .map(|name| name.to_string_lossy().into_owned())
 }
 
 impl Display for Software {
     fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        let unknown = |value: &Option<String>| value.clone().unwrap_or_else(|| "Unknown".into());
         let header = "Software {";
         let label = format!("{:>16}: {}", "Label", self.label);
         let name = format!("{:>16}: {}", "Name", self.name);
         let kernel = format!("{:>16}: {}", "Kernel", self.kernel);
         let version = format!("{:>16}: {}", "Version", self.version);
         let distro = format!("{:>16}: {}", "Distro", self.distro);
+        let init = format!("{:>16}: {}", "Init", unknown(&self.init));
+        let desktop = format!("{:>16}: {}", "Desktop", unknown(&self.desktop));
+        let display = format!(
+            "{:>16}: {}",
+            "Display Server",
+            unknown(&self.display_server)
+        );
+        let shell = format!("{:>16}: {}", "Shell", unknown(&self.shell));
         let footer = "}";
 
         write!(
             f,
-            "{}\n{}\n{}\n{}\n{}\n{}\n{}",
-            header, label, name, kernel, version, distro, footer
-        )
+            "{}\n{}\n{}\n{}\n{}\n{}\n{}\n{}\n{}\n{}",
+            header, label, name, kernel, version, distro, init, desktop, display, shell
+        )?;
+        if !self.packages.is_empty() {
+            write!(f, "\n{:>16}: {}", "Packages", self.packages_fmt())?;
+        }
+        write!(f, "\n{}", footer)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample() -> Software {
+        Software {
+            label: "Linux".into(),
+            name: "Arch Linux".into(),
+            kernel: "6.9.1".into(),
+            version: "rolling".into(),
+            distro: "arch".into(),
+            init: Some("systemd".into()),
+            desktop: None,
+            display_server: Some("Wayland".into()),
+            shell: Some("zsh".into()),
+            packages: vec![
+                PackageCount {
+                    manager: "pacman",
+                    count: 1204,
+                },
+                PackageCount {
+                    manager: "cargo",
+                    count: 37,
+                },
+            ],
+        }
+    }
+
+    #[test]
+    fn test_graphical_only_for_display_sessions() {
+        assert_eq!(graphical(SessionKind::Wayland).as_deref(), Some("Wayland"));
+        assert_eq!(graphical(SessionKind::X11).as_deref(), Some("X11"));
+        assert_eq!(graphical(SessionKind::Ssh), None);
+        assert_eq!(graphical(SessionKind::Tty), None);
+        assert_eq!(graphical(SessionKind::Unknown), None);
+    }
+
+    #[test]
+    fn test_display_marks_unknown_and_lists_packages() {
+        let mut software = sample();
+        assert_eq!(software.packages_fmt(), "1204 (pacman), 37 (cargo)");
+
+        let text = software.to_string();
+        assert!(text.contains("         Desktop: Unknown\n"));
+        assert!(text.contains("  Display Server: Wayland\n"));
+        assert!(text.ends_with("        Packages: 1204 (pacman), 37 (cargo)\n}"));
+
+        software.packages.clear();
+        assert!(!software.to_string().contains("Packages"));
+    }
+
+    #[test]
+    fn test_serializes_to_json() {
+        let json = serde_json::to_value(sample()).unwrap();
+        assert_eq!(json["display_server"], "Wayland");
+        assert_eq!(json["desktop"], serde_json::Value::Null);
+        assert_eq!(json["packages"][1]["manager"], "cargo");
+        assert_eq!(json["packages"][1]["count"], 37);
     }
 }
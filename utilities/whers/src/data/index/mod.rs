@@ -0,0 +1,254 @@
+mod store;
+
+use crate::data::{PathType, Search, SearchError as Error};
+use logline::debug;
+use std::{
+	collections::BTreeMap,
+	fs,
+	path::{Path, PathBuf},
+	time::UNIX_EPOCH,
+};
+
+/// A persistent index of every name below a root directory.
+///
+/// The index remembers each directory's modification time, which
+/// changes whenever entries are added, removed or renamed in it.
+/// Refreshing only re-reads directories whose time has changed, so
+/// repeated searches of a large tree cost a `stat` per directory
+/// rather than a full walk.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Index {
+	/// The indexed directory.
+	pub root: PathBuf,
+
+	/// Indexed directories keyed by their path relative to `root`.
+	dirs: BTreeMap<PathBuf, Dir>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+struct Dir {
+	modified: u128,
+	entries: Vec<Entry>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Entry {
+	name: String,
+	path_type: Option<PathType>,
+}
+
+impl Index {
+	/// Loads the cached index for `root`, refreshes it and saves any
+	/// changes, building it from scratch the first time.
+	pub fn open<P: AsRef<Path>>(root: P) -> Result<Self, Error> {
+		let root = fs::canonicalize(root)?;
+		let path = store::path(&root)?;
+		let mut index =
+			store::load(&path, &root).unwrap_or_else(|| Self {
+				root,
+				dirs: BTreeMap::new(),
+			});
+
+		let rescanned = index.refresh();
+		debug!(
+			"Index of {} has {} directories, {} rescanned",
+			index.root.display(),
+			index.dirs.len(),
+			rescanned
+		);
+		if rescanned > 0 {
+			store::save(&index, &path)?;
+		}
+
+		Ok(index)
+	}
+
+	/// Re-reads every directory whose modification time changed since
+	/// it was indexed, returning how many were read.
+	pub fn refresh(&mut self) -> usize {
+		let mut previous = std::mem::take(&mut self.dirs);
+		let mut stack = vec![PathBuf::new()];
+		let mut rescanned = 0;
+
+		while let Some(relative) = stack.pop() {
+			let dir = self.root.join(&relative);
+			let Some(modified) = modified(&dir) else {
+				continue;
+			};
+
+			let record = match previous.remove(&relative) {
+				Some(record) if record.modified == modified => record,
+				_ => {
+					rescanned += 1;
+					match scan(&dir, modified) {
+						Some(record) => record,
+						None => continue,
+					}
+				}
+			};
+
+			stack.extend(
+				record
+					.entries
+					.iter()
+					.filter(|entry| {
+						entry.path_type == Some(PathType::Dir)
+					})
+					.map(|entry| relative.join(&entry.name)),
+			);
+			self.dirs.insert(relative, record);
+		}
+
+		rescanned
+	}
+
+	/// The number of indexed entries.
+	pub fn len(&self) -> usize {
+		self.dirs.values().map(|dir| dir.entries.len()).sum()
+	}
+
+	pub fn is_empty(&self) -> bool {
+		self.len() == 0
+	}
+
+	/// Runs a downward search against the index instead of the disk.
+	///
+	/// Names, types and depth are answered from the index; size and
+	/// modification filters read metadata for the matching names only.
+	pub fn search(
+		&self,
+		search: &Search,
+	) -> Result<Vec<PathBuf>, Error> {
+		let patterns = search.name_patterns()?;
+		let cutoff = search.cutoff();
+		let mut hits = Vec::new();
+
+		'dirs: for (relative, dir) in &self.dirs {
+			let depth = relative.components().count() + 1;
+			if search.max_depth.is_some_and(|max| depth > max)
+				|| search.min_depth.is_some_and(|min| depth < min)
+				|| relative.components().any(|part| {
+					search.is_excluded(
+						&part.as_os_str().to_string_lossy(),
+					)
+				}) {
+				continue;
+			}
+
+			for entry in &dir.entries {
+				if search.is_excluded(&entry.name)
+					|| !search.keeps_type(entry.path_type)
+				{
+					continue;
+				}
+				let Some(score) =
+					search.name_score(&patterns, &entry.name)
+				else {
+					continue;
+				};
+
+				let path = self.root.join(relative).join(&entry.name);
+				if search.needs_metadata()
+					&& !fs::symlink_metadata(&path).is_ok_and(
+						|metadata| {
+							search.keeps_metadata(&metadata, cutoff)
+						},
+					) {
+					continue;
+				}
+
+				hits.push((score, depth, path));
+				if !search.fuzzy
+					&& search
+						.limit
+						.is_some_and(|limit| hits.len() >= limit)
+				{
+					break 'dirs;
+				}
+			}
+		}
+
+		search.rank(hits)
+	}
+}
+
+/// A directory's modification time in nanoseconds since the epoch.
+fn modified(dir: &Path) -> Option<u128> {
+	let time = fs::metadata(dir).ok()?.modified().ok()?;
+	Some(time.duration_since(UNIX_EPOCH).ok()?.as_nanos())
+}
+
+fn scan(dir: &Path, modified: u128) -> Option<Dir> {
+	let mut entries: Vec<Entry> = fs::read_dir(dir)
+		.map_err(|e| debug!("Skipping {}: {}", dir.display(), e))
+		.ok()?
+		.filter_map(Result::ok)
+		.filter_map(|entry| {
+			let name = entry.file_name().into_string().ok()?;
+			let path_type =
+				entry.file_type().ok().and_then(PathType::of);
+			Some(Entry { name, path_type })
+		})
+		.collect();
+	entries.sort_by(|a, b| a.name.cmp(&b.name));
+
+	Some(Dir { modified, entries })
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use std::{
+		env,
+		fs::File,
+		time::{Duration, SystemTime},
+	};
+
+	/// Opens a directory so its timestamps can be set.
+	fn open_dir(dir: &Path) -> File {
+		let mut options = fs::OpenOptions::new();
+		options.read(true);
+		#[cfg(windows)]
+		{
+			use std::os::windows::fs::OpenOptionsExt;
+			// FILE_FLAG_BACKUP_SEMANTICS, needed to open a directory
+			options.write(true).custom_flags(0x0200_0000);
+		}
+		options.open(dir).unwrap()
+	}
+
+	#[test]
+	fn test_refresh_rescans_changed_directories_only() {
+		let root = env::temp_dir()
+			.join(format!("whers-index-{}", std::process::id()));
+		fs::create_dir_all(root.join("docs")).unwrap();
+		fs::create_dir_all(root.join("src")).unwrap();
+		fs::write(root.join("src/main.rs"), "").unwrap();
+
+		let mut index = Index {
+			root: fs::canonicalize(&root).unwrap(),
+			dirs: BTreeMap::new(),
+		};
+		assert_eq!(index.refresh(), 3);
+		assert_eq!(index.refresh(), 0);
+
+		// An explicit mtime, since a coarse clock may not move between
+		// the scan and the write
+		fs::write(root.join("src/lib.rs"), "").unwrap();
+		let later = SystemTime::now() + Duration::from_secs(60);
+		open_dir(&root.join("src")).set_modified(later).unwrap();
+		let rescanned = index.refresh();
+		let found =
+			index.search(&Search::default().with_pattern(r"\.rs$"));
+		fs::remove_dir_all(&root).unwrap();
+
+		assert_eq!(rescanned, 1);
+		assert_eq!(
+			found.unwrap(),
+			vec![
+				index.root.join("src/lib.rs"),
+				index.root.join("src/main.rs")
+			]
+		);
+	}
+}
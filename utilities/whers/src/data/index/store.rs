@@ -0,0 +1,125 @@
+use super::{Dir, Entry, Index};
+use crate::data::{PathType, SearchError as Error};
+use directories::BaseDirs;
+use logline::debug;
+use std::{
+	collections::BTreeMap,
+	fmt::Write as _,
+	fs,
+	path::{Path, PathBuf},
+};
+
+const HEADER: &str = "whers-index 1";
+
+/// Where the index for `root` is cached, one file per root.
+pub fn path(root: &Path) -> Result<PathBuf, Error> {
+	let cache = BaseDirs::new()
+		.map(|dirs| dirs.cache_dir().join("whers"))
+		.ok_or_else(|| {
+			Error::Directory(String::from("No cache directory"))
+		})?;
+
+	Ok(cache.join(format!("{:016x}.idx", fnv1a(root))))
+}
+
+/// Reads a cached index, or `None` if it is missing, unreadable or
+/// belongs to a different root.
+///
+/// Each directory is a `D <modified> <relative path>` line followed
+/// by one `<type> <name>` line per entry.
+pub fn load(path: &Path, root: &Path) -> Option<Index> {
+	let content = fs::read_to_string(path).ok()?;
+	let mut lines = content.lines();
+	if lines.next()? != HEADER || Path::new(lines.next()?) != root {
+		return None;
+	}
+
+	let mut dirs = BTreeMap::new();
+	let mut current: Option<(PathBuf, Dir)> = None;
+
+	for line in lines {
+		let (tag, rest) = line.split_once(' ')?;
+		if tag == "D" {
+			let (modified, relative) =
+				rest.split_once(' ').unwrap_or((rest, ""));
+			let dir = Dir {
+				modified: modified.parse().ok()?,
+				entries: Vec::new(),
+			};
+			if let Some((relative, dir)) =
+				current.replace((relative.into(), dir))
+			{
+				dirs.insert(relative, dir);
+			}
+			continue;
+		}
+
+		let path_type = match tag {
+			"f" => Some(PathType::File),
+			"d" => Some(PathType::Dir),
+			"l" => Some(PathType::Symlink),
+			_ => None,
+		};
+		current.as_mut()?.1.entries.push(Entry {
+			name: rest.to_string(),
+			path_type,
+		});
+	}
+	if let Some((relative, dir)) = current {
+		dirs.insert(relative, dir);
+	}
+
+	debug!("Loaded index from {}", path.display());
+	Some(Index {
+		root: root.to_path_buf(),
+		dirs,
+	})
+}
+
+/// Writes the index atomically, replacing any earlier copy.
+pub fn save(index: &Index, path: &Path) -> Result<(), Error> {
+	let mut content = format!("{HEADER}\n{}\n", index.root.display());
+	for (relative, dir) in &index.dirs {
+		if relative.to_string_lossy().contains('\n') {
+			continue;
+		}
+		let _ = writeln!(
+			content,
+			"D {} {}",
+			dir.modified,
+			relative.display()
+		);
+		for entry in &dir.entries {
+			if entry.name.contains('\n') {
+				continue;
+			}
+			let tag = match entry.path_type {
+				Some(PathType::File) => 'f',
+				Some(PathType::Dir) => 'd',
+				Some(PathType::Symlink) => 'l',
+				None => '-',
+			};
+			let _ = writeln!(content, "{} {}", tag, entry.name);
+		}
+	}
+
+	if let Some(parent) = path.parent() {
+		fs::create_dir_all(parent)?;
+	}
+	let partial = path.with_extension("tmp");
+	fs::write(&partial, content)?;
+	fs::rename(&partial, path)?;
+
+	debug!("Saved index to {}", path.display());
+	Ok(())
+}
+
+/// A stable hash of the root path, used to name its cache file.
+fn fnv1a(root: &Path) -> u64 {
+	root.as_os_str().as_encoded_bytes().iter().fold(
+		0xcbf29ce484222325,
+		|hash, &byte| {
+			(hash ^ byte as u64).wrapping_mul(0x100000001b3)
+		},
+	)
+}
@@ -1,3 +1,8 @@
+mod index;
 mod search;
 
-pub use search::Search;
+pub use index::Index;
+pub use search::{
+	Direction, Duplicates, Error as SearchError, Executable, PathType,
+	Search,
+};
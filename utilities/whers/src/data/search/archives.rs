@@ -0,0 +1,189 @@
+use flate2::read::GzDecoder;
+use std::{
+	fs::File,
+	io::{self, BufReader, Read},
+	path::{Path, PathBuf},
+	time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+/// Separates an archive's path from a member's path inside it.
+pub const SEPARATOR: char = '!';
+
+/// An entry listed in an archive.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct Member {
+	/// The path inside the archive, `/`-separated without a trailing
+	/// slash.
+	pub name: String,
+	pub is_dir: bool,
+	/// Uncompressed size in bytes.
+	pub size: u64,
+	/// Only tar archives record a usable time.
+	pub modified: Option<SystemTime>,
+}
+
+impl Member {
+	/// Levels below the archive, one for top-level members.
+	pub fn depth(&self) -> usize {
+		self.name.split('/').count()
+	}
+
+	/// The last component of the member's path.
+	pub fn file_name(&self) -> &str {
+		self.name.rsplit('/').next().unwrap_or(&self.name)
+	}
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Kind {
+	Zip,
+	Tar,
+	TarGz,
+}
+
+impl Kind {
+	fn of(name: &str) -> Option<Self> {
+		let name = name.to_lowercase();
+		if name.ends_with(".zip") {
+			Some(Self::Zip)
+		} else if name.ends_with(".tar.gz") || name.ends_with(".tgz")
+		{
+			Some(Self::TarGz)
+		} else if name.ends_with(".tar") {
+			Some(Self::Tar)
+		} else {
+			None
+		}
+	}
+}
+
+/// Whether a file name looks like an archive whers can list.
+pub(crate) fn is_archive(name: &str) -> bool {
+	Kind::of(name).is_some()
+}
+
+/// How a member is reported, e.g. `backup.zip!etc/app.toml`.
+pub(crate) fn member_path(archive: &Path, member: &str) -> PathBuf {
+	PathBuf::from(format!(
+		"{}{}{}",
+		archive.display(),
+		SEPARATOR,
+		member
+	))
+}
+
+/// Lists the members of the archive at `path` without extracting them.
+pub(crate) fn members(path: &Path) -> io::Result<Vec<Member>> {
+	let name = path.file_name().unwrap_or_default().to_string_lossy();
+	let file = BufReader::new(File::open(path)?);
+
+	match Kind::of(&name) {
+		Some(Kind::Zip) => zip_members(file),
+		Some(Kind::Tar) => tar_members(file),
+		Some(Kind::TarGz) => tar_members(GzDecoder::new(file)),
+		None => Ok(Vec::new()),
+	}
+}
+
+fn zip_members<R: Read + io::Seek>(
+	reader: R,
+) -> io::Result<Vec<Member>> {
+	let mut archive =
+		zip::ZipArchive::new(reader).map_err(io::Error::other)?;
+	(0..archive.len())
+		.map(|index| {
+			let file = archive
+				.by_index_raw(index)
+				.map_err(io::Error::other)?;
+			Ok(Member {
+				name: file.name().trim_end_matches('/').to_string(),
+				is_dir: file.is_dir(),
+				size: file.size(),
+				modified: None,
+			})
+		})
+		.collect()
+}
+
+fn tar_members<R: Read>(reader: R) -> io::Result<Vec<Member>> {
+	let mut archive = tar::Archive::new(reader);
+	archive
+		.entries()?
+		.map(|entry| {
+			let entry = entry?;
+			let header = entry.header();
+			let name = entry.path()?.to_string_lossy().into_owned();
+			Ok(Member {
+				name: name
+					.trim_start_matches("./")
+					.trim_end_matches('/')
+					.to_string(),
+				is_dir: header.entry_type().is_dir(),
+				size: entry.size(),
+				modified: header.mtime().ok().map(|secs| {
+					UNIX_EPOCH + Duration::from_secs(secs)
+				}),
+			})
+		})
+		.filter(|member| {
+			member
+				.as_ref()
+				.map_or(true, |member| !member.name.is_empty())
+		})
+		.collect()
+}
+
+#[cfg(test)]
+mod tests {
+	use crate::data::Search;
+	use flate2::{write::GzEncoder, Compression};
+	use std::{env, fs, io::Write};
+
+	#[test]
+	fn test_members_are_searched_inside_archives() {
+		let root = env::temp_dir()
+			.join(format!("whers-archives-{}", std::process::id()));
+		fs::create_dir_all(&root).unwrap();
+
+		let mut zip = zip::ZipWriter::new(
+			fs::File::create(root.join("backup.zip")).unwrap(),
+		);
+		let options = zip::write::SimpleFileOptions::default()
+			.compression_method(zip::CompressionMethod::Stored);
+		zip.start_file("etc/app.toml", options).unwrap();
+		zip.write_all(b"port = 80").unwrap();
+		zip.finish().unwrap();
+
+		let gz = GzEncoder::new(
+			fs::File::create(root.join("old.tar.gz")).unwrap(),
+			Compression::fast(),
+		);
+		let mut tar = tar::Builder::new(gz);
+		let mut header = tar::Header::new_gnu();
+		header.set_size(4);
+		header.set_cksum();
+		tar.append_data(&mut header, "conf/app.toml", &b"x=1\n"[..])
+			.unwrap();
+		tar.into_inner().unwrap().finish().unwrap();
+
+		let search = Search::default().with_pattern(r"^app\.toml$");
+		let plain = search.clone().execute_in(&root);
+		let inside = search.clone().with_archives().execute_in(&root);
+		let big =
+			search.with_archives().with_min_size(5).execute_in(&root);
+		fs::remove_dir_all(&root).unwrap();
+
+		assert!(plain.is_err());
+		assert_eq!(
+			inside.unwrap(),
+			vec![
+				root.join("backup.zip!etc/app.toml"),
+				root.join("old.tar.gz!conf/app.toml"),
+			]
+		);
+		assert_eq!(
+			big.unwrap(),
+			vec![root.join("backup.zip!etc/app.toml")]
+		);
+	}
+}
@@ -1,3 +1,6 @@
+use super::{Direction, Error};
+use std::{str::FromStr, time::Duration};
+
 #[derive(Default, Debug, Clone, PartialEq, Eq)]
 pub struct Search {
 	/// The pattern to search for.
@@ -79,4 +82,103 @@ pub struct Search {
 	/// If set to `true`, the search returns only an exit code (0 or 1).
 	/// The default value is `false`.
 	pub quiet: bool,
+
+	/// Only report entries of this type.
+	///
+	/// If `None`, entries of every type are reported.
+	pub path_type: Option<PathType>,
+
+	/// Only report entries at least this many bytes in size.
+	pub min_size: Option<u64>,
+
+	/// Only report entries at most this many bytes in size.
+	pub max_size: Option<u64>,
+
+	/// Only report entries modified within this long of the search.
+	pub modified_within: Option<Duration>,
+
+	/// Rank entries by fuzzy match quality instead of matching patterns as regular expressions.
+	///
+	/// Results are ordered best first, shallower paths winning ties, and `limit` keeps the top entries.
+	pub fuzzy: bool,
+
+	/// Which way to search from the starting directory.
+	///
+	/// The default, `Direction::Down`, searches below it.
+	pub direction: Direction,
+
+	/// Answer downward searches from a cached file-name index.
+	///
+	/// See `Index` for how the index is stored and refreshed.
+	pub indexed: bool,
+
+	/// Also match the members of zip and tar(.gz) archives.
+	///
+	/// Members are reported as `archive.zip!inner/path`. Indexed searches do not look inside archives.
+	pub archives: bool,
+}
+
+/// The kind of entry a search reports.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PathType {
+	File,
+	Dir,
+	Symlink,
+}
+
+impl PathType {
+	/// The type of a directory entry, if it is one a search reports.
+	pub fn of(file_type: std::fs::FileType) -> Option<Self> {
+		if file_type.is_symlink() {
+			Some(PathType::Symlink)
+		} else if file_type.is_dir() {
+			Some(PathType::Dir)
+		} else if file_type.is_file() {
+			Some(PathType::File)
+		} else {
+			None
+		}
+	}
+}
+
+impl FromStr for PathType {
+	type Err = Error;
+
+	fn from_str(s: &str) -> Result<Self, Self::Err> {
+		match s.to_lowercase().as_str() {
+			"f" | "file" => Ok(PathType::File),
+			"d" | "dir" | "directory" => Ok(PathType::Dir),
+			"l" | "link" | "symlink" => Ok(PathType::Symlink),
+			_ => Err(Error::PathType(s.to_string())),
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn test_path_type_parses_names() {
+		assert_eq!(
+			"f".parse::<PathType>().ok(),
+			Some(PathType::File)
+		);
+		assert_eq!(
+			"Dir".parse::<PathType>().ok(),
+			Some(PathType::Dir)
+		);
+		assert_eq!(
+			"symlink".parse::<PathType>().ok(),
+			Some(PathType::Symlink)
+		);
+	}
+
+	#[test]
+	fn test_path_type_rejects_unknown() {
+		let err = "socket".parse::<PathType>().unwrap_err();
+		assert!(
+			matches!(err, Error::PathType(ref s) if s == "socket")
+		);
+	}
 }
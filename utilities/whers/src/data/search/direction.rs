@@ -0,0 +1,57 @@
+use super::Error;
+use std::str::FromStr;
+
+/// Which way a search travels from its starting directory.
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Direction {
+	/// Search the starting directory and everything below it.
+	#[default]
+	Down,
+	/// Search the starting directory and its ancestors, stopping at
+	/// the first project or home boundary.
+	Up,
+	/// Search below the starting directory, then above it.
+	Both,
+}
+
+impl FromStr for Direction {
+	type Err = Error;
+
+	fn from_str(s: &str) -> Result<Self, Self::Err> {
+		match s.to_lowercase().as_str() {
+			"down" | "d" => Ok(Direction::Down),
+			"up" | "u" => Ok(Direction::Up),
+			"both" | "b" => Ok(Direction::Both),
+			_ => Err(Error::Direction(s.to_string())),
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn test_direction_parses_names() {
+		assert_eq!(
+			"d".parse::<Direction>().ok(),
+			Some(Direction::Down)
+		);
+		assert_eq!(
+			"UP".parse::<Direction>().ok(),
+			Some(Direction::Up)
+		);
+		assert_eq!(
+			"both".parse::<Direction>().ok(),
+			Some(Direction::Both)
+		);
+	}
+
+	#[test]
+	fn test_direction_rejects_unknown() {
+		let err = "sideways".parse::<Direction>().unwrap_err();
+		assert!(
+			matches!(err, Error::Direction(ref s) if s == "sideways")
+		);
+	}
+}
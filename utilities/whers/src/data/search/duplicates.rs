@@ -0,0 +1,347 @@
+use super::{Error, PathType, Search};
+use logline::debug;
+use std::{
+	collections::HashMap,
+	env,
+	fs::{self, File},
+	hash::{DefaultHasher, Hasher},
+	io::{self, Read},
+	path::{Path, PathBuf},
+	thread,
+};
+
+/// Bytes hashed from the start of each file before hashing it whole.
+const PREFIX: u64 = 4096;
+
+/// Files with identical contents.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Duplicates {
+	/// The size of each copy in bytes.
+	pub size: u64,
+
+	/// Every copy, in the order the search found them.
+	pub paths: Vec<PathBuf>,
+}
+
+impl Duplicates {
+	/// Bytes freed by keeping only one copy.
+	pub fn reclaimable(&self) -> u64 {
+		self.size * (self.paths.len() as u64).saturating_sub(1)
+	}
+}
+
+impl Search {
+	/// Finds duplicate files below the current directory.
+	pub fn duplicates(&self) -> Result<Vec<Duplicates>, Error> {
+		self.duplicates_in(env::current_dir()?)
+	}
+
+	/// Groups the regular files this search matches below `root` by
+	/// identical contents, most reclaimable space first.
+	///
+	/// Files are grouped by size, then by a hash of their first few
+	/// kilobytes, and only the survivors are hashed in full, spread
+	/// across threads. Files sharing a hash are then compared byte for
+	/// byte, so a collision never reports different files as copies.
+	/// Empty files are ignored and the limit applies to the number of
+	/// sets.
+	pub fn duplicates_in<P: AsRef<Path>>(
+		&self,
+		root: P,
+	) -> Result<Vec<Duplicates>, Error> {
+		let mut search = self.clone().with_type(PathType::File);
+		search.limit = None;
+		search.archives = false;
+
+		let mut by_size: HashMap<u64, Vec<PathBuf>> = HashMap::new();
+		for path in search.execute_in(root)? {
+			match fs::metadata(&path) {
+				Ok(metadata) if metadata.len() > 0 => by_size
+					.entry(metadata.len())
+					.or_default()
+					.push(path),
+				Ok(_) => {}
+				Err(e) => {
+					debug!("Skipping {}: {}", path.display(), e)
+				}
+			}
+		}
+
+		let groups = by_size
+			.into_iter()
+			.filter(|(_, paths)| paths.len() > 1)
+			.map(|(size, paths)| Duplicates { size, paths })
+			.collect();
+		let groups =
+			regroup(groups, |path, _| hash(path, Some(PREFIX)));
+		let groups = regroup(groups, |path, size| match size {
+			size if size <= PREFIX => Ok(0),
+			_ => hash(path, None),
+		});
+		let mut groups = confirm(groups);
+
+		groups.sort_by(|a, b| {
+			b.reclaimable()
+				.cmp(&a.reclaimable())
+				.then_with(|| a.paths.cmp(&b.paths))
+		});
+		groups.truncate(self.limit.unwrap_or(usize::MAX));
+
+		if groups.is_empty() {
+			Err(Error::NoResults)
+		} else {
+			Ok(groups)
+		}
+	}
+}
+
+/// Splits each group by the `key` of its files, hashing in parallel and
+/// dropping files that cannot be read and groups left with one file.
+fn regroup<F>(groups: Vec<Duplicates>, key: F) -> Vec<Duplicates>
+where
+	F: Fn(&Path, u64) -> io::Result<u64> + Sync,
+{
+	let files: Vec<(usize, u64, &Path)> = groups
+		.iter()
+		.enumerate()
+		.flat_map(|(index, group)| {
+			group
+				.paths
+				.iter()
+				.map(move |path| (index, group.size, path.as_path()))
+		})
+		.collect();
+	if files.is_empty() {
+		return Vec::new();
+	}
+
+	let threads =
+		thread::available_parallelism().map_or(1, usize::from);
+	let chunk = files.len().div_ceil(threads);
+	let keys: Vec<Option<u64>> = thread::scope(|scope| {
+		let key = &key;
+		let workers: Vec<_> = files
+			.chunks(chunk)
+			.map(|chunk| {
+				scope.spawn(move || {
+					chunk
+						.iter()
+						.map(|&(_, size, path)| {
+							match key(path, size) {
+								Ok(key) => Some(key),
+								Err(e) => {
+									debug!(
+										"Skipping {}: {}",
+										path.display(),
+										e
+									);
+									None
+								}
+							}
+						})
+						.collect::<Vec<_>>()
+				})
+			})
+			.collect();
+		workers
+			.into_iter()
+			.flat_map(|worker| worker.join().unwrap_or_default())
+			.collect()
+	});
+
+	let mut split: HashMap<(usize, u64), Vec<PathBuf>> =
+		HashMap::new();
+	let mut order = Vec::new();
+	for ((index, _, path), key) in files.iter().zip(keys) {
+		let Some(key) = key else { continue };
+		let paths = split.entry((*index, key)).or_insert_with(|| {
+			order.push((*index, key));
+			Vec::new()
+		});
+		paths.push(path.to_path_buf());
+	}
+
+	order
+		.into_iter()
+		.filter_map(|id| {
+			let paths = split.remove(&id)?;
+			(paths.len() > 1).then(|| Duplicates {
+				size: groups[id.0].size,
+				paths,
+			})
+		})
+		.collect()
+}
+
+/// Splits each group into sets whose files match byte for byte,
+/// dropping files that cannot be read and sets left with one file.
+fn confirm(groups: Vec<Duplicates>) -> Vec<Duplicates> {
+	groups
+		.into_iter()
+		.flat_map(|group| {
+			let mut sets: Vec<Vec<PathBuf>> = Vec::new();
+			'paths: for path in group.paths {
+				// Only readable files may stand for a set
+				if let Err(e) = File::open(&path) {
+					debug!("Skipping {}: {}", path.display(), e);
+					continue;
+				}
+				for set in &mut sets {
+					match same(&set[0], &path) {
+						Ok(true) => {
+							set.push(path);
+							continue 'paths;
+						}
+						Ok(false) => {}
+						Err(e) => {
+							debug!(
+								"Skipping {}: {}",
+								path.display(),
+								e
+							);
+							continue 'paths;
+						}
+					}
+				}
+				sets.push(vec![path]);
+			}
+
+			let size = group.size;
+			sets.into_iter()
+				.filter(|paths| paths.len() > 1)
+				.map(move |paths| Duplicates { size, paths })
+		})
+		.collect()
+}
+
+/// Whether two files hold the same bytes.
+fn same(a: &Path, b: &Path) -> io::Result<bool> {
+	let (mut a, mut b) = (File::open(a)?, File::open(b)?);
+	let (mut left, mut right) =
+		(vec![0; 64 * 1024], vec![0; 64 * 1024]);
+	loop {
+		let read = fill(&mut a, &mut left)?;
+		if read != fill(&mut b, &mut right)?
+			|| left[..read] != right[..read]
+		{
+			return Ok(false);
+		}
+		if read == 0 {
+			return Ok(true);
+		}
+	}
+}
+
+/// Reads until `buffer` is full or the reader ends, returning the
+/// bytes read.
+fn fill(
+	reader: &mut impl Read,
+	buffer: &mut [u8],
+) -> io::Result<usize> {
+	let mut filled = 0;
+	while filled < buffer.len() {
+		match reader.read(&mut buffer[filled..])? {
+			0 => break,
+			read => filled += read,
+		}
+	}
+	Ok(filled)
+}
+
+/// Hashes the first `limit` bytes of a file, or all of it.
+fn hash(path: &Path, limit: Option<u64>) -> io::Result<u64> {
+	let file = File::open(path)?;
+	let mut reader: Box<dyn Read> = match limit {
+		Some(limit) => Box::new(file.take(limit)),
+		None => Box::new(file),
+	};
+
+	let mut hasher = DefaultHasher::new();
+	let mut buffer = [0; 64 * 1024];
+	loop {
+		match reader.read(&mut buffer)? {
+			0 => return Ok(hasher.finish()),
+			read => hasher.write(&buffer[..read]),
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn test_duplicates_group_by_content() {
+		let root = env::temp_dir()
+			.join(format!("whers-dupes-{}", std::process::id()));
+		fs::create_dir_all(root.join("backup")).unwrap();
+		let big = "x".repeat(10_000);
+		fs::write(root.join("a.txt"), &big).unwrap();
+		fs::write(root.join("backup/a.txt"), &big).unwrap();
+		fs::write(root.join("b.txt"), format!("{}y", &big[1..]))
+			.unwrap();
+		fs::write(root.join("c.txt"), "same").unwrap();
+		fs::write(root.join("backup/c.txt"), "same").unwrap();
+		fs::write(root.join("backup/d.txt"), "same").unwrap();
+		fs::write(root.join("empty"), "").unwrap();
+		fs::write(root.join("backup/empty"), "").unwrap();
+
+		let sets = Search::default().duplicates_in(&root);
+		let none = Search::default()
+			.with_pattern(r"^b")
+			.duplicates_in(&root);
+		fs::remove_dir_all(&root).unwrap();
+
+		let sets = sets.unwrap();
+		assert_eq!(sets.len(), 2);
+		assert_eq!(sets[0].size, 10_000);
+		assert_eq!(
+			sets[0].paths,
+			vec![root.join("a.txt"), root.join("backup/a.txt")]
+		);
+		assert_eq!(sets[0].reclaimable(), 10_000);
+		assert_eq!(sets[1].paths.len(), 3);
+		assert_eq!(sets[1].reclaimable(), 8);
+		assert!(matches!(none, Err(Error::NoResults)));
+	}
+
+	#[test]
+	fn test_confirm_splits_hash_collisions() {
+		let root = env::temp_dir()
+			.join(format!("whers-confirm-{}", std::process::id()));
+		fs::create_dir_all(&root).unwrap();
+		let big = "x".repeat(100_000);
+		for (name, content) in [
+			("a", "same".to_string()),
+			("b", "diff".to_string()),
+			("c", "same".to_string()),
+			("d", format!("{}1", big)),
+			("e", format!("{}2", big)),
+		] {
+			fs::write(root.join(name), content).unwrap();
+		}
+
+		// Stands in for groups whose hashes collided
+		let sets = confirm(vec![
+			Duplicates {
+				size: 4,
+				paths: ["missing", "a", "b", "c"]
+					.map(|name| root.join(name))
+					.to_vec(),
+			},
+			Duplicates {
+				size: 100_001,
+				paths: vec![root.join("d"), root.join("e")],
+			},
+		]);
+		fs::remove_dir_all(&root).unwrap();
+
+		assert_eq!(
+			sets,
+			[Duplicates {
+				size: 4,
+				paths: vec![root.join("a"), root.join("c")],
+			}]
+		);
+	}
+}
@@ -12,4 +12,8 @@ pub enum Error {
     Directory(String),
     #[error("Regex error: {0}")]
     Regex(#[from] regex::Error),
+    #[error("Unknown path type '{0}', expected file, dir or symlink")]
+    PathType(String),
+    #[error("Unknown direction '{0}', expected down, up or both")]
+    Direction(String),
 }
@@ -0,0 +1,265 @@
+use super::{Error, Search};
+use regex::{Regex, RegexBuilder};
+use std::{
+	collections::{HashMap, HashSet},
+	env, fs,
+	path::{Path, PathBuf},
+};
+
+/// An executable found on `PATH`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Executable {
+	/// The command name, without any `PATHEXT` extension.
+	pub name: String,
+
+	/// The full path to the executable.
+	pub path: PathBuf,
+
+	/// The earlier hit that runs instead of this one, if any.
+	pub shadowed_by: Option<PathBuf>,
+}
+
+impl Executable {
+	pub fn is_shadowed(&self) -> bool {
+		self.shadowed_by.is_some()
+	}
+}
+
+impl Search {
+	/// Looks up executables on `PATH` whose name matches a pattern.
+	///
+	/// Patterns are regular expressions matched against the whole
+	/// command name, so `cargo` finds only `cargo` while `cargo.*`
+	/// also finds `cargo-clippy`. Hits are returned in `PATH` order;
+	/// later hits for a name already seen are marked as shadowed.
+	///
+	/// On Windows, names match regardless of case, the current
+	/// directory is searched first and hits within a directory follow
+	/// `PATHEXT` order, as `cmd` resolves them.
+	pub fn executables(&self) -> Result<Vec<Executable>, Error> {
+		let patterns = self.exec_patterns()?;
+		let extensions = path_extensions();
+		let mut seen_dirs = HashSet::new();
+		let mut first: HashMap<String, PathBuf> = HashMap::new();
+		let mut hits = Vec::new();
+
+		for dir in search_path() {
+			if !seen_dirs
+				.insert(fs::canonicalize(&dir).unwrap_or(dir.clone()))
+			{
+				continue;
+			}
+			let Ok(entries) = fs::read_dir(&dir) else {
+				continue;
+			};
+
+			let mut found: Vec<(String, usize, PathBuf)> = entries
+				.filter_map(Result::ok)
+				.filter_map(|entry| {
+					let path = entry.path();
+					let name = command_name(&path, &extensions)?;
+					(patterns.iter().any(|re| re.is_match(&name))
+						&& is_executable(&path))
+					.then(|| {
+						(
+							name,
+							extension_rank(&path, &extensions),
+							path,
+						)
+					})
+				})
+				.collect();
+			sort_hits(&mut found);
+
+			for (name, _, path) in found {
+				let key = if cfg!(windows) {
+					name.to_lowercase()
+				} else {
+					name.clone()
+				};
+				let shadowed_by = first.get(&key).cloned();
+				if shadowed_by.is_none() {
+					first.insert(key, path.clone());
+				}
+				hits.push(Executable {
+					name,
+					path,
+					shadowed_by,
+				});
+
+				if self.limit.is_some_and(|limit| hits.len() >= limit)
+				{
+					return Ok(hits);
+				}
+			}
+		}
+
+		if hits.is_empty() {
+			Err(Error::NoResults)
+		} else {
+			Ok(hits)
+		}
+	}
+
+	fn exec_patterns(&self) -> Result<Vec<Regex>, Error> {
+		let patterns = if self.pattern.is_empty() {
+			vec![String::from(".*")]
+		} else {
+			self.pattern.clone()
+		};
+
+		patterns
+			.iter()
+			.map(|pattern| {
+				RegexBuilder::new(&format!("^(?:{pattern})$"))
+					.case_insensitive(
+						cfg!(windows) || !self.case_sensitive,
+					)
+					.build()
+					.map_err(Error::from)
+			})
+			.collect()
+	}
+}
+
+/// The directories listed in `PATH`, in order, after the current
+/// directory on Windows.
+fn search_path() -> Vec<PathBuf> {
+	let mut dirs = Vec::new();
+	if cfg!(windows) {
+		dirs.extend(env::current_dir());
+	}
+	if let Some(paths) = env::var_os("PATH") {
+		dirs.extend(env::split_paths(&paths));
+	}
+	dirs
+}
+
+/// The extensions that make a file executable, from `PATHEXT`.
+///
+/// Always empty outside Windows.
+fn path_extensions() -> Vec<String> {
+	if !cfg!(windows) {
+		return Vec::new();
+	}
+
+	env::var("PATHEXT")
+		.unwrap_or_else(|_| String::from(".COM;.EXE;.BAT;.CMD"))
+		.split(';')
+		.filter(|ext| !ext.is_empty())
+		.map(|ext| ext.trim_start_matches('.').to_lowercase())
+		.collect()
+}
+
+/// The name a file is invoked by, or `None` if its extension is
+/// not in `extensions` on platforms that use them.
+fn command_name(
+	path: &Path,
+	extensions: &[String],
+) -> Option<String> {
+	if extensions.is_empty() {
+		return path.file_name()?.to_str().map(String::from);
+	}
+
+	let ext = path.extension()?.to_str()?.to_lowercase();
+	if !extensions.contains(&ext) {
+		return None;
+	}
+	path.file_stem()?.to_str().map(String::from)
+}
+
+/// Where the file's extension sits in `extensions`, so `cargo.exe`
+/// comes before `cargo.cmd` when `PATHEXT` lists them that way.
+fn extension_rank(path: &Path, extensions: &[String]) -> usize {
+	path.extension()
+		.and_then(|ext| ext.to_str())
+		.and_then(|ext| {
+			extensions
+				.iter()
+				.position(|e| e.eq_ignore_ascii_case(ext))
+		})
+		.unwrap_or(0)
+}
+
+/// Orders one directory's hits by name, then by extension rank.
+fn sort_hits(found: &mut [(String, usize, PathBuf)]) {
+	found.sort_by(|a, b| {
+		let key = |name: &str| {
+			if cfg!(windows) {
+				name.to_lowercase()
+			} else {
+				name.to_string()
+			}
+		};
+		(key(&a.0), a.1, &a.2).cmp(&(key(&b.0), b.1, &b.2))
+	});
+}
+
+#[cfg(unix)]
+fn is_executable(path: &Path) -> bool {
+	use std::os::unix::fs::PermissionsExt;
+	fs::metadata(path).is_ok_and(|m| {
+		m.is_file() && m.permissions().mode() & 0o111 != 0
+	})
+}
+
+#[cfg(not(unix))]
+fn is_executable(path: &Path) -> bool {
+	path.is_file()
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn test_command_name_respects_extensions() {
+		let exts = vec![String::from("exe"), String::from("cmd")];
+
+		assert_eq!(
+			command_name(Path::new("bin/cargo"), &[]).as_deref(),
+			Some("cargo")
+		);
+		assert_eq!(
+			command_name(Path::new("bin/cargo.EXE"), &exts)
+				.as_deref(),
+			Some("cargo")
+		);
+		assert_eq!(
+			command_name(Path::new("bin/notes.txt"), &exts),
+			None
+		);
+	}
+
+	#[test]
+	fn test_hits_follow_extension_order() {
+		let exts: Vec<String> =
+			["com", "exe", "bat"].map(String::from).to_vec();
+		let hit = |file: &str| {
+			let path = PathBuf::from(file);
+			let name = command_name(&path, &exts).unwrap();
+			(name, extension_rank(&path, &exts), path)
+		};
+		let mut found =
+			vec![hit("tool.bat"), hit("cargo.exe"), hit("tool.COM")];
+		sort_hits(&mut found);
+
+		let paths: Vec<_> = found
+			.iter()
+			.map(|(_, _, path)| path.to_str().unwrap())
+			.collect();
+		assert_eq!(paths, ["cargo.exe", "tool.COM", "tool.bat"]);
+	}
+
+	#[test]
+	fn test_patterns_match_whole_name() {
+		let patterns = Search::default()
+			.with_pattern("cargo")
+			.exec_patterns()
+			.unwrap();
+
+		assert!(patterns[0].is_match("cargo"));
+		assert!(patterns[0].is_match("Cargo"));
+		assert!(!patterns[0].is_match("cargo-clippy"));
+	}
+}
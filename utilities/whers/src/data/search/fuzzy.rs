@@ -0,0 +1,123 @@
+const MATCH: i32 = 16;
+const GAP_START: i32 = -3;
+const GAP_EXTENSION: i32 = -1;
+const CONSECUTIVE: i32 = 4;
+const BOUNDARY: i32 = 8;
+const CAMEL_CASE: i32 = 7;
+const FIRST_CHAR_MULTIPLIER: i32 = 2;
+
+/// Scores how well `pattern` matches `candidate` as a subsequence.
+///
+/// Uses a Smith-Waterman style alignment in the spirit of fzf:
+/// matches at word boundaries and runs of consecutive characters score
+/// higher, gaps between matched characters cost a little. Returns
+/// `None` when the pattern is not a subsequence of the candidate.
+pub fn score(
+	pattern: &str,
+	candidate: &str,
+	case_sensitive: bool,
+) -> Option<i32> {
+	let fold = |c: char| {
+		if case_sensitive {
+			c
+		} else {
+			c.to_ascii_lowercase()
+		}
+	};
+	let pattern: Vec<char> = pattern.chars().map(fold).collect();
+	let original: Vec<char> = candidate.chars().collect();
+	let text: Vec<char> =
+		original.iter().copied().map(fold).collect();
+
+	if pattern.is_empty() {
+		return Some(0);
+	}
+	if !is_subsequence(&pattern, &text) {
+		return None;
+	}
+
+	let bonus: Vec<i32> = (0..original.len())
+		.map(|i| boundary_bonus(&original, i))
+		.collect();
+	let mut previous = vec![None; text.len()];
+
+	for (i, &p) in pattern.iter().enumerate() {
+		let mut row = vec![None; text.len()];
+		let mut gapped: Option<i32> = None;
+
+		for j in 0..text.len() {
+			if i > 0 && j >= 2 {
+				let opened =
+					previous[j - 2].map(|s: i32| s + GAP_START);
+				gapped = match (gapped, opened) {
+					(Some(g), Some(o)) => {
+						Some((g + GAP_EXTENSION).max(o))
+					}
+					(Some(g), None) => Some(g + GAP_EXTENSION),
+					(None, opened) => opened,
+				};
+			}
+			if text[j] != p {
+				continue;
+			}
+
+			row[j] = if i == 0 {
+				Some(MATCH + bonus[j] * FIRST_CHAR_MULTIPLIER)
+			} else {
+				let consecutive = j
+					.checked_sub(1)
+					.and_then(|k| previous[k])
+					.map(|s| s + CONSECUTIVE);
+				consecutive.max(gapped).map(|s| s + MATCH + bonus[j])
+			};
+		}
+
+		previous = row;
+	}
+
+	previous.into_iter().flatten().max()
+}
+
+fn is_subsequence(pattern: &[char], text: &[char]) -> bool {
+	let mut text = text.iter();
+	pattern.iter().all(|p| text.any(|c| c == p))
+}
+
+fn boundary_bonus(text: &[char], i: usize) -> i32 {
+	let Some(&prev) = i.checked_sub(1).and_then(|k| text.get(k))
+	else {
+		return BOUNDARY;
+	};
+	let current = text[i];
+
+	if matches!(prev, '/' | '\\' | '_' | '-' | '.' | ' ') {
+		BOUNDARY
+	} else if (prev.is_lowercase() && current.is_uppercase())
+		|| (!prev.is_ascii_digit() && current.is_ascii_digit())
+	{
+		CAMEL_CASE
+	} else {
+		0
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn test_fuzzy_score_ranking() {
+		assert!(score("cfg", "config.toml", false).is_some());
+		assert!(score("cfg", "README.md", false).is_none());
+
+		let boundary = score("ct", "config.toml", false).unwrap();
+		let inner = score("ct", "accent", false).unwrap();
+		assert!(boundary > inner);
+
+		let tight = score("conf", "config.toml", false).unwrap();
+		let loose = score("conf", "coin-of.txt", false).unwrap();
+		assert!(tight > loose);
+
+		assert!(score("CFG", "config", true).is_none());
+	}
+}
@@ -1,7 +1,16 @@
+mod archives;
 mod config;
+mod direction;
+mod duplicates;
 mod error;
+mod exec;
+mod fuzzy;
 mod utils;
+mod walk;
 mod tests;
 
-pub use config::Search;
+pub use config::{PathType, Search};
+pub use direction::Direction;
+pub use duplicates::Duplicates;
 pub use error::Error;
+pub use exec::Executable;
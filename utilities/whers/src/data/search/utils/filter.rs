@@ -0,0 +1,46 @@
+use crate::data::PathType;
+use std::time::Duration;
+
+impl crate::Search {
+	/// Restricts the search to entries of one type.
+	///
+	/// # Arguments
+	///
+	/// * `path_type` - A `PathType`; names such as `"dir"` parse into one.
+	///
+	/// # Returns
+	///
+	/// The updated Search instance.
+	pub fn with_type<T: Into<PathType>>(
+		mut self,
+		path_type: T,
+	) -> Self {
+		self.path_type = Some(path_type.into());
+		self
+	}
+
+	/// Skips entries smaller than `bytes`.
+	pub fn with_min_size(mut self, bytes: u64) -> Self {
+		self.min_size = Some(bytes);
+		self
+	}
+
+	/// Skips entries larger than `bytes`.
+	pub fn with_max_size(mut self, bytes: u64) -> Self {
+		self.max_size = Some(bytes);
+		self
+	}
+
+	/// Skips entries last modified more than `age` ago.
+	pub fn modified_within(mut self, age: Duration) -> Self {
+		self.modified_within = Some(age);
+		self
+	}
+
+	/// Whether any filter needs the entry's metadata.
+	pub(crate) fn needs_metadata(&self) -> bool {
+		self.min_size.is_some()
+			|| self.max_size.is_some()
+			|| self.modified_within.is_some()
+	}
+}
@@ -1,5 +1,4 @@
 impl crate::Search {
-
 	/// Sets the case sensitivity of the search.
 	///
 	/// # Arguments
@@ -37,6 +36,61 @@ impl crate::Search {
 		self
 	}
 
+	/// Limits how deep below the starting directory the search goes.
+	pub fn with_max_depth(mut self, depth: usize) -> Self {
+		self.max_depth = Some(depth);
+		self
+	}
+
+	/// Only reports entries at least `depth` levels deep.
+	pub fn with_min_depth(mut self, depth: usize) -> Self {
+		self.min_depth = Some(depth);
+		self
+	}
+
+	/// Includes hidden files and directories in the search.
+	pub fn with_hidden(mut self) -> Self {
+		self.include_hidden = true;
+		self
+	}
+
+	/// Skips entries whose name matches any of the glob patterns.
+	pub fn with_exclude<I, S>(mut self, globs: I) -> Self
+	where
+		I: IntoIterator<Item = S>,
+		S: Into<String>,
+	{
+		self.exclude.extend(globs.into_iter().map(Into::into));
+		self
+	}
+
+	/// Ranks results by fuzzy match quality, so `cfg` finds `config.toml`.
+	pub fn fuzzy(mut self) -> Self {
+		self.fuzzy = true;
+		self
+	}
+
+	/// Sets which way to search, e.g. `Direction::Up`.
+	pub fn with_direction<D: Into<crate::Direction>>(
+		mut self,
+		direction: D,
+	) -> Self {
+		self.direction = direction.into();
+		self
+	}
+
+	/// Answers the search from the cached file-name index.
+	pub fn indexed(mut self) -> Self {
+		self.indexed = true;
+		self
+	}
+
+	/// Looks inside zip and tar(.gz) archives as well.
+	pub fn with_archives(mut self) -> Self {
+		self.archives = true;
+		self
+	}
+
 	// pub fn with_limit<L>(mut self, limit: L) -> Self
 	// where
 	// 	L: Into<Option<isize>>, // Accept signed integers for better flexibility
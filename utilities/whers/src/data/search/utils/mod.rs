@@ -1,3 +1,4 @@
+mod filter;
 mod misc;
 mod limit;
 mod pattern;
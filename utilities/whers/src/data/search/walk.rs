@@ -0,0 +1,524 @@
+use super::{
+	archives::{self, Member},
+	fuzzy, Direction, Error, PathType, Search,
+};
+use crate::data::Index;
+use directories::BaseDirs;
+use logline::{debug, throttle, trace};
+use regex::{Regex, RegexBuilder};
+use std::{
+	env,
+	fs::{self, DirEntry, Metadata},
+	path::{Path, PathBuf},
+	time::SystemTime,
+};
+
+/// Files or directories that mark the top of a project.
+const BOUNDARY_MARKERS: [&str; 1] = [".git"];
+
+impl Search {
+	/// Runs the search from the current directory.
+	pub fn execute(&self) -> Result<Vec<PathBuf>, Error> {
+		self.execute_in(env::current_dir()?)
+	}
+
+	/// Runs the search from `root` in the configured direction.
+	///
+	/// Downward hits come first, depth first in name order, followed by
+	/// upward hits from the nearest ancestor outwards. Fuzzy searches
+	/// instead order every hit best match first.
+	///
+	/// Type, size and modification filters are checked as each entry
+	/// is visited, and metadata is only read when a filter needs it.
+	pub fn execute_in<P: AsRef<Path>>(
+		&self,
+		root: P,
+	) -> Result<Vec<PathBuf>, Error> {
+		let root = root.as_ref();
+		if !root.is_dir() {
+			return Err(Error::Directory(format!(
+				"Not a directory: {}",
+				root.display()
+			)));
+		}
+		if self.indexed && self.direction == Direction::Down {
+			return Index::open(root)?.search(self);
+		}
+
+		let mut walk = Walk {
+			search: self,
+			patterns: self.name_patterns()?,
+			cutoff: self.cutoff(),
+			hits: Vec::new(),
+		};
+
+		match self.direction {
+			Direction::Down => {
+				walk.down(root);
+			}
+			Direction::Up => {
+				walk.up(&fs::canonicalize(root)?);
+			}
+			Direction::Both => {
+				let root_path = fs::canonicalize(root)?;
+				if !walk.down(root) {
+					if let Some(parent) = root_path.parent() {
+						walk.up(parent);
+					}
+				}
+			}
+		}
+
+		self.rank(walk.hits)
+	}
+
+	/// Orders scored `(score, depth, path)` hits for output, best first
+	/// when fuzzy, and applies the limit.
+	pub(crate) fn rank(
+		&self,
+		mut hits: Vec<(i32, usize, PathBuf)>,
+	) -> Result<Vec<PathBuf>, Error> {
+		if self.fuzzy {
+			hits.sort_by(|a, b| {
+				b.0.cmp(&a.0).then(a.1.cmp(&b.1)).then(a.2.cmp(&b.2))
+			});
+		}
+		hits.truncate(self.limit.unwrap_or(usize::MAX));
+
+		if hits.is_empty() {
+			Err(Error::NoResults)
+		} else {
+			Ok(hits.into_iter().map(|(_, _, path)| path).collect())
+		}
+	}
+
+	/// The oldest modification time `modified_within` accepts.
+	pub(crate) fn cutoff(&self) -> Option<SystemTime> {
+		self.modified_within
+			.and_then(|age| SystemTime::now().checked_sub(age))
+	}
+
+	pub(crate) fn name_patterns(&self) -> Result<Vec<Regex>, Error> {
+		if self.fuzzy {
+			return Ok(Vec::new());
+		}
+		self.pattern
+			.iter()
+			.map(|pattern| {
+				RegexBuilder::new(pattern)
+					.case_insensitive(!self.case_sensitive)
+					.build()
+					.map_err(Error::from)
+			})
+			.collect()
+	}
+
+	/// Scores a name against every pattern, or `None` if any misses.
+	///
+	/// Regular expression matches all score zero.
+	pub(crate) fn name_score(
+		&self,
+		patterns: &[Regex],
+		name: &str,
+	) -> Option<i32> {
+		if !self.fuzzy {
+			return patterns
+				.iter()
+				.all(|re| re.is_match(name))
+				.then_some(0);
+		}
+		self.pattern.iter().try_fold(0, |total, pattern| {
+			Some(
+				total
+					+ fuzzy::score(
+						pattern,
+						name,
+						self.case_sensitive,
+					)?,
+			)
+		})
+	}
+
+	pub(crate) fn is_excluded(&self, name: &str) -> bool {
+		(!self.include_hidden && name.starts_with('.'))
+			|| self.exclude.iter().any(|glob| glob_match(glob, name))
+	}
+
+	/// Applies the type, size and modification filters to an entry.
+	fn keep(
+		&self,
+		entry: &DirEntry,
+		file_type: fs::FileType,
+		cutoff: Option<SystemTime>,
+	) -> bool {
+		self.keeps_type(PathType::of(file_type))
+			&& (!self.needs_metadata()
+				|| entry.metadata().is_ok_and(|metadata| {
+					self.keeps_metadata(&metadata, cutoff)
+				}))
+	}
+
+	pub(crate) fn keeps_type(
+		&self,
+		path_type: Option<PathType>,
+	) -> bool {
+		self.path_type
+			.is_none_or(|wanted| path_type == Some(wanted))
+	}
+
+	pub(crate) fn keeps_metadata(
+		&self,
+		metadata: &Metadata,
+		cutoff: Option<SystemTime>,
+	) -> bool {
+		self.keeps_size(metadata.len())
+			&& cutoff.is_none_or(|cutoff| {
+				metadata.modified().is_ok_and(|time| time >= cutoff)
+			})
+	}
+
+	fn keeps_size(&self, size: u64) -> bool {
+		self.min_size.is_none_or(|min| size >= min)
+			&& self.max_size.is_none_or(|max| size <= max)
+	}
+
+	/// Applies the depth, name and filter checks to an archive member
+	/// found `depth` levels down, scoring its file name.
+	fn keeps_member(
+		&self,
+		patterns: &[Regex],
+		member: &Member,
+		depth: usize,
+		cutoff: Option<SystemTime>,
+	) -> Option<i32> {
+		let depth = depth + member.depth();
+		if self.min_depth.is_some_and(|min| depth < min)
+			|| self.max_depth.is_some_and(|max| depth > max)
+			|| member
+				.name
+				.split('/')
+				.any(|part| self.is_excluded(part))
+		{
+			return None;
+		}
+
+		let path_type = match member.is_dir {
+			true => PathType::Dir,
+			false => PathType::File,
+		};
+		let keep = self.keeps_type(Some(path_type))
+			&& self.keeps_size(member.size)
+			&& cutoff.is_none_or(|cutoff| {
+				member.modified.is_some_and(|time| time >= cutoff)
+			});
+		keep.then(|| self.name_score(patterns, member.file_name()))?
+	}
+}
+
+/// State shared by the downward and upward passes of a search.
+struct Walk<'a> {
+	search: &'a Search,
+	patterns: Vec<Regex>,
+	cutoff: Option<SystemTime>,
+	hits: Vec<(i32, usize, PathBuf)>,
+}
+
+impl Walk<'_> {
+	/// Searches below `root`, returning `true` once the limit is hit.
+	fn down(&mut self, root: &Path) -> bool {
+		let mut stack = vec![(root.to_path_buf(), 0)];
+
+		while let Some((dir, depth)) = stack.pop() {
+			let depth = depth + 1;
+			let mut subdirs = Vec::new();
+
+			for (entry, file_type) in self.entries(&dir) {
+				if file_type.is_dir()
+					&& self
+						.search
+						.max_depth
+						.is_none_or(|max| depth < max)
+				{
+					subdirs.push(entry.path());
+				}
+				if self.visit(&entry, file_type, depth) {
+					return true;
+				}
+			}
+
+			stack.extend(
+				subdirs.into_iter().rev().map(|dir| (dir, depth)),
+			);
+		}
+
+		false
+	}
+
+	/// Searches the entries of `start` and each of its ancestors,
+	/// stopping after the first boundary directory.
+	///
+	/// A boundary is a directory holding a `.git` marker or the home
+	/// directory; the filesystem root always ends the walk. Depth
+	/// counts the levels climbed, starting at one for `start`.
+	fn up(&mut self, start: &Path) -> bool {
+		let home =
+			BaseDirs::new().map(|dirs| dirs.home_dir().to_path_buf());
+
+		for (level, dir) in start.ancestors().enumerate() {
+			let depth = level + 1;
+			if self.search.max_depth.is_some_and(|max| depth > max) {
+				break;
+			}
+
+			for (entry, file_type) in self.entries(dir) {
+				if self.visit(&entry, file_type, depth) {
+					return true;
+				}
+			}
+
+			let is_boundary = BOUNDARY_MARKERS
+				.iter()
+				.any(|marker| dir.join(marker).exists())
+				|| home.as_deref() == Some(dir);
+			if is_boundary {
+				debug!("Stopping upward search at {}", dir.display());
+				break;
+			}
+		}
+
+		false
+	}
+
+	/// Lists the visible entries of `dir` in name order.
+	fn entries(&self, dir: &Path) -> Vec<(DirEntry, fs::FileType)> {
+		let entries = match fs::read_dir(dir) {
+			Ok(entries) => entries,
+			Err(e) => {
+				debug!("Skipping {}: {}", dir.display(), e);
+				return Vec::new();
+			}
+		};
+
+		let mut entries: Vec<(DirEntry, fs::FileType)> = entries
+			.filter_map(Result::ok)
+			.filter(|entry| {
+				!self
+					.search
+					.is_excluded(&entry.file_name().to_string_lossy())
+			})
+			.filter_map(|entry| {
+				let file_type = entry.file_type().ok()?;
+				Some((entry, file_type))
+			})
+			.collect();
+		entries.sort_by_key(|(entry, _)| entry.file_name());
+		entries
+	}
+
+	/// Records `entry` if it passes every filter, followed by its
+	/// members when it is an archive, returning `true` once a non-fuzzy
+	/// search has reached its limit.
+	fn visit(
+		&mut self,
+		entry: &DirEntry,
+		file_type: fs::FileType,
+		depth: usize,
+	) -> bool {
+		throttle!(
+			every = 1000,
+			trace!("Visiting {}", entry.path().display())
+		);
+		let search = self.search;
+		let name = entry.file_name();
+		let name = name.to_string_lossy();
+
+		if let Some(score) =
+			self.score(entry, file_type, &name, depth)
+		{
+			self.hits.push((score, depth, entry.path()));
+			if self.is_full() {
+				return true;
+			}
+		}
+
+		search.archives
+			&& file_type.is_file()
+			&& archives::is_archive(&name)
+			&& self.archive(&entry.path(), depth)
+	}
+
+	/// Scores `entry` if it passes the depth, name and entry filters.
+	fn score(
+		&self,
+		entry: &DirEntry,
+		file_type: fs::FileType,
+		name: &str,
+		depth: usize,
+	) -> Option<i32> {
+		let search = self.search;
+		if search.min_depth.is_some_and(|min| depth < min) {
+			return None;
+		}
+		let score = search.name_score(&self.patterns, name)?;
+		search.keep(entry, file_type, self.cutoff).then_some(score)
+	}
+
+	/// Records the members of `archive` that pass every filter,
+	/// returning `true` once the limit is reached.
+	fn archive(&mut self, archive: &Path, depth: usize) -> bool {
+		let members = match archives::members(archive) {
+			Ok(members) => members,
+			Err(e) => {
+				debug!(
+					"Skipping archive {}: {}",
+					archive.display(),
+					e
+				);
+				return false;
+			}
+		};
+
+		for member in members {
+			let Some(score) = self.search.keeps_member(
+				&self.patterns,
+				&member,
+				depth,
+				self.cutoff,
+			) else {
+				continue;
+			};
+			let path = archives::member_path(archive, &member.name);
+			self.hits.push((score, depth + member.depth(), path));
+			if self.is_full() {
+				return true;
+			}
+		}
+
+		false
+	}
+
+	/// Whether a non-fuzzy search has reached its limit.
+	fn is_full(&self) -> bool {
+		!self.search.fuzzy
+			&& self
+				.search
+				.limit
+				.is_some_and(|limit| self.hits.len() >= limit)
+	}
+}
+
+/// Matches a name against a glob supporting `*` and `?`.
+fn glob_match(glob: &str, name: &str) -> bool {
+	let glob: Vec<char> = glob.chars().collect();
+	let name: Vec<char> = name.chars().collect();
+	let (mut g, mut n) = (0, 0);
+	let mut backtrack = None;
+
+	while n < name.len() {
+		match glob.get(g) {
+			Some('*') => {
+				backtrack = Some((g, n));
+				g += 1;
+			}
+			Some(&c) if c == '?' || c == name[n] => {
+				g += 1;
+				n += 1;
+			}
+			_ => match backtrack {
+				Some((star, matched)) => {
+					g = star + 1;
+					n = matched + 1;
+					backtrack = Some((star, matched + 1));
+				}
+				None => return false,
+			},
+		}
+	}
+
+	glob[g..].iter().all(|&c| c == '*')
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use std::time::Duration;
+
+	#[test]
+	fn test_glob_match() {
+		assert!(glob_match("target", "target"));
+		assert!(glob_match("*.log", "build.log"));
+		assert!(glob_match("node_?odules", "node_modules"));
+		assert!(!glob_match("*.log", "build.txt"));
+	}
+
+	#[test]
+	fn test_filters_apply_during_walk() {
+		let root = env::temp_dir()
+			.join(format!("whers-walk-{}", std::process::id()));
+		fs::create_dir_all(root.join("src/nested")).unwrap();
+		fs::write(root.join("src/small.rs"), "x").unwrap();
+		fs::write(root.join("src/nested/large.rs"), "x".repeat(2048))
+			.unwrap();
+
+		let search = Search::default().with_pattern(r"\.rs$");
+		let large =
+			search.clone().with_min_size(1024).execute_in(&root);
+		let dirs = Search::default()
+			.with_type(PathType::Dir)
+			.modified_within(Duration::from_secs(3600))
+			.execute_in(&root);
+		let shallow = search.with_max_depth(2).execute_in(&root);
+		let ranked = Search::default()
+			.with_pattern("s")
+			.fuzzy()
+			.with_limit(2)
+			.execute_in(&root);
+		fs::remove_dir_all(&root).unwrap();
+
+		assert_eq!(
+			large.unwrap(),
+			vec![root.join("src/nested/large.rs")]
+		);
+		assert_eq!(
+			dirs.unwrap(),
+			vec![root.join("src"), root.join("src/nested")]
+		);
+		assert_eq!(shallow.unwrap(), vec![root.join("src/small.rs")]);
+		assert_eq!(
+			ranked.unwrap(),
+			vec![root.join("src"), root.join("src/small.rs")]
+		);
+	}
+
+	#[test]
+	fn test_upward_search_stops_at_boundary() {
+		let base = env::temp_dir()
+			.join(format!("whers-up-{}", std::process::id()));
+		let project = base.join("project");
+		fs::create_dir_all(project.join(".git")).unwrap();
+		fs::create_dir_all(project.join("src")).unwrap();
+		fs::write(base.join("Cargo.toml"), "").unwrap();
+		fs::write(project.join("Cargo.toml"), "").unwrap();
+		fs::write(project.join("src/main.rs"), "").unwrap();
+		let project = fs::canonicalize(&project).unwrap();
+
+		let up = Search::default()
+			.with_pattern(r"^Cargo\.toml$")
+			.with_direction(Direction::Up)
+			.execute_in(project.join("src"));
+		let both = Search::default()
+			.with_pattern(r"\.(rs|toml)$")
+			.with_direction(Direction::Both)
+			.execute_in(project.join("src"));
+		fs::remove_dir_all(&base).unwrap();
+
+		assert_eq!(up.unwrap(), vec![project.join("Cargo.toml")]);
+		assert_eq!(
+			both.unwrap(),
+			vec![
+				project.join("src/main.rs"),
+				project.join("Cargo.toml")
+			]
+		);
+	}
+}
@@ -1,16 +1,260 @@
 // mod cli;
-use whers::data::*;
+use clap::{Parser, Subcommand};
+use numba::units::Bytes;
+use std::{path::PathBuf, process, time::Duration};
+use whers::{data::*, parse};
+
+#[derive(Parser)]
+#[command(version, about, long_about = None)]
+#[command(args_conflicts_with_subcommands = true)]
+struct Cli {
+	#[command(subcommand)]
+	command: Option<Commands>,
+
+	/// Fuzzy search below the current directory, best matches first
+	query: Vec<String>,
+
+	/// Number of fuzzy matches to show
+	#[arg(short = 'n', long, default_value_t = 10)]
+	count: isize,
+}
+
+#[derive(Subcommand)]
+enum Commands {
+	/// Find files and directories below the current directory
+	Find {
+		/// Regular expressions every name must match
+		patterns: Vec<String>,
+
+		/// Only report files (f), directories (d) or symlinks (l)
+		#[arg(short = 't', long = "type", value_parser = str::parse::<PathType>)]
+		path_type: Option<PathType>,
+
+		/// Only report entries at least this big, e.g. 10k or 2M
+		#[arg(long, value_parser = parse_size)]
+		min_size: Option<u64>,
+
+		/// Only report entries at most this big
+		#[arg(long, value_parser = parse_size)]
+		max_size: Option<u64>,
+
+		/// Only report entries modified within this long, e.g. 2h
+		#[arg(long, value_parser = parse_age)]
+		within: Option<Duration>,
+
+		/// Maximum depth to descend
+		#[arg(short = 'd', long)]
+		max_depth: Option<usize>,
+
+		/// Search down, up to the project root, or both
+		#[arg(
+			short = 'D',
+			long,
+			default_value = "down",
+			value_parser = str::parse::<Direction>
+		)]
+		direction: Direction,
+
+		/// Include hidden entries
+		#[arg(short = 'H', long)]
+		hidden: bool,
+
+		/// Skip entries matching these globs
+		#[arg(short = 'E', long)]
+		exclude: Vec<String>,
+
+		/// Answer from the cached index, refreshing it first
+		#[arg(short = 'i', long)]
+		index: bool,
+
+		/// Rank names by fuzzy match quality instead of regex
+		#[arg(short = 'z', long)]
+		fuzzy: bool,
+
+		/// Also match members of zip and tar(.gz) archives
+		#[arg(short = 'A', long)]
+		archives: bool,
+
+		/// Stop after this many hits
+		#[arg(short, long)]
+		limit: Option<String>,
+	},
+
+	/// Find files with identical contents below the current directory
+	#[command(alias = "dupes")]
+	Duplicates {
+		/// Regular expressions every name must match
+		patterns: Vec<String>,
+
+		/// Only compare files at least this big, e.g. 1M
+		#[arg(long, value_parser = parse_size)]
+		min_size: Option<u64>,
+
+		/// Maximum depth to descend
+		#[arg(short = 'd', long)]
+		max_depth: Option<usize>,
+
+		/// Include hidden entries
+		#[arg(short = 'H', long)]
+		hidden: bool,
+
+		/// Skip entries matching these globs
+		#[arg(short = 'E', long)]
+		exclude: Vec<String>,
+
+		/// Show at most this many sets
+		#[arg(short, long)]
+		limit: Option<String>,
+	},
+
+	/// Build or refresh the cached file-name index for a directory
+	Index {
+		/// Directory to index
+		#[arg(default_value = ".")]
+		root: PathBuf,
+	},
+
+	/// Find executables on PATH, like `which -a`
+	Exec {
+		/// Command names or regular expressions to look up
+		#[arg(required = true)]
+		patterns: Vec<String>,
+
+		/// Match names regardless of case, as always on Windows
+		#[arg(short, long)]
+		ignore_case: bool,
+
+		/// Stop after this many hits
+		#[arg(short, long)]
+		limit: Option<String>,
+
+		/// Print nothing, only set the exit code
+		#[arg(short, long)]
+		quiet: bool,
+	},
+}
 
 fn main() -> anyhow::Result<()> {
 	logline::init();
-	numba::test();
-	Search::test();
+
+	let cli = Cli::parse();
+	match cli.command {
+		Some(Commands::Find {
+			patterns,
+			path_type,
+			min_size,
+			max_size,
+			within,
+			max_depth,
+			direction,
+			hidden,
+			exclude,
+			index,
+			fuzzy,
+			archives,
+			limit,
+		}) => {
+			let mut search = Search::default()
+				.with_patterns(patterns)
+				.with_exclude(exclude)
+				.with_direction(direction);
+			if let Some(path_type) = path_type {
+				search = search.with_type(path_type);
+			}
+			if let Some(bytes) = min_size {
+				search = search.with_min_size(bytes);
+			}
+			if let Some(bytes) = max_size {
+				search = search.with_max_size(bytes);
+			}
+			if let Some(age) = within {
+				search = search.modified_within(age);
+			}
+			if let Some(depth) = max_depth {
+				search = search.with_max_depth(depth);
+			}
+			if hidden {
+				search = search.with_hidden();
+			}
+			if index {
+				search = search.indexed();
+			}
+			if fuzzy {
+				search = search.fuzzy();
+			}
+			if archives {
+				search = search.with_archives();
+			}
+			if let Some(limit) = limit {
+				search = search.with_limit(limit);
+			}
+			find(&search)?;
+		}
+		Some(Commands::Duplicates {
+			patterns,
+			min_size,
+			max_depth,
+			hidden,
+			exclude,
+			limit,
+		}) => {
+			let mut search = Search::default()
+				.with_patterns(patterns)
+				.with_exclude(exclude);
+			if let Some(bytes) = min_size {
+				search = search.with_min_size(bytes);
+			}
+			if let Some(depth) = max_depth {
+				search = search.with_max_depth(depth);
+			}
+			if hidden {
+				search = search.with_hidden();
+			}
+			if let Some(limit) = limit {
+				search = search.with_limit(limit);
+			}
+			duplicates(&search)?;
+		}
+		Some(Commands::Index { root }) => {
+			let index = Index::open(&root)?;
+			println!(
+				"Indexed {} entries under {}",
+				index.len(),
+				index.root.display()
+			);
+		}
+		Some(Commands::Exec {
+			patterns,
+			ignore_case,
+			limit,
+			quiet,
+		}) => {
+			let mut search = Search::default()
+				.with_patterns(patterns)
+				.with_case_sensitivity(!ignore_case);
+			if let Some(limit) = limit {
+				search = search.with_limit(limit);
+			}
+			exec(&search, quiet);
+		}
+		None if !cli.query.is_empty() => {
+			let search = Search::default()
+				.with_patterns(cli.query)
+				.fuzzy()
+				.with_limit(cli.count);
+			find(&search)?;
+		}
+		None => {
+			numba::test();
+			Search::test();
+		}
+	}
 
 	// let result = whers::types::Number;
 	// logline::info!("{:#?}", result);
 
 	// .with_pattern("cargo.toml")
-	// .with_direction("both")
+	// .with_direction(Direction::Both)
 	// .with_limit(2)
 	// .with_depth(1)
 	// .exclude(vec!["target".to_string()]);
@@ -21,3 +265,77 @@ fn main() -> anyhow::Result<()> {
 
 	Ok(())
 }
+
+fn find(search: &Search) -> anyhow::Result<()> {
+	match search.execute() {
+		Ok(paths) => {
+			for path in paths {
+				println!("{}", path.display());
+			}
+			Ok(())
+		}
+		Err(SearchError::NoResults) => process::exit(1),
+		Err(e) => Err(e.into()),
+	}
+}
+
+fn duplicates(search: &Search) -> anyhow::Result<()> {
+	let sets = match search.duplicates() {
+		Ok(sets) => sets,
+		Err(SearchError::NoResults) => process::exit(1),
+		Err(e) => return Err(e.into()),
+	};
+
+	for set in &sets {
+		println!(
+			"{} x {} ({} reclaimable)",
+			set.paths.len(),
+			Bytes::new(set.size),
+			Bytes::new(set.reclaimable())
+		);
+		for path in &set.paths {
+			println!("  {}", path.display());
+		}
+	}
+	let total: u64 = sets.iter().map(Duplicates::reclaimable).sum();
+	println!(
+		"{} duplicate sets, {} reclaimable",
+		sets.len(),
+		Bytes::new(total)
+	);
+	Ok(())
+}
+
+fn parse_size(s: &str) -> Result<u64, String> {
+	parse::to_bytes(s).ok_or_else(|| format!("invalid size: {s}"))
+}
+
+fn parse_age(s: &str) -> Result<Duration, String> {
+	parse::to_duration(s).ok_or_else(|| format!("invalid age: {s}"))
+}
+
+fn exec(search: &Search, quiet: bool) {
+	let hits = match search.executables() {
+		Ok(hits) => hits,
+		Err(e) => {
+			if !quiet {
+				eprintln!("{}", e);
+			}
+			process::exit(1);
+		}
+	};
+
+	if quiet {
+		return;
+	}
+	for hit in hits {
+		println!("{}", hit.path.display());
+		if let Some(winner) = &hit.shadowed_by {
+			eprintln!(
+				"warning: {} is shadowed by {}",
+				hit.path.display(),
+				winner.display()
+			);
+		}
+	}
+}
@@ -1,3 +1,5 @@
+mod size;
 mod usize;
 
+pub use size::{to_bytes, to_duration};
 pub use usize::to_usize;
@@ -0,0 +1,93 @@
+use std::time::Duration;
+
+/// Parses a size such as `512`, `10k` or `1.5MiB` into bytes.
+///
+/// Units are binary, so `1k` is 1024 bytes.
+pub fn to_bytes<S: AsRef<str>>(size: S) -> Option<u64> {
+	let (number, unit) = split_unit(size.as_ref());
+	let unit = unit.to_lowercase();
+	let unit = unit.trim_end_matches('b').trim_end_matches('i');
+	let power = match unit {
+		"" => 0,
+		"k" => 1,
+		"m" => 2,
+		"g" => 3,
+		"t" => 4,
+		_ => return None,
+	};
+
+	Some((number? * 1024f64.powi(power)) as u64)
+}
+
+/// Parses an age such as `90`, `30m`, `2h` or `7d`.
+///
+/// A bare number is taken as seconds.
+pub fn to_duration<S: AsRef<str>>(age: S) -> Option<Duration> {
+	let (number, unit) = split_unit(age.as_ref());
+	let seconds = match unit.to_lowercase().as_str() {
+		"" | "s" => 1.0,
+		"m" => 60.0,
+		"h" => 3600.0,
+		"d" => 86400.0,
+		"w" => 604800.0,
+		_ => return None,
+	};
+
+	// Ages too long for a Duration are rejected like any bad input
+	Duration::try_from_secs_f64(number? * seconds).ok()
+}
+
+fn split_unit(s: &str) -> (Option<f64>, &str) {
+	let s = s.trim();
+	let at = s
+		.find(|c: char| !(c.is_ascii_digit() || c == '.'))
+		.unwrap_or(s.len());
+	let number = s[..at].parse().ok().filter(|n: &f64| *n >= 0.0);
+
+	(number, s[at..].trim())
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn test_to_bytes_uses_binary_units() {
+		assert_eq!(to_bytes("512"), Some(512));
+		assert_eq!(to_bytes("10k"), Some(10 * 1024));
+		assert_eq!(to_bytes("1.5MiB"), Some(3 * 512 * 1024));
+		assert_eq!(to_bytes(" 2 GB "), Some(2 << 30));
+		assert_eq!(to_bytes("1x"), None);
+		assert_eq!(to_bytes("-1k"), None);
+		assert_eq!(to_bytes("k"), None);
+	}
+
+	#[test]
+	fn test_to_duration_units() {
+		assert_eq!(to_duration("90"), Some(Duration::from_secs(90)));
+		assert_eq!(
+			to_duration("30m"),
+			Some(Duration::from_secs(1800))
+		);
+		assert_eq!(
+			to_duration("2H"),
+			Some(Duration::from_secs(7200))
+		);
+		assert_eq!(
+			to_duration("1.5d"),
+			Some(Duration::from_secs(129_600))
+		);
+		assert_eq!(
+			to_duration("1w"),
+			Some(Duration::from_secs(604_800))
+		);
+		assert_eq!(to_duration("3y"), None);
+		assert_eq!(to_duration(""), None);
+	}
+
+	#[test]
+	fn test_to_duration_rejects_overflow() {
+		assert_eq!(to_duration("99999999999999999999d"), None);
+		assert_eq!(to_duration("1e400"), None);
+	}
+}